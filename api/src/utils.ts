@@ -516,6 +516,7 @@ export function getReputationLevel(points: number): {
  */
 export function formatBountyStatus(status: string): string {
   const statusMap: { [key: string]: string } = {
+    'draft': 'Draft (awaiting funding)',
     'open': 'Open for Workers',
     'in_progress': 'In Progress',
     'completed': 'Completed',
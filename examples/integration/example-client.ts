@@ -27,7 +27,7 @@ import {
   TransactionSignature,
 } from "@solana/web3.js";
 import { createHash } from "crypto";
-import { BOUNTYGRAPH_IDL, BOUNTYGRAPH_PROGRAM_ID } from "../../sdk/src";
+import { BOUNTYGRAPH_IDL, BOUNTYGRAPH_PROGRAM_ID, findProtocolConfigPda } from "../../sdk/src";
 
 class KeypairWallet implements anchor.Wallet {
   constructor(readonly payer: Keypair) {}
@@ -113,8 +113,12 @@ async function main() {
   const programId = BOUNTYGRAPH_PROGRAM_ID;
   const program = new anchor.Program(BOUNTYGRAPH_IDL, programId, provider);
 
+  const graphId = 0n;
+  const graphIdLe = Buffer.alloc(8);
+  graphIdLe.writeBigUInt64LE(graphId);
+
   const [graphPda] = PublicKey.findProgramAddressSync(
-    [Buffer.from("graph"), authority.publicKey.toBuffer()],
+    [Buffer.from("graph"), authority.publicKey.toBuffer(), graphIdLe],
     programId
   );
 
@@ -128,7 +132,13 @@ async function main() {
   // 1) Initialize graph (idempotent-ish: will fail if already exists; in production you can fetch-first)
   try {
     const sig = await program.methods
-      .initializeGraph({ maxDependenciesPerTask: 16 })
+      .initializeGraph({
+        graphId: new anchor.BN(graphId.toString()),
+        maxDependenciesPerTask: 16,
+        creationPolicy: { authorityOnly: {} },
+        creationDepositLamports: new anchor.BN(0),
+        guardian: null,
+      })
       .accounts({
         graph: graphPda,
         authority: authority.publicKey,
@@ -161,11 +171,32 @@ async function main() {
       taskId: new anchor.BN(taskId.toString()),
       rewardLamports: new anchor.BN(rewardLamports.toString()),
       dependencies: [],
+      dependencyGroups: [],
+      externalDependencies: [],
+      claimDurationSlots: new anchor.BN(0),
+      competitionDeadlineSlot: new anchor.BN(0),
+      milestones: [],
+      vesting: null,
+      crowdfunded: false,
+      requireFunding: false,
+      epic: false,
+      parentTaskId: null,
+      specUri: '',
+      specHash: Array(32).fill(0),
+      category: 0,
+      tags: [],
+      maxCompletions: 0,
+      recurrenceIntervalSlots: new anchor.BN(0),
+      recurrenceEndSlot: new anchor.BN(0),
+      verifiers: [],
+      verifierThreshold: 0,
     })
     .accounts({
       graph: graphPda,
       authority: authority.publicKey,
       creator: creator.publicKey,
+      creatorAllowlist: null,
+      parent: null,
       task: taskPda,
       systemProgram: SystemProgram.programId,
     })
@@ -185,6 +216,7 @@ async function main() {
   const fundSig = await program.methods
     .fundTask(new anchor.BN(rewardLamports.toString()))
     .accounts({
+      graph: graphPda,
       task: taskPda,
       escrow: escrowPda,
       funder: creator.publicKey,
@@ -211,8 +243,10 @@ async function main() {
     .submitReceipt({
       workHash: Array.from(workHash),
       uri,
+      contributors: [],
     })
     .accounts({
+      graph: graphPda,
       task: taskPda,
       receipt: receiptPda,
       agent: agent.publicKey,
@@ -227,11 +261,20 @@ async function main() {
   // 5) Claim reward (release funds from escrow PDA to agent)
   const before = await connection.getBalance(agent.publicKey, "confirmed");
 
+  // Fees go to the treasury recorded in the protocol config, set up once at deploy time.
+  const [protocolConfigPda] = findProtocolConfigPda(program.programId);
+  const protocolConfig: any = await program.account.protocolConfig.fetch(protocolConfigPda);
+
   const claimSig = await program.methods
     .claimReward()
     .accounts({
+      graph: graphPda,
       task: taskPda,
       escrow: escrowPda,
+      protocolConfig: protocolConfigPda,
+      treasury: protocolConfig.treasury,
+      graphAuthority: authority.publicKey,
+      receipt: null,
       agent: agent.publicKey,
       systemProgram: SystemProgram.programId,
     })
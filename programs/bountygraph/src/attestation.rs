@@ -0,0 +1,89 @@
+//! Oracle attestations: Ed25519 signatures verified by the native precompile and found through
+//! the instructions sysvar.
+//!
+//! The program cannot check signatures itself within its compute budget. Instead the transaction
+//! carries Ed25519 program instructions, and `attest_receipt` inspects them to see which keys
+//! signed which messages.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::load_instruction_at_checked;
+
+// Layout of an Ed25519 precompile instruction: a signature count and a padding byte, then one
+// 14-byte block of seven u16 offsets per signature.
+const OFFSETS_START: usize = 2;
+const OFFSETS_SIZE: usize = 14;
+const PUBKEY_OFFSET: usize = 4;
+const PUBKEY_INSTRUCTION: usize = 6;
+const MESSAGE_OFFSET: usize = 8;
+const MESSAGE_SIZE: usize = 10;
+const MESSAGE_INSTRUCTION: usize = 12;
+/// Instruction index meaning "this instruction's own data".
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// What a verifier signs to attest a receipt: `task || agent || work_hash`.
+pub fn attestation_message(task: &Pubkey, agent: &Pubkey, work_hash: &[u8; 32]) -> [u8; 96] {
+    let mut message = [0u8; 96];
+    message[..32].copy_from_slice(task.as_ref());
+    message[32..64].copy_from_slice(agent.as_ref());
+    message[64..].copy_from_slice(work_hash);
+    message
+}
+
+/// Number of distinct `verifiers` whose signature over `message` an Ed25519 precompile
+/// instruction in this transaction verified. The runtime fails the whole transaction if any
+/// precompile signature is invalid, so finding the instruction is enough; only entries whose key
+/// and message live in the instruction's own data count, as those are the bytes it verified.
+pub fn count_attestations(
+    instructions: &AccountInfo,
+    verifiers: &[Pubkey],
+    message: &[u8],
+) -> Result<usize> {
+    let instruction_count = {
+        let data = instructions.try_borrow_data()?;
+        u16::from_le_bytes([data[0], data[1]]) as usize
+    };
+
+    let mut attested = vec![false; verifiers.len()];
+    for index in 0..instruction_count {
+        let instruction = load_instruction_at_checked(index, instructions)?;
+        if instruction.program_id != ed25519_program::ID {
+            continue;
+        }
+        for (pubkey, signed) in signed_entries(&instruction.data) {
+            if signed != message {
+                continue;
+            }
+            if let Some(position) = verifiers.iter().position(|v| v.as_ref() == pubkey) {
+                attested[position] = true;
+            }
+        }
+    }
+    Ok(attested.iter().filter(|a| **a).count())
+}
+
+/// (public key, message) pairs of an Ed25519 precompile instruction's self-contained entries.
+fn signed_entries(data: &[u8]) -> Vec<(&[u8], &[u8])> {
+    let read_u16 = |at: usize| {
+        data.get(at..at + 2)
+            .map(|b| u16::from_le_bytes([b[0], b[1]]))
+    };
+    let count = data.first().copied().unwrap_or(0) as usize;
+    (0..count)
+        .filter_map(|i| {
+            let block = OFFSETS_START + i * OFFSETS_SIZE;
+            if read_u16(block + PUBKEY_INSTRUCTION)? != CURRENT_INSTRUCTION
+                || read_u16(block + MESSAGE_INSTRUCTION)? != CURRENT_INSTRUCTION
+            {
+                return None;
+            }
+            let pubkey_at = read_u16(block + PUBKEY_OFFSET)? as usize;
+            let message_at = read_u16(block + MESSAGE_OFFSET)? as usize;
+            let message_len = read_u16(block + MESSAGE_SIZE)? as usize;
+            Some((
+                data.get(pubkey_at..pubkey_at + 32)?,
+                data.get(message_at..message_at + message_len)?,
+            ))
+        })
+        .collect()
+}
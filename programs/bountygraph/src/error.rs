@@ -2,7 +2,7 @@ use anchor_lang::prelude::*;
 
 #[error_code]
 pub enum BountyGraphError {
-    #[msg("Invalid graph configuration (max_dependencies_per_task must be > 0)")]
+    #[msg("Invalid graph configuration (max_dependencies_per_task must be > 0, reward bounds must be ordered)")]
     InvalidConfig,
     #[msg("Invalid reward amount (must be > 0 and within declared bounds)")]
     InvalidReward,
@@ -54,4 +54,126 @@ pub enum BountyGraphError {
     InvalidTaskStatus,
     #[msg("Invalid split percentage")]
     InvalidSplit,
+    #[msg("Signer is not allowed to create tasks under the graph's creation policy")]
+    UnauthorizedCreator,
+    #[msg("Graph is paused")]
+    GraphPaused,
+    #[msg("Only the graph authority or guardian can pause the graph")]
+    UnauthorizedPause,
+    #[msg("Account is already at the current layout version")]
+    AlreadyMigrated,
+    #[msg("Account is not a BountyGraph account of the expected type")]
+    InvalidAccountLayout,
+    #[msg("Task is not in a terminal state (paid, cancelled or resolved)")]
+    TaskNotTerminal,
+    #[msg("Retention period has not elapsed")]
+    RetentionPeriodActive,
+    #[msg("Task is reserved by another worker")]
+    TaskAlreadyClaimed,
+    #[msg("Signer is not the task's assignee")]
+    NotAssignee,
+    #[msg("Task does not use claim-based assignment")]
+    AssignmentDisabled,
+    #[msg("Task is not a competition")]
+    NotACompetition,
+    #[msg("Competition submission window is closed")]
+    SubmissionWindowClosed,
+    #[msg("Competition submission window is still open")]
+    SubmissionWindowOpen,
+    #[msg("Invalid winner list (1..=MAX_WINNERS receipts, non-zero weights summing to 10000 bps)")]
+    InvalidWinners,
+    #[msg("Only the task creator or graph authority can select winners")]
+    UnauthorizedWinnerSelection,
+    #[msg("Receipt is not a winning receipt for this task")]
+    NotAWinner,
+    #[msg("Reward already claimed")]
+    RewardAlreadyClaimed,
+    #[msg("Invalid contributors (unique workers incl. the submitter, non-zero shares summing to 10000 bps)")]
+    InvalidContributors,
+    #[msg("Not all contributors have accepted their share")]
+    ContributionsPending,
+    #[msg("Invalid milestones (at most MAX_MILESTONES non-zero amounts summing to the reward)")]
+    InvalidMilestones,
+    #[msg("Milestone is not the next one awaiting submission or approval")]
+    MilestoneNotReady,
+    #[msg("Milestone tasks are submitted and paid per milestone")]
+    MilestoneTask,
+    #[msg("Escrow balance does not cover this payout")]
+    InsufficientEscrow,
+    #[msg("Vesting rewards are paid out through withdraw_vested")]
+    VestingTask,
+    #[msg("Task has no vesting schedule")]
+    NotVesting,
+    #[msg("Nothing has vested since the last withdrawal")]
+    NothingVested,
+    #[msg("Fee exceeds ProtocolConfig::MAX_FEE_BPS")]
+    FeeTooHigh,
+    #[msg("Treasury account does not match the protocol config")]
+    InvalidTreasury,
+    #[msg("Signer is not the protocol admin")]
+    UnauthorizedAdmin,
+    #[msg("Only the creator can raise the reward of a task that is not crowdfunded")]
+    UnauthorizedFunder,
+    #[msg("Task is a draft until its reward is fully funded")]
+    TaskNotFunded,
+    #[msg("Invalid dependency groups (disjoint subsets of dependencies, 1 <= threshold <= size)")]
+    InvalidDependencyGroups,
+    #[msg("Cross-graph dependency has not been approved by its graph's authority")]
+    ExternalDependencyNotApproved,
+    #[msg("Dependency is completed but its dispute state does not satisfy the graph's policy")]
+    DependencyDisputed,
+    #[msg("Epics are completed by roll_up, not by submissions")]
+    EpicTask,
+    #[msg("Invalid epic: parent must be an open epic in this graph, owned by the same creator, with room for more children")]
+    InvalidEpic,
+    #[msg("Children must be all of the epic's child tasks, in increasing task id order")]
+    InvalidEpicChildren,
+    #[msg("Not all of the epic's children are completed")]
+    ChildNotCompleted,
+    #[msg("Invalid batch: too many tasks, a repeated task id, a task account that is not its PDA, or a dependency on a later task")]
+    InvalidBatch,
+    #[msg(
+        "Invalid template: 1 to 5 tasks, each depending only on earlier tasks in increasing order"
+    )]
+    InvalidTemplate,
+    #[msg("Invalid spec: URI longer than 200 bytes, or more than 4 tags or a zero tag")]
+    InvalidSpec,
+    #[msg("The worker engaged on this task must countersign the amendment")]
+    WorkerConsentRequired,
+    #[msg("Repeatable tasks are completed with submit_completion")]
+    RepeatableTask,
+    #[msg("Task is not repeatable")]
+    NotRepeatable,
+    #[msg("The next completion is not due until the recurrence interval has passed")]
+    CompletionNotDue,
+    #[msg("Revealed work does not match the receipt commitment")]
+    CommitmentMismatch,
+    #[msg("The reveal window for this commitment has closed")]
+    RevealWindowClosed,
+    #[msg("Committed receipts can still be revealed")]
+    RevealWindowOpen,
+    #[msg(
+        "Invalid verifiers: at most 5 distinct keys with a threshold between 1 and their number"
+    )]
+    InvalidVerifiers,
+    #[msg("Fewer than the threshold of the task's verifiers attested this receipt")]
+    MissingAttestation,
+    #[msg("A completion is awaiting the creator's approval")]
+    CompletionPending,
+    #[msg("No completion from this worker is awaiting approval")]
+    NoPendingCompletion,
+    #[msg("Competition entries are submitted with commit_receipt and reveal_receipt")]
+    CommitRevealRequired,
+    #[msg("Submitted work is still awaiting review")]
+    PendingSubmissions,
+    #[msg(
+        "Funders other than the creator must pass their Deposit account; the creator passes none"
+    )]
+    InvalidDeposit,
+    #[msg("Deposits from other funders are still open; close them with refund_deposit first")]
+    DepositsOutstanding,
+    #[msg("Competition results cannot be disputed; winners are paid by their selected weights")]
+    CompetitionNotDisputable,
+    #[msg("The task has been funded; pass its escrow")]
+    EscrowRequired,
 }
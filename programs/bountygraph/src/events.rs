@@ -0,0 +1,64 @@
+//! Events emitted by the program for indexers and accounting.
+
+use anchor_lang::prelude::*;
+
+use crate::state::Task;
+
+/// Fees withheld from a worker payout. Emitted once per payout, including when both fees are 0.
+#[event]
+pub struct FeesCollected {
+    pub task: Pubkey,
+    /// Amount leaving escrow for the worker side before fees.
+    pub gross_lamports: u64,
+    pub protocol_fee_lamports: u64,
+    pub graph_fee_lamports: u64,
+    pub treasury: Pubkey,
+    pub graph_fee_recipient: Pubkey,
+}
+
+/// An open task's reward was raised and the extra lamports deposited into its escrow.
+#[event]
+pub struct RewardIncreased {
+    pub task: Pubkey,
+    pub funder: Pubkey,
+    pub added_lamports: u64,
+    pub new_reward_lamports: u64,
+}
+
+/// Emitted for every new task so indexers can filter by category and tags without decoding
+/// task accounts.
+#[event]
+pub struct TaskCreated {
+    pub graph: Pubkey,
+    pub task: Pubkey,
+    pub task_id: u64,
+    pub creator: Pubkey,
+    pub category: u16,
+    pub tags: [u16; Task::MAX_TAGS],
+    pub spec_hash: [u8; 32],
+}
+
+/// One per spec revision, so disputes can be checked against the spec in force at the time.
+#[event]
+pub struct TaskSpecAmended {
+    pub task: Pubkey,
+    pub revision: u32,
+    pub previous_spec_hash: [u8; 32],
+    pub spec_hash: [u8; 32],
+    pub spec_uri: String,
+    /// The engaged worker who countersigned, if any.
+    pub countersigned_by: Option<Pubkey>,
+    pub slot: u64,
+}
+
+/// A receipt completed its task on the strength of oracle attestations.
+#[event]
+pub struct ReceiptAttested {
+    pub task: Pubkey,
+    pub receipt: Pubkey,
+    pub agent: Pubkey,
+    pub work_hash: [u8; 32],
+    /// Distinct task verifiers whose signatures were found in the transaction.
+    pub attestations: u8,
+    pub slot: u64,
+}
@@ -1,10 +1,22 @@
 use anchor_lang::prelude::*;
 
+pub mod attestation;
 pub mod error;
+pub mod events;
+pub mod migration;
+pub mod payout;
 pub mod state;
+pub mod task_params;
+pub mod task_view;
 
+use crate::attestation::*;
 use crate::error::BountyGraphError;
+use crate::events::*;
+use crate::migration::*;
+use crate::payout::*;
 use crate::state::*;
+use crate::task_params::*;
+use crate::task_view::*;
 
 declare_id!("Ghm5zPnHy5yJwQ6P22NYgNVrqPokDqAV3otdut3DSbSS");
 
@@ -12,6 +24,44 @@ declare_id!("Ghm5zPnHy5yJwQ6P22NYgNVrqPokDqAV3otdut3DSbSS");
 pub mod bountygraph {
     use super::*;
 
+    /// Create the protocol fee config. Only the program's upgrade authority can do this.
+    pub fn initialize_protocol_config(
+        ctx: Context<InitializeProtocolConfig>,
+        params: InitializeProtocolConfigParams,
+    ) -> Result<()> {
+        require!(
+            params.fee_bps <= ProtocolConfig::MAX_FEE_BPS,
+            BountyGraphError::FeeTooHigh
+        );
+
+        let config = &mut ctx.accounts.protocol_config;
+        config.admin = ctx.accounts.admin.key();
+        config.treasury = params.treasury;
+        config.fee_bps = params.fee_bps;
+        config.bump = ctx.bumps.protocol_config;
+        config.version = ACCOUNT_VERSION;
+
+        Ok(())
+    }
+
+    pub fn update_protocol_config(
+        ctx: Context<UpdateProtocolConfig>,
+        params: UpdateProtocolConfigParams,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.protocol_config;
+        let fee_bps = params.fee_bps.unwrap_or(config.fee_bps);
+        require!(
+            fee_bps <= ProtocolConfig::MAX_FEE_BPS,
+            BountyGraphError::FeeTooHigh
+        );
+
+        config.fee_bps = fee_bps;
+        config.treasury = params.treasury.unwrap_or(config.treasury);
+        config.admin = params.admin.unwrap_or(config.admin);
+
+        Ok(())
+    }
+
     pub fn initialize_graph(
         ctx: Context<InitializeGraph>,
         params: InitializeGraphParams,
@@ -20,109 +70,297 @@ pub mod bountygraph {
             params.max_dependencies_per_task > 0,
             BountyGraphError::InvalidConfig
         );
+        require!(
+            params.graph_id != Graph::LEGACY_GRAPH_ID,
+            BountyGraphError::InvalidConfig
+        );
 
         let graph = &mut ctx.accounts.graph;
         graph.authority = ctx.accounts.authority.key();
+        graph.graph_id = params.graph_id;
         graph.bump = ctx.bumps.graph;
+        graph.version = ACCOUNT_VERSION;
         graph.max_dependencies_per_task = params.max_dependencies_per_task;
         graph.task_count = 0;
+        graph.creation_policy = params.creation_policy;
+        graph.creation_deposit_lamports = params.creation_deposit_lamports;
+        graph.guardian = params.guardian;
+        graph.paused = false;
+        graph.pause_reason = 0;
+        graph.paused_at_slot = 0;
+        graph.min_reward_lamports = Graph::DEFAULT_MIN_REWARD_LAMPORTS;
+        graph.max_reward_lamports = 0;
+        graph.config_version = 1;
+        graph.close_retention_slots = Graph::DEFAULT_CLOSE_RETENTION_SLOTS;
+        graph.graph_fee_bps = 0;
+        graph.require_dependent_approval = false;
+        graph.dependency_dispute_policy = Graph::DEFAULT_DEPENDENCY_DISPUTE_POLICY;
 
         Ok(())
     }
 
-    pub fn create_task<'a>(
-        ctx: Context<'_, '_, 'a, 'a, CreateTask<'a>>,
-        params: CreateTaskParams,
+    pub fn update_graph_config(
+        ctx: Context<UpdateGraphConfig>,
+        params: UpdateGraphConfigParams,
     ) -> Result<()> {
-        const MIN_REWARD_LAMPORTS: u64 = 1_000;
-
-        let graph_key = ctx.accounts.graph.key();
-        let max_deps = ctx.accounts.graph.max_dependencies_per_task;
-        let deps = params.dependencies.clone();
+        let graph = &mut ctx.accounts.graph;
 
+        let max_deps = params
+            .max_dependencies_per_task
+            .unwrap_or(graph.max_dependencies_per_task);
+        let min_reward = params
+            .min_reward_lamports
+            .unwrap_or(graph.min_reward_lamports);
+        let max_reward = params
+            .max_reward_lamports
+            .unwrap_or(graph.max_reward_lamports);
+        let close_retention_slots = params
+            .close_retention_slots
+            .unwrap_or(graph.close_retention_slots);
+        let graph_fee_bps = params.graph_fee_bps.unwrap_or(graph.graph_fee_bps);
+
+        require!(max_deps > 0, BountyGraphError::InvalidConfig);
+        require!(min_reward > 0, BountyGraphError::InvalidConfig);
         require!(
-            params.reward_lamports >= MIN_REWARD_LAMPORTS,
-            BountyGraphError::InvalidReward
+            max_reward == 0 || max_reward >= min_reward,
+            BountyGraphError::InvalidConfig
+        );
+        require!(
+            graph_fee_bps <= ProtocolConfig::MAX_FEE_BPS,
+            BountyGraphError::FeeTooHigh
         );
+        let dependency_dispute_policy = params
+            .dependency_dispute_policy
+            .unwrap_or(graph.dependency_dispute_policy);
         require!(
-            (deps.len() as u16) <= max_deps,
-            BountyGraphError::TooManyDependencies
+            dependency_dispute_policy.min_worker_pct <= 100,
+            BountyGraphError::InvalidConfig
         );
 
-        // SECURITY: Validate dependency array is sorted and contains no self-references
-        // Sorting requirement ensures O(log n) binary search during dependency queries
-        let mut prev: Option<u64> = None;
-        for dep in deps.iter() {
-            require!(*dep != params.task_id, BountyGraphError::InvalidDependency);
-            if let Some(p) = prev {
-                require!(*dep > p, BountyGraphError::InvalidDependency);
-            }
-            prev = Some(*dep);
+        // Lowering max_dependencies_per_task only affects new tasks; existing tasks keep their edges.
+        graph.max_dependencies_per_task = max_deps;
+        graph.min_reward_lamports = min_reward;
+        graph.max_reward_lamports = max_reward;
+        graph.close_retention_slots = close_retention_slots;
+        graph.graph_fee_bps = graph_fee_bps;
+        if let Some(require_dependent_approval) = params.require_dependent_approval {
+            graph.require_dependent_approval = require_dependent_approval;
         }
+        graph.dependency_dispute_policy = dependency_dispute_policy;
+        graph.config_version = graph
+            .config_version
+            .checked_add(1)
+            .ok_or(BountyGraphError::ArithmeticOverflow)?;
 
-        if !deps.is_empty() {
-            require!(
-                ctx.remaining_accounts.len() == deps.len(),
-                BountyGraphError::MissingDependencyAccounts
-            );
+        Ok(())
+    }
 
-            // SECURITY: Circular dependency prevention - verify no dependency points back to this task.
-            //
-            // WHY this check exists on-chain:
-            // - The easiest class of cycles to accidentally introduce is a 2-cycle (A depends on B while
-            //   B already depends on A). That can be prevented deterministically at instruction time.
-            // - Full transitive cycle checks require walking the dependency graph, which would either
-            //   require passing a large transitive-closure account set or doing unbounded account loads.
-            //   We keep the on-chain rule bounded and deterministic, while the client/API performs the
-            //   complete DFS-based cycle check before submitting the transaction.
-            for (i, dep_task_info) in ctx.remaining_accounts.iter().enumerate() {
-                let expected_dep_id = deps[i];
-                let dep_task: Account<Task> = Account::try_from(dep_task_info)?;
-
-                // Verify dependency account belongs to same graph
-                require!(
-                    dep_task.graph == graph_key,
-                    BountyGraphError::InvalidDependency
-                );
-                // Verify dependency task ID matches expected (prevents account substitution)
-                require!(
-                    dep_task.task_id == expected_dep_id,
-                    BountyGraphError::InvalidDependency
-                );
+    pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Option<Pubkey>) -> Result<()> {
+        ctx.accounts.graph.guardian = guardian;
+
+        Ok(())
+    }
+
+    /// EMERGENCY STOP: Halts task creation, funding, submissions and payouts.
+    /// Dispute resolution is deliberately left running so funds are never trapped.
+    pub fn pause_graph(ctx: Context<PauseGraph>, reason_code: u16) -> Result<()> {
+        let signer = ctx.accounts.signer.key();
+        let graph = &mut ctx.accounts.graph;
+        require!(
+            signer == graph.authority || graph.guardian == Some(signer),
+            BountyGraphError::UnauthorizedPause
+        );
+
+        graph.paused = true;
+        graph.pause_reason = reason_code;
+        graph.paused_at_slot = Clock::get()?.slot;
+
+        Ok(())
+    }
+
+    /// Only the authority can lift a pause; the guardian is pause-only by design.
+    pub fn unpause_graph(ctx: Context<UnpauseGraph>) -> Result<()> {
+        let graph = &mut ctx.accounts.graph;
+        graph.paused = false;
+        graph.pause_reason = 0;
+        graph.paused_at_slot = 0;
+
+        Ok(())
+    }
+
+    pub fn set_creation_policy(
+        ctx: Context<SetCreationPolicy>,
+        params: SetCreationPolicyParams,
+    ) -> Result<()> {
+        let graph = &mut ctx.accounts.graph;
+        graph.creation_policy = params.creation_policy;
+        graph.creation_deposit_lamports = params.creation_deposit_lamports;
+
+        Ok(())
+    }
 
-                // CRITICAL: Prevent the immediate back-edge (2-cycle).
-                // If any dependency already lists this task, adding (this -> dependency) would create
-                // A -> B and B -> A, which we must reject at the protocol layer.
+    pub fn add_allowed_creator(ctx: Context<AddAllowedCreator>) -> Result<()> {
+        let entry = &mut ctx.accounts.creator_allowlist;
+        entry.graph = ctx.accounts.graph.key();
+        entry.creator = ctx.accounts.creator.key();
+        entry.added_at_slot = Clock::get()?.slot;
+        entry.bump = ctx.bumps.creator_allowlist;
+        entry.version = ACCOUNT_VERSION;
+
+        Ok(())
+    }
+
+    pub fn remove_allowed_creator(_ctx: Context<RemoveAllowedCreator>) -> Result<()> {
+        // Account is closed back to the authority by the `close` constraint.
+        Ok(())
+    }
+
+    pub fn create_task<'a>(
+        ctx: Context<'_, '_, 'a, 'a, CreateTask<'a>>,
+        params: CreateTaskParams,
+    ) -> Result<()> {
+        let graph_key = ctx.accounts.graph.key();
+        let now = Clock::get()?.slot;
+
+        let creator_key = ctx.accounts.creator.key();
+        let authority_signed = ctx
+            .accounts
+            .authority
+            .as_ref()
+            .is_some_and(|a| a.key() == ctx.accounts.graph.authority);
+        let creation_deposit = creation_deposit(
+            &ctx.accounts.graph,
+            graph_key,
+            authority_signed,
+            creator_key,
+            ctx.accounts.creator_allowlist.as_deref(),
+        )?;
+        validate_task_params(&ctx.accounts.graph, &params, now)?;
+
+        // A child registers with its epic, which must stay open until all children are done.
+        match (params.parent_task_id, ctx.accounts.parent.as_mut()) {
+            (Some(parent_id), Some(parent)) => {
                 require!(
-                    !dep_task.dependencies.contains(&params.task_id),
-                    BountyGraphError::CircularDependency
+                    parent.graph == graph_key
+                        && parent.task_id == parent_id
+                        && parent.accepts_child(&creator_key, authority_signed),
+                    BountyGraphError::InvalidEpic
                 );
+                parent.child_count += 1;
             }
-        } else {
-            // No dependencies: verify no dependency accounts provided
+            (None, None) => {}
+            _ => return err!(BountyGraphError::InvalidEpic),
+        }
+
+        let deps = &params.dependencies;
+        let external = &params.external_dependencies;
+
+        // Remaining accounts: one task per dependency, then a (graph, task) pair per external one.
+        require!(
+            ctx.remaining_accounts.len() == deps.len() + 2 * external.len(),
+            BountyGraphError::MissingDependencyAccounts
+        );
+        let (dep_accounts, external_accounts) = ctx.remaining_accounts.split_at(deps.len());
+
+        // SECURITY: Circular dependency prevention - verify no dependency points back to this task.
+        //
+        // WHY this check exists on-chain:
+        // - The easiest class of cycles to accidentally introduce is a 2-cycle (A depends on B while
+        //   B already depends on A). That can be prevented deterministically at instruction time.
+        // - Full transitive cycle checks require walking the dependency graph, which would either
+        //   require passing a large transitive-closure account set or doing unbounded account loads.
+        //   We keep the on-chain rule bounded and deterministic, while the client/API performs the
+        //   complete DFS-based cycle check before submitting the transaction.
+        for (i, dep_task_info) in dep_accounts.iter().enumerate() {
+            let expected_dep_id = deps[i];
+            let dep_task = load_task_view(dep_task_info)?;
+
+            // Verify dependency account belongs to same graph
+            require!(
+                dep_task.graph == graph_key,
+                BountyGraphError::InvalidDependency
+            );
+            // Verify dependency task ID matches expected (prevents account substitution)
+            require!(
+                dep_task.task_id == expected_dep_id,
+                BountyGraphError::InvalidDependency
+            );
+
+            // CRITICAL: Prevent the immediate back-edge (2-cycle).
+            // If any dependency already lists this task, adding (this -> dependency) would create
+            // A -> B and B -> A, which we must reject at the protocol layer.
+            require!(
+                !dep_task.dependencies.contains(&params.task_id),
+                BountyGraphError::CircularDependency
+            );
+        }
+
+        // CROSS-GRAPH EDGES: The foreign graph decides whether its authority must approve the edge
+        // before it can unlock this task. Same-graph edges belong in `dependencies`.
+        let mut external_dependencies: Vec<ExternalDependency> = Vec::with_capacity(external.len());
+        for (edge, pair) in external.iter().zip(external_accounts.chunks(2)) {
+            require!(
+                edge.graph != graph_key
+                    && external_dependencies
+                        .iter()
+                        .all(|d| d.graph != edge.graph || d.task_id != edge.task_id),
+                BountyGraphError::InvalidDependency
+            );
+            let foreign_graph = Account::<Graph>::try_from(&pair[0])?;
+            require_keys_eq!(
+                foreign_graph.key(),
+                edge.graph,
+                BountyGraphError::InvalidDependency
+            );
+            let dep_task = load_task_view(&pair[1])?;
             require!(
-                ctx.remaining_accounts.is_empty(),
-                BountyGraphError::MissingDependencyAccounts
+                dep_task.graph == edge.graph && dep_task.task_id == edge.task_id,
+                BountyGraphError::InvalidDependency
+            );
+            // Same 2-cycle rule as above, across the graph boundary.
+            require!(
+                !dep_task
+                    .external_dependencies
+                    .iter()
+                    .any(|d| d.graph == graph_key && d.task_id == params.task_id),
+                BountyGraphError::CircularDependency
             );
+            external_dependencies.push(ExternalDependency {
+                graph: edge.graph,
+                task_id: edge.task_id,
+                approved: !foreign_graph.require_dependent_approval,
+            });
         }
 
         // Initialize task PDA with validated parameters
-        let task = &mut ctx.accounts.task;
-        task.graph = graph_key;
-        task.task_id = params.task_id;
-        task.creator = ctx.accounts.creator.key();
-        task.reward_lamports = params.reward_lamports;
-        task.status = TaskStatus::Open;
-        task.dispute_status = DisputeStatus::None;
-        task.dependencies = deps;
-        task.created_at_slot = Clock::get()?.slot;
-        task.completed_by = None;
-        task.disputed_by = None;
-        task.dispute_raised_at_slot = 0;
-        task.resolved_by = None;
-        task.dispute_resolved_at_slot = 0;
-        task.worker_award_lamports = 0;
-        task.bump = ctx.bumps.task;
+        let task = new_task(
+            &params,
+            graph_key,
+            &ctx.accounts.graph,
+            creator_key,
+            external_dependencies,
+            creation_deposit,
+            now,
+            ctx.bumps.task,
+        );
+        emit_task_created(&task, ctx.accounts.task.key());
+        ctx.accounts.task.set_inner(task);
+
+        // The deposit sits in the task account on top of rent and is returned when the task is closed.
+        if creation_deposit > 0 {
+            anchor_lang::solana_program::program::invoke(
+                &anchor_lang::solana_program::system_instruction::transfer(
+                    &creator_key,
+                    &ctx.accounts.task.key(),
+                    creation_deposit,
+                ),
+                &[
+                    ctx.accounts.creator.to_account_info(),
+                    ctx.accounts.task.to_account_info(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+            )?;
+        }
 
         // Increment graph task counter with overflow protection
         let graph = &mut ctx.accounts.graph;
@@ -134,15 +372,228 @@ pub mod bountygraph {
         Ok(())
     }
 
+    /// Create up to `Task::MAX_BATCH_TASKS` tasks in one instruction, with the same rules as
+    /// `create_task`. A task may depend on tasks earlier in the batch (topological order); every
+    /// account outside the batch is loaded and validated once, however many tasks reference it.
+    ///
+    /// Remaining accounts: the new task PDAs in `params` order, then each distinct existing
+    /// dependency: same-graph tasks, and for cross-graph edges the foreign graph and task, in any
+    /// order. An epic named as `parent_task_id` must be earlier in the same batch.
+    pub fn create_tasks_batch<'a>(
+        ctx: Context<'_, '_, 'a, 'a, CreateTasksBatch<'a>>,
+        params: Vec<CreateTaskParams>,
+    ) -> Result<()> {
+        let authority_signed = ctx
+            .accounts
+            .authority
+            .as_ref()
+            .is_some_and(|a| a.key() == ctx.accounts.graph.authority);
+        create_tasks(
+            &mut ctx.accounts.graph,
+            authority_signed,
+            &ctx.accounts.creator.to_account_info(),
+            ctx.accounts.creator_allowlist.as_deref(),
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.remaining_accounts,
+            &params,
+        )
+    }
+
+    pub fn create_template(
+        ctx: Context<CreateTemplate>,
+        template_id: u64,
+        tasks: Vec<TemplateTask>,
+    ) -> Result<()> {
+        require!(
+            !tasks.is_empty() && tasks.len() <= GraphTemplate::MAX_TASKS,
+            BountyGraphError::InvalidTemplate
+        );
+        // Edges only point backwards, so every instantiation is acyclic and in batch order.
+        for (i, task) in tasks.iter().enumerate() {
+            let mut prev: Option<u8> = None;
+            for dep in task.dependencies.iter() {
+                require!(
+                    (*dep as usize) < i && prev.map_or(true, |p| *dep > p),
+                    BountyGraphError::InvalidTemplate
+                );
+                prev = Some(*dep);
+            }
+            validate_spec(&task.spec_uri, &task.tags)?;
+        }
+
+        let template = &mut ctx.accounts.template;
+        template.authority = ctx.accounts.authority.key();
+        template.template_id = template_id;
+        template.tasks = tasks;
+        template.bump = ctx.bumps.template;
+        template.version = ACCOUNT_VERSION;
+
+        Ok(())
+    }
+
+    pub fn close_template(_ctx: Context<CloseTemplate>) -> Result<()> {
+        // Account is closed back to the authority by the `close` constraint.
+        Ok(())
+    }
+
+    /// Create a template's tasks in `graph` with ids offset by `params.task_id_offset`, under the
+    /// same rules as `create_tasks_batch`. Anyone allowed to create tasks in the graph may use any
+    /// template. Remaining accounts: the new task PDAs in template order.
+    pub fn instantiate_template<'a>(
+        ctx: Context<'_, '_, 'a, 'a, InstantiateTemplate<'a>>,
+        params: InstantiateTemplateParams,
+    ) -> Result<()> {
+        let offset = params.task_id_offset;
+        let id = |index: usize| {
+            offset
+                .checked_add(index as u64)
+                .ok_or(BountyGraphError::ArithmeticOverflow)
+        };
+
+        let mut batch: Vec<CreateTaskParams> =
+            Vec::with_capacity(ctx.accounts.template.tasks.len());
+        for (i, spec) in ctx.accounts.template.tasks.iter().enumerate() {
+            let reward_lamports = match params.reward_multiplier_bps {
+                Some(bps) => u64::try_from(spec.reward_lamports as u128 * bps as u128 / 10_000)
+                    .map_err(|_| BountyGraphError::ArithmeticOverflow)?,
+                None => spec.reward_lamports,
+            };
+            batch.push(CreateTaskParams {
+                task_id: id(i)?,
+                reward_lamports,
+                dependencies: spec
+                    .dependencies
+                    .iter()
+                    .map(|dep| id(*dep as usize))
+                    .collect::<std::result::Result<_, _>>()?,
+                dependency_groups: Vec::new(),
+                external_dependencies: Vec::new(),
+                claim_duration_slots: spec.claim_duration_slots,
+                competition_deadline_slot: 0,
+                milestones: Vec::new(),
+                vesting: None,
+                crowdfunded: false,
+                require_funding: spec.require_funding,
+                epic: false,
+                parent_task_id: None,
+                spec_uri: spec.spec_uri.clone(),
+                spec_hash: spec.spec_hash,
+                category: spec.category,
+                tags: spec.tags.clone(),
+                max_completions: 0,
+                recurrence_interval_slots: 0,
+                recurrence_end_slot: 0,
+                verifiers: Vec::new(),
+                verifier_threshold: 0,
+            });
+        }
+
+        let authority_signed = ctx
+            .accounts
+            .authority
+            .as_ref()
+            .is_some_and(|a| a.key() == ctx.accounts.graph.authority);
+        create_tasks(
+            &mut ctx.accounts.graph,
+            authority_signed,
+            &ctx.accounts.creator.to_account_info(),
+            ctx.accounts.creator_allowlist.as_deref(),
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.remaining_accounts,
+            &batch,
+        )
+    }
+
+    /// The foreign graph's authority approves a cross-graph edge into one of its tasks.
+    pub fn approve_external_dependency(
+        ctx: Context<ApproveExternalDependency>,
+        index: u8,
+    ) -> Result<()> {
+        let graph_key = ctx.accounts.graph.key();
+        let edge = ctx
+            .accounts
+            .task
+            .external_dependencies
+            .get_mut(index as usize)
+            .ok_or(BountyGraphError::InvalidDependency)?;
+        require_keys_eq!(edge.graph, graph_key, BountyGraphError::InvalidDependency);
+        edge.approved = true;
+
+        Ok(())
+    }
+
+    /// Replace the task's spec and bump its revision. Once a worker has claimed the task or taken
+    /// up a milestone, they must countersign; competitions are frozen after the first submission.
+    pub fn amend_task_spec(ctx: Context<AmendTaskSpec>, params: AmendTaskSpecParams) -> Result<()> {
+        let now = Clock::get()?.slot;
+        let task = &mut ctx.accounts.task;
+        require!(
+            task.status == TaskStatus::Open || task.status == TaskStatus::Draft,
+            BountyGraphError::TaskNotOpen
+        );
+        require!(
+            params.spec_uri.len() <= Task::MAX_SPEC_URI_LEN,
+            BountyGraphError::InvalidSpec
+        );
+        // Work handed in against the old spec (entries, commitments, receipts awaiting attestation,
+        // a submitted milestone or completion) is settled first: its authors cannot all
+        // countersign.
+        require!(
+            !task.has_pending_work(now) && task.pending_completion_agent.is_none(),
+            BountyGraphError::WorkerConsentRequired
+        );
+
+        let countersigned_by = match task.engaged_worker(now) {
+            Some(worker) => {
+                require!(
+                    ctx.accounts
+                        .worker
+                        .as_ref()
+                        .is_some_and(|w| w.key() == worker),
+                    BountyGraphError::WorkerConsentRequired
+                );
+                Some(worker)
+            }
+            None => None,
+        };
+
+        let previous_spec_hash = task.spec_hash;
+        task.spec_revision = task
+            .spec_revision
+            .checked_add(1)
+            .ok_or(BountyGraphError::ArithmeticOverflow)?;
+        task.spec_hash = params.spec_hash;
+        task.spec_uri = params.spec_uri;
+
+        emit!(TaskSpecAmended {
+            task: task.key(),
+            revision: task.spec_revision,
+            previous_spec_hash,
+            spec_hash: task.spec_hash,
+            spec_uri: task.spec_uri.clone(),
+            countersigned_by,
+            slot: now,
+        });
+
+        Ok(())
+    }
+
     pub fn fund_task(ctx: Context<FundTask>, lamports: u64) -> Result<()> {
         require!(lamports > 0, BountyGraphError::InvalidReward);
+        let status = ctx.accounts.task.status;
         require!(
-            ctx.accounts.task.status == TaskStatus::Open,
+            status == TaskStatus::Open || status == TaskStatus::Draft,
             BountyGraphError::TaskNotOpen
         );
-        // SECURITY: Ensure funder doesn't over-commit compared to declared reward
+        // SECURITY: Ensure funders don't over-commit compared to declared reward
+        let funded = ctx
+            .accounts
+            .task
+            .funded_lamports
+            .checked_add(lamports)
+            .ok_or(BountyGraphError::ArithmeticOverflow)?;
         require!(
-            lamports <= ctx.accounts.task.reward_lamports,
+            funded <= ctx.accounts.task.reward_lamports,
             BountyGraphError::InvalidReward
         );
 
@@ -156,9 +607,13 @@ pub mod bountygraph {
         }
 
         // ESCROW SAFETY: Prevent double-funding same task
-        // Empty balance ensures first funder establishes escrow custody
+        // Empty balance ensures first funder establishes escrow custody. Drafts may be funded in
+        // several deposits until they open.
         let escrow_balance = ctx.accounts.escrow.to_account_info().lamports();
-        require!(escrow_balance == 0, BountyGraphError::EscrowAlreadyFunded);
+        require!(
+            escrow_balance == 0 || status == TaskStatus::Draft,
+            BountyGraphError::EscrowAlreadyFunded
+        );
 
         let funder = ctx.accounts.funder.key();
         let escrow_key = ctx.accounts.escrow.key();
@@ -182,6 +637,96 @@ pub mod bountygraph {
         let escrow = &mut ctx.accounts.escrow;
         escrow.task = ctx.accounts.task.key();
         escrow.bump = ctx.bumps.escrow;
+        escrow.version = ACCOUNT_VERSION;
+
+        let task_key = ctx.accounts.task.key();
+        let task = &mut ctx.accounts.task;
+        task.record_funder(
+            task_key,
+            ctx.accounts.deposit.as_mut(),
+            funder,
+            lamports,
+            ctx.bumps.deposit,
+        )?;
+        task.funded_lamports = funded;
+        if task.status == TaskStatus::Draft && task.is_fully_funded() {
+            task.status = TaskStatus::Open;
+        }
+
+        Ok(())
+    }
+
+    /// Funding state of a task, for agents checking that work will be paid before taking it.
+    pub fn funding_status(ctx: Context<FundingStatus>) -> Result<TaskFunding> {
+        let task = &ctx.accounts.task;
+        Ok(TaskFunding {
+            status: task.status,
+            reward_lamports: task.reward_lamports,
+            funded_lamports: task.funded_lamports,
+            fully_funded: task.is_fully_funded(),
+        })
+    }
+
+    /// Raise an open task's reward and deposit the difference into its escrow.
+    pub fn increase_reward(ctx: Context<IncreaseReward>, additional_lamports: u64) -> Result<()> {
+        require!(additional_lamports > 0, BountyGraphError::InvalidReward);
+        let task = &ctx.accounts.task;
+        require!(
+            task.status == TaskStatus::Open || task.status == TaskStatus::Draft,
+            BountyGraphError::TaskNotOpen
+        );
+        // Milestone amounts must keep summing to the reward, so those tasks are fixed.
+        require!(task.milestones.is_empty(), BountyGraphError::MilestoneTask);
+        require!(
+            task.crowdfunded || ctx.accounts.funder.key() == task.creator,
+            BountyGraphError::UnauthorizedFunder
+        );
+
+        let new_reward = task
+            .reward_lamports
+            .checked_add(additional_lamports)
+            .ok_or(BountyGraphError::ArithmeticOverflow)?;
+        let max_reward = ctx.accounts.graph.max_reward_lamports;
+        require!(
+            max_reward == 0 || new_reward <= max_reward,
+            BountyGraphError::InvalidReward
+        );
+
+        let ix = anchor_lang::solana_program::system_instruction::transfer(
+            &ctx.accounts.funder.key(),
+            &ctx.accounts.escrow.key(),
+            additional_lamports,
+        );
+        anchor_lang::solana_program::program::invoke(
+            &ix,
+            &[
+                ctx.accounts.funder.to_account_info(),
+                ctx.accounts.escrow.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+        )?;
+
+        let task_key = ctx.accounts.task.key();
+        let task = &mut ctx.accounts.task;
+        task.record_funder(
+            task_key,
+            ctx.accounts.deposit.as_mut(),
+            ctx.accounts.funder.key(),
+            additional_lamports,
+            ctx.bumps.deposit,
+        )?;
+        task.reward_lamports = new_reward;
+        task.funded_lamports = task
+            .funded_lamports
+            .checked_add(additional_lamports)
+            .ok_or(BountyGraphError::ArithmeticOverflow)?;
+
+        emit!(RewardIncreased {
+            task: task.key(),
+            funder: ctx.accounts.funder.key(),
+            added_lamports: additional_lamports,
+            new_reward_lamports: new_reward,
+        });
 
         Ok(())
     }
@@ -195,10 +740,38 @@ pub mod bountygraph {
         let task_graph = ctx.accounts.task.graph;
         let dependencies = ctx.accounts.task.dependencies.clone();
 
+        require!(
+            task_status != TaskStatus::Draft,
+            BountyGraphError::TaskNotFunded
+        );
         require!(
             task_status == TaskStatus::Open,
             BountyGraphError::TaskNotOpen
         );
+        require!(
+            ctx.accounts.task.milestones.is_empty(),
+            BountyGraphError::MilestoneTask
+        );
+        require!(!ctx.accounts.task.is_epic, BountyGraphError::EpicTask);
+        require!(
+            !ctx.accounts.task.is_repeatable(),
+            BountyGraphError::RepeatableTask
+        );
+
+        // ASSIGNMENT: An active reservation is exclusive. Claim-mode tasks additionally require the
+        // submitter to be the last claimant, even if their reservation has lapsed unclaimed.
+        let agent_key = ctx.accounts.agent.key();
+        let now = Clock::get()?.slot;
+        require!(
+            ctx.accounts.task.may_submit(&agent_key, now),
+            BountyGraphError::NotAssignee
+        );
+        // Competition entries go through `commit_receipt`, so nobody can copy a rival's entry.
+        require!(
+            !ctx.accounts.task.is_competition(),
+            BountyGraphError::CommitRevealRequired
+        );
+
         require!(!params.uri.is_empty(), BountyGraphError::InvalidUri);
         require!(
             params.uri.len() <= Receipt::MAX_URI_LEN,
@@ -211,33 +784,36 @@ pub mod bountygraph {
         // - `task.dependencies` is stored on-chain as a strictly increasing list (validated at create_task).
         // - Clients must pass the corresponding Task accounts in the exact same order.
         // - This keeps the check O(n) and avoids extra sorting / indexing syscalls on-chain.
+        // - External dependencies' task accounts follow, in `task.external_dependencies` order.
+        // - Any accounts after those are co-signing team contributors.
+        let external_count = ctx.accounts.task.external_dependencies.len();
         require!(
-            ctx.remaining_accounts.len() == dependencies.len(),
+            ctx.remaining_accounts.len() >= dependencies.len() + external_count,
             BountyGraphError::MissingDependencyAccounts
         );
+        let (dep_accounts, rest) = ctx.remaining_accounts.split_at(dependencies.len());
+        let (external_accounts, cosigners) = rest.split_at(external_count);
+        let policy = ctx.accounts.graph.dependency_dispute_policy;
+        require_dependencies_satisfied(
+            task_graph,
+            &policy,
+            &dependencies,
+            &ctx.accounts.task.dependency_groups,
+            dep_accounts,
+        )?;
+        require_external_dependencies_satisfied(
+            &policy,
+            &ctx.accounts.task.external_dependencies,
+            external_accounts,
+        )?;
 
-        // TOPOLOGICAL CONSTRAINT: Verify ALL dependencies are marked Completed before allowing this task to complete.
-        // This enforces the DAG execution rule: a task cannot be completed until all prerequisites are satisfied.
-        for (i, dep_task_info) in ctx.remaining_accounts.iter().enumerate() {
-            let expected_dep_id = dependencies[i];
-            let dep_task: Account<Task> = Account::try_from(dep_task_info)?;
-            // Verify dependency belongs to same graph
-            require!(
-                dep_task.graph == task_graph,
-                BountyGraphError::InvalidDependency
-            );
-            // Verify task ID matches (prevents account substitution attacks)
-            require!(
-                dep_task.task_id == expected_dep_id,
-                BountyGraphError::InvalidDependency
-            );
-            // CRITICAL: Only allow completion if ALL dependencies are Completed
-            // This is the enforcement mechanism that prevents parallel execution of dependent tasks
-            require!(
-                dep_task.status == TaskStatus::Completed,
-                BountyGraphError::DependencyNotCompleted
-            );
-        }
+        // TEAM: Shares must cover the whole reward and include the submitter, who accepts by
+        // submitting. Other members accept by co-signing here or via `accept_contribution`.
+        let contributors = build_contributors(&params.contributors, agent_key, cosigners)?;
+        require!(
+            contributors.is_empty() || ctx.accounts.task.vesting.is_none(),
+            BountyGraphError::InvalidContributors
+        );
 
         // Create receipt: proof-of-work anchor
         let task = &mut ctx.accounts.task;
@@ -246,341 +822,2629 @@ pub mod bountygraph {
         receipt.agent = ctx.accounts.agent.key();
         receipt.work_hash = params.work_hash; // Hash of work artifact (e.g., commit hash, file hash)
         receipt.uri = params.uri; // URI to work details (IPFS, GitHub, etc.)
-        receipt.submitted_at_slot = Clock::get()?.slot;
+        receipt.submitted_at_slot = now;
+        receipt.payout_bps = 0;
+        receipt.claimed = false;
+        receipt.contributors = contributors;
+        receipt.milestone_index = None;
+        receipt.completion_index = None;
         receipt.bump = ctx.bumps.receipt;
-
-        // Mark task as completed (atomically with receipt creation)
-        task.status = TaskStatus::Completed;
-        task.completed_by = Some(ctx.accounts.agent.key());
+        receipt.version = ACCOUNT_VERSION;
+
+        if task.requires_attestation() {
+            // ATTESTATION: The receipt waits for `attest_receipt`; the first one attested wins.
+            task.submission_count = task
+                .submission_count
+                .checked_add(1)
+                .ok_or(BountyGraphError::ArithmeticOverflow)?;
+        } else {
+            // Mark task as completed (atomically with receipt creation)
+            task.status = TaskStatus::Completed;
+            task.completed_by = Some(ctx.accounts.agent.key());
+            task.team_size = receipt.contributors.len() as u8;
+            task.vesting_start_slot = now;
+        }
 
         Ok(())
     }
 
-    pub fn claim_reward(ctx: Context<ClaimReward>) -> Result<()> {
-        // PAYMENT SAFETY: Verify task is completed
+    /// How competition entries are submitted, so rivals cannot copy work seen in the mempool:
+    /// record `sha256(work_hash || uri || salt || agent)` before the deadline and open it with
+    /// `reveal_receipt` within `ReceiptCommitment::REVEAL_WINDOW_SLOTS`. The revealed receipt
+    /// ranks by the commit slot.
+    pub fn commit_receipt(ctx: Context<CommitReceipt>, commitment: [u8; 32]) -> Result<()> {
+        let agent_key = ctx.accounts.agent.key();
+        let now = Clock::get()?.slot;
+        let task = &mut ctx.accounts.task;
+
         require!(
-            ctx.accounts.task.status == TaskStatus::Completed,
-            BountyGraphError::TaskNotCompleted
+            task.status != TaskStatus::Draft,
+            BountyGraphError::TaskNotFunded
         );
-        // PAYMENT SAFETY: Verify no dispute is pending
         require!(
-            ctx.accounts.task.dispute_status == DisputeStatus::None,
-            BountyGraphError::TaskInDispute
+            task.status == TaskStatus::Open,
+            BountyGraphError::TaskNotOpen
         );
-        // PAYMENT SAFETY: Verify caller is the worker who completed the task
+        require!(task.is_competition(), BountyGraphError::NotACompetition);
         require!(
-            ctx.accounts.task.completed_by == Some(ctx.accounts.agent.key()),
-            BountyGraphError::NotTaskCompleter
+            task.may_submit(&agent_key, now),
+            BountyGraphError::NotAssignee
         );
+        require!(
+            now < task.competition_deadline_slot,
+            BountyGraphError::SubmissionWindowClosed
+        );
+        task.last_commit_slot = now;
 
-        let escrow_lamports = ctx.accounts.escrow.to_account_info().lamports();
-        require!(escrow_lamports > 0, BountyGraphError::EscrowEmpty);
-
-        // DESIGN: PDA lamport transfer pattern (not system_instruction::transfer)
-        // Reason: system_instruction::transfer requires a signer for the source account.
-        // Since escrow is a program-owned PDA (not a keypair), we cannot sign with it.
-        // Instead, we directly manipulate lamports via &mut reference (allowed for PDAs).
-        // This is safe because Anchor enforces PDA ownership at the account deserialization layer.
-        **ctx.accounts.escrow.to_account_info().lamports.borrow_mut() -= escrow_lamports;
-        **ctx.accounts.agent.to_account_info().lamports.borrow_mut() += escrow_lamports;
-
-        // Close escrow account: zero out discriminator and data to reclaim rent
-        ctx.accounts.escrow.task = Pubkey::default();
-        ctx.accounts.escrow.bump = 0;
+        let receipt_commitment = &mut ctx.accounts.commitment;
+        receipt_commitment.task = task.key();
+        receipt_commitment.agent = agent_key;
+        receipt_commitment.commitment = commitment;
+        receipt_commitment.committed_at_slot = now;
+        receipt_commitment.bump = ctx.bumps.commitment;
+        receipt_commitment.version = ACCOUNT_VERSION;
 
         Ok(())
     }
 
-    pub fn dispute_task(ctx: Context<DisputeTask>, params: DisputeTaskParams) -> Result<()> {
-        require!(!params.reason.is_empty(), BountyGraphError::InvalidUri);
-        require!(
-            params.reason.len() <= Dispute::MAX_REASON_LEN,
+    /// Open a `commit_receipt` commitment and create the receipt, dated at the commit slot.
+    /// Dependencies are passed as in `submit_receipt`; team submissions are not supported.
+    pub fn reveal_receipt<'a>(
+        ctx: Context<'_, '_, 'a, 'a, RevealReceipt<'a>>,
+        params: RevealReceiptParams,
+    ) -> Result<()> {
+        let agent_key = ctx.accounts.agent.key();
+        let now = Clock::get()?.slot;
+        let task = &ctx.accounts.task;
+        let committed_at_slot = ctx.accounts.commitment.committed_at_slot;
+
+        require!(
+            task.status == TaskStatus::Open,
+            BountyGraphError::TaskNotOpen
+        );
+        require!(
+            now <= ctx.accounts.commitment.reveal_deadline_slot(),
+            BountyGraphError::RevealWindowClosed
+        );
+        require!(
+            ReceiptCommitment::compute(&params.work_hash, &params.uri, &params.salt, &agent_key)
+                == ctx.accounts.commitment.commitment,
+            BountyGraphError::CommitmentMismatch
+        );
+        require!(!params.uri.is_empty(), BountyGraphError::InvalidUri);
+        require!(
+            params.uri.len() <= Receipt::MAX_URI_LEN,
             BountyGraphError::InvalidUri
         );
 
+        let dependency_count = task.dependencies.len();
+        require!(
+            ctx.remaining_accounts.len() == dependency_count + task.external_dependencies.len(),
+            BountyGraphError::MissingDependencyAccounts
+        );
+        let (dep_accounts, external_accounts) = ctx.remaining_accounts.split_at(dependency_count);
+        let policy = ctx.accounts.graph.dependency_dispute_policy;
+        require_dependencies_satisfied(
+            task.graph,
+            &policy,
+            &task.dependencies,
+            &task.dependency_groups,
+            dep_accounts,
+        )?;
+        require_external_dependencies_satisfied(
+            &policy,
+            &task.external_dependencies,
+            external_accounts,
+        )?;
+
+        let task = &mut ctx.accounts.task;
+        let receipt = &mut ctx.accounts.receipt;
+        receipt.task = task.key();
+        receipt.agent = agent_key;
+        receipt.work_hash = params.work_hash;
+        receipt.uri = params.uri;
+        receipt.submitted_at_slot = committed_at_slot;
+        receipt.payout_bps = 0;
+        receipt.claimed = false;
+        receipt.contributors = Vec::new();
+        receipt.milestone_index = None;
+        receipt.completion_index = None;
+        receipt.bump = ctx.bumps.receipt;
+        receipt.version = ACCOUNT_VERSION;
+
+        // COMPETITION: The commit was made before the deadline; `select_winners` waits for its
+        // reveal, and the task completes at winner selection.
+        task.submission_count = task
+            .submission_count
+            .checked_add(1)
+            .ok_or(BountyGraphError::ArithmeticOverflow)?;
+
+        Ok(())
+    }
+
+    /// Complete a task with verifiers from one of its receipts. Permissionless: the transaction
+    /// must carry Ed25519 program instructions in which at least `verifier_threshold` of the
+    /// task's verifiers sign `task || agent || work_hash` (see `attestation_message`).
+    pub fn attest_receipt(ctx: Context<AttestReceipt>) -> Result<()> {
+        let now = Clock::get()?.slot;
+        let task_key = ctx.accounts.task.key();
+        let receipt = &ctx.accounts.receipt;
         let task = &mut ctx.accounts.task;
-        let signer = &ctx.accounts.initiator;
 
-        // DISPUTE STATE: Disputes are only meaningful after a task is completed and before payout.
-        // This also prevents "pre-emptive" disputes that could freeze tasks.
         require!(
-            task.status == TaskStatus::Completed,
-            BountyGraphError::InvalidTaskStatus
+            task.status == TaskStatus::Open,
+            BountyGraphError::TaskNotOpen
         );
         require!(
-            task.dispute_status == DisputeStatus::None,
-            BountyGraphError::DisputeAlreadyRaised
+            task.requires_attestation(),
+            BountyGraphError::InvalidVerifiers
         );
 
-        // AUTHORIZATION: Only creator or the worker who completed the task can initiate dispute.
-        let worker = task
-            .completed_by
-            .ok_or(BountyGraphError::InvalidTaskStatus)?;
+        let message = attestation_message(&task_key, &receipt.agent, &receipt.work_hash);
+        let attestations = count_attestations(
+            &ctx.accounts.instructions.to_account_info(),
+            &task.verifiers,
+            &message,
+        )?;
         require!(
-            signer.key() == task.creator || signer.key() == worker,
-            BountyGraphError::UnauthorizedDispute
+            attestations >= task.verifier_threshold as usize,
+            BountyGraphError::MissingAttestation
         );
 
-        let raised_at_slot = Clock::get()?.slot;
+        task.status = TaskStatus::Completed;
+        task.completed_by = Some(receipt.agent);
+        task.team_size = receipt.contributors.len() as u8;
+        task.vesting_start_slot = now;
+
+        emit!(ReceiptAttested {
+            task: task_key,
+            receipt: receipt.key(),
+            agent: receipt.agent,
+            work_hash: receipt.work_hash,
+            attestations: attestations as u8,
+            slot: now,
+        });
 
-        // Update task dispute flags so reward claims are blocked while dispute is open.
-        task.dispute_status = DisputeStatus::Raised;
-        task.disputed_by = Some(signer.key());
-        task.dispute_raised_at_slot = raised_at_slot;
+        Ok(())
+    }
 
-        let dispute = &mut ctx.accounts.dispute;
-        dispute.task = task.key();
-        dispute.creator = task.creator;
-        dispute.worker = worker;
-        dispute.raised_by = signer.key();
-        dispute.reason = params.reason;
-        dispute.status = DisputeStatus::Raised;
-        dispute.raised_at_slot = raised_at_slot;
-        dispute.resolved_at_slot = None;
-        dispute.arbiter = None;
-        dispute.creator_pct = None;
-        dispute.worker_pct = None;
-        dispute.bump = ctx.bumps.dispute;
+    /// Turn down a receipt still awaiting attestation. It is closed back to the agent and no
+    /// longer holds off `cancel_task`, so unattestable receipts cannot lock the escrow.
+    pub fn reject_receipt(ctx: Context<RejectReceipt>) -> Result<()> {
+        let task = &mut ctx.accounts.task;
+        require!(
+            task.status == TaskStatus::Open,
+            BountyGraphError::TaskNotOpen
+        );
+        require!(
+            task.requires_attestation(),
+            BountyGraphError::InvalidVerifiers
+        );
+        task.submission_count = task
+            .submission_count
+            .checked_sub(1)
+            .ok_or(BountyGraphError::ArithmeticOverflow)?;
 
         Ok(())
     }
 
-    pub fn resolve_dispute(
-        ctx: Context<ResolveDispute>,
-        params: ResolveDisputeParams,
+    /// Submit one completion of a repeatable task for the creator to approve or reject. Only one
+    /// completion can be pending at a time. Dependencies are passed as in `submit_receipt`.
+    pub fn submit_completion<'a>(
+        ctx: Context<'_, '_, 'a, 'a, SubmitCompletion<'a>>,
+        params: SubmitCompletionParams,
     ) -> Result<()> {
+        let agent_key = ctx.accounts.agent.key();
+        let now = Clock::get()?.slot;
+        let task = &ctx.accounts.task;
+
         require!(
-            params.creator_pct + params.worker_pct == 100,
-            BountyGraphError::InvalidSplit
+            task.status != TaskStatus::Draft,
+            BountyGraphError::TaskNotFunded
+        );
+        require!(
+            task.status == TaskStatus::Open,
+            BountyGraphError::TaskNotOpen
+        );
+        require!(task.is_repeatable(), BountyGraphError::NotRepeatable);
+        require!(
+            task.recurrence_end_slot == 0 || now < task.recurrence_end_slot,
+            BountyGraphError::SubmissionWindowClosed
+        );
+        require!(
+            now >= task.next_completion_slot(),
+            BountyGraphError::CompletionNotDue
+        );
+        require!(
+            task.pending_completion_agent.is_none(),
+            BountyGraphError::CompletionPending
         );
 
+        require!(!params.uri.is_empty(), BountyGraphError::InvalidUri);
         require!(
-            ctx.accounts.authority.key() == ctx.accounts.graph.authority,
-            BountyGraphError::UnauthorizedResolution
+            params.uri.len() <= Receipt::MAX_URI_LEN,
+            BountyGraphError::InvalidUri
+        );
+
+        // Dependency accounts in `task.dependencies` order, then external dependencies.
+        let dependency_count = task.dependencies.len();
+        require!(
+            ctx.remaining_accounts.len() == dependency_count + task.external_dependencies.len(),
+            BountyGraphError::MissingDependencyAccounts
         );
+        let (dep_accounts, external_accounts) = ctx.remaining_accounts.split_at(dependency_count);
+        let policy = ctx.accounts.graph.dependency_dispute_policy;
+        require_dependencies_satisfied(
+            task.graph,
+            &policy,
+            &task.dependencies,
+            &task.dependency_groups,
+            dep_accounts,
+        )?;
+        require_external_dependencies_satisfied(
+            &policy,
+            &task.external_dependencies,
+            external_accounts,
+        )?;
 
         let task = &mut ctx.accounts.task;
-        let dispute = &mut ctx.accounts.dispute;
+        let receipt = &mut ctx.accounts.receipt;
+        receipt.task = task.key();
+        receipt.agent = agent_key;
+        receipt.work_hash = params.work_hash;
+        receipt.uri = params.uri;
+        receipt.submitted_at_slot = now;
+        receipt.payout_bps = 0;
+        receipt.claimed = false;
+        receipt.contributors = Vec::new();
+        receipt.milestone_index = None;
+        receipt.completion_index = Some(task.completion_count);
+        receipt.bump = ctx.bumps.receipt;
+        receipt.version = ACCOUNT_VERSION;
+
+        task.pending_completion_agent = Some(agent_key);
+
+        Ok(())
+    }
+
+    /// Pay the pending completion's slice to its agent. The last completion pays out whatever
+    /// is left in escrow and completes the task.
+    pub fn approve_completion(ctx: Context<ApproveCompletion>) -> Result<()> {
+        let worker = ctx.accounts.worker.key();
+        let task = &ctx.accounts.task;
 
         require!(
-            dispute.task == task.key(),
-            BountyGraphError::InvalidResolution
+            task.status == TaskStatus::Open,
+            BountyGraphError::TaskNotOpen
         );
         require!(
-            dispute.creator == task.creator,
-            BountyGraphError::InvalidCreator
+            task.pending_completion_agent == Some(worker),
+            BountyGraphError::NoPendingCompletion
         );
 
-        let worker = task
-            .completed_by
-            .ok_or(BountyGraphError::InvalidTaskStatus)?;
-        require!(dispute.worker == worker, BountyGraphError::InvalidWorker);
+        let escrow_info = ctx.accounts.escrow.to_account_info();
+        let escrow_lamports = escrow_info.lamports();
+        let is_final = task.completion_count + 1 == task.max_completions;
+        let payout = if is_final {
+            escrow_lamports
+        } else {
+            // Intermediate slices must leave the escrow rent-exempt.
+            let slice = task.reward_lamports / task.max_completions as u64;
+            let rent = Rent::get()?.minimum_balance(escrow_info.data_len());
+            require!(
+                escrow_lamports >= slice.saturating_add(rent),
+                BountyGraphError::InsufficientEscrow
+            );
+            slice
+        };
+        require!(payout > 0, BountyGraphError::EscrowEmpty);
+
+        let net = collect_fees(
+            &escrow_info,
+            &ctx.accounts.treasury.to_account_info(),
+            &ctx.accounts.graph_authority.to_account_info(),
+            FeeSchedule::new(&ctx.accounts.protocol_config, &ctx.accounts.graph),
+            payout,
+            task.key(),
+        )?;
+        **escrow_info.try_borrow_mut_lamports()? -= net;
+        **ctx
+            .accounts
+            .worker
+            .to_account_info()
+            .try_borrow_mut_lamports()? += net;
+
+        let receipt = &mut ctx.accounts.receipt;
+        receipt.claimed = true;
+
+        // The recurrence interval runs from when the work was submitted, not when it was approved.
+        let task = &mut ctx.accounts.task;
+        task.pending_completion_agent = None;
+        task.completion_count += 1;
+        task.last_completed_slot = receipt.submitted_at_slot;
+
+        if is_final {
+            task.status = TaskStatus::Completed;
+            task.completed_by = Some(worker);
+            task.settled_at_slot = Clock::get()?.slot;
+
+            // Close escrow account: zero out discriminator and data to reclaim rent
+            ctx.accounts.escrow.task = Pubkey::default();
+            ctx.accounts.escrow.bump = 0;
+        }
+
+        Ok(())
+    }
+
+    /// Turn down the pending completion. Its receipt is closed back to the agent, and the
+    /// completion slot opens for a new submission.
+    pub fn reject_completion(ctx: Context<RejectCompletion>) -> Result<()> {
+        let task = &mut ctx.accounts.task;
         require!(
-            ctx.accounts.creator.key() == task.creator,
-            BountyGraphError::InvalidCreator
+            task.pending_completion_agent == Some(ctx.accounts.worker.key()),
+            BountyGraphError::NoPendingCompletion
         );
+        task.pending_completion_agent = None;
+
+        Ok(())
+    }
+
+    /// Accept a share on a team receipt the contributor did not co-sign.
+    pub fn accept_contribution(ctx: Context<AcceptContribution>) -> Result<()> {
+        let contributor = ctx.accounts.contributor.key();
+        let entry = ctx
+            .accounts
+            .receipt
+            .contributors
+            .iter_mut()
+            .find(|c| c.worker == contributor)
+            .ok_or(BountyGraphError::InvalidContributors)?;
+        entry.accepted = true;
+        Ok(())
+    }
+
+    /// Deliver the next milestone of a milestone task. Dependencies are checked as in
+    /// `submit_receipt`, so no tranche is paid before the task's prerequisites are complete.
+    pub fn submit_milestone<'a>(
+        ctx: Context<'_, '_, 'a, 'a, SubmitMilestone<'a>>,
+        params: SubmitMilestoneParams,
+    ) -> Result<()> {
+        let agent_key = ctx.accounts.agent.key();
+        let now = Clock::get()?.slot;
+        let index = params.index as usize;
+        let task = &mut ctx.accounts.task;
+
         require!(
-            ctx.accounts.worker.key() == worker,
-            BountyGraphError::InvalidWorker
+            task.status != TaskStatus::Draft,
+            BountyGraphError::TaskNotFunded
         );
-
         require!(
-            task.dispute_status == DisputeStatus::Raised,
-            BountyGraphError::NoDisputeRaised
+            task.status == TaskStatus::Open,
+            BountyGraphError::TaskNotOpen
         );
         require!(
-            dispute.status == DisputeStatus::Raised,
-            BountyGraphError::InvalidTaskStatus
+            task.next_milestone() == Some(index)
+                && task.milestones[index].status == MilestoneStatus::Pending,
+            BountyGraphError::MilestoneNotReady
+        );
+        require!(
+            task.may_submit(&agent_key, now),
+            BountyGraphError::NotAssignee
+        );
+        require!(!params.uri.is_empty(), BountyGraphError::InvalidUri);
+        require!(
+            params.uri.len() <= Receipt::MAX_URI_LEN,
+            BountyGraphError::InvalidUri
+        );
+        require!(
+            ctx.remaining_accounts.len() >= task.dependencies.len(),
+            BountyGraphError::MissingDependencyAccounts
         );
+        let (dep_accounts, external_accounts) =
+            ctx.remaining_accounts.split_at(task.dependencies.len());
+        let policy = ctx.accounts.graph.dependency_dispute_policy;
+        require_dependencies_satisfied(
+            task.graph,
+            &policy,
+            &task.dependencies,
+            &task.dependency_groups,
+            dep_accounts,
+        )?;
+        require_external_dependencies_satisfied(
+            &policy,
+            &task.external_dependencies,
+            external_accounts,
+        )?;
 
-        let escrow_lamports = ctx.accounts.escrow.to_account_info().lamports();
-        require!(escrow_lamports > 0, BountyGraphError::EscrowEmpty);
+        let receipt = &mut ctx.accounts.receipt;
+        receipt.task = task.key();
+        receipt.agent = agent_key;
+        receipt.work_hash = params.work_hash;
+        receipt.uri = params.uri;
+        receipt.submitted_at_slot = now;
+        receipt.payout_bps = 0;
+        receipt.claimed = false;
+        receipt.contributors = Vec::new();
+        receipt.milestone_index = Some(params.index);
+        receipt.completion_index = None;
+        receipt.bump = ctx.bumps.receipt;
+        receipt.version = ACCOUNT_VERSION;
 
-        let creator_amount = escrow_lamports
-            .checked_mul(params.creator_pct as u64)
-            .ok_or(BountyGraphError::ArithmeticOverflow)?
-            .checked_div(100)
-            .ok_or(BountyGraphError::ArithmeticOverflow)?;
+        let milestone = &mut task.milestones[index];
+        milestone.status = MilestoneStatus::Submitted;
+        milestone.worker = Some(agent_key);
 
-        let worker_amount = escrow_lamports
-            .checked_sub(creator_amount)
-            .ok_or(BountyGraphError::ArithmeticOverflow)?;
+        Ok(())
+    }
 
-        let task_key = task.key();
-        let seeds: &[&[u8]] = &[
-            Escrow::SEED_PREFIX,
-            task_key.as_ref(),
-            &[ctx.accounts.escrow.bump],
-        ];
-        let signer_seeds: &[&[&[u8]]] = &[seeds];
-
-        if creator_amount > 0 {
-            anchor_lang::solana_program::program::invoke_signed(
-                &anchor_lang::solana_program::system_instruction::transfer(
-                    &ctx.accounts.escrow.key(),
-                    &ctx.accounts.creator.key(),
-                    creator_amount,
-                ),
-                &[
-                    ctx.accounts.escrow.to_account_info(),
-                    ctx.accounts.creator.to_account_info(),
-                    ctx.accounts.system_program.to_account_info(),
-                ],
-                signer_seeds,
-            )?;
-        }
+    /// Approve a submitted milestone and pay its amount from escrow. Approving the final
+    /// milestone sweeps the escrow, completes the task, and unlocks its dependents.
+    pub fn approve_milestone(ctx: Context<ApproveMilestone>, index: u8) -> Result<()> {
+        let index = index as usize;
+        let task = &mut ctx.accounts.task;
 
-        if worker_amount > 0 {
-            anchor_lang::solana_program::program::invoke_signed(
-                &anchor_lang::solana_program::system_instruction::transfer(
-                    &ctx.accounts.escrow.key(),
-                    &ctx.accounts.worker.key(),
-                    worker_amount,
-                ),
-                &[
-                    ctx.accounts.escrow.to_account_info(),
-                    ctx.accounts.worker.to_account_info(),
-                    ctx.accounts.system_program.to_account_info(),
-                ],
-                signer_seeds,
-            )?;
+        require!(
+            task.status == TaskStatus::Open,
+            BountyGraphError::TaskNotOpen
+        );
+        require!(
+            task.next_milestone() == Some(index)
+                && task.milestones[index].status == MilestoneStatus::Submitted,
+            BountyGraphError::MilestoneNotReady
+        );
+        let worker = task.milestones[index]
+            .worker
+            .ok_or(BountyGraphError::MilestoneNotReady)?;
+        require_keys_eq!(
+            ctx.accounts.worker.key(),
+            worker,
+            BountyGraphError::InvalidWorker
+        );
+
+        let escrow_info = ctx.accounts.escrow.to_account_info();
+        let escrow_lamports = escrow_info.lamports();
+        let is_final = index + 1 == task.milestones.len();
+        let payout = if is_final {
+            escrow_lamports
+        } else {
+            // Intermediate tranches must leave the escrow rent-exempt.
+            let amount = task.milestones[index].amount_lamports;
+            let rent = Rent::get()?.minimum_balance(escrow_info.data_len());
+            require!(
+                escrow_lamports >= amount.saturating_add(rent),
+                BountyGraphError::InsufficientEscrow
+            );
+            amount
+        };
+        require!(payout > 0, BountyGraphError::EscrowEmpty);
+
+        let net = collect_fees(
+            &escrow_info,
+            &ctx.accounts.treasury.to_account_info(),
+            &ctx.accounts.graph_authority.to_account_info(),
+            FeeSchedule::new(&ctx.accounts.protocol_config, &ctx.accounts.graph),
+            payout,
+            task.key(),
+        )?;
+        **escrow_info.try_borrow_mut_lamports()? -= net;
+        **ctx
+            .accounts
+            .worker
+            .to_account_info()
+            .try_borrow_mut_lamports()? += net;
+
+        task.milestones[index].status = MilestoneStatus::Paid;
+
+        if is_final {
+            let now = Clock::get()?.slot;
+            task.status = TaskStatus::Completed;
+            task.completed_by = Some(worker);
+            task.settled_at_slot = now;
+
+            // Close escrow account: zero out discriminator and data to reclaim rent
+            ctx.accounts.escrow.task = Pubkey::default();
+            ctx.accounts.escrow.bump = 0;
         }
 
-        let resolved_at_slot = Clock::get()?.slot;
+        Ok(())
+    }
 
-        task.dispute_status = DisputeStatus::Resolved;
-        task.resolved_by = Some(ctx.accounts.authority.key());
-        task.dispute_resolved_at_slot = resolved_at_slot;
-        task.worker_award_lamports = worker_amount;
+    /// Send a submitted milestone back to Pending. Its receipt is closed back to the worker, who
+    /// can then resubmit it.
+    pub fn reject_milestone(ctx: Context<RejectMilestone>, index: u8) -> Result<()> {
+        let index = index as usize;
+        let task = &mut ctx.accounts.task;
 
-        dispute.status = DisputeStatus::Resolved;
-        dispute.resolved_at_slot = Some(resolved_at_slot);
-        dispute.arbiter = Some(ctx.accounts.authority.key());
-        dispute.creator_pct = Some(params.creator_pct);
-        dispute.worker_pct = Some(params.worker_pct);
+        require!(
+            task.status == TaskStatus::Open,
+            BountyGraphError::TaskNotOpen
+        );
+        require!(
+            task.next_milestone() == Some(index)
+                && task.milestones[index].status == MilestoneStatus::Submitted,
+            BountyGraphError::MilestoneNotReady
+        );
+        require!(
+            task.milestones[index].worker == Some(ctx.accounts.worker.key()),
+            BountyGraphError::InvalidWorker
+        );
+
+        let milestone = &mut task.milestones[index];
+        milestone.status = MilestoneStatus::Pending;
+        milestone.worker = None;
 
         Ok(())
     }
-}
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct InitializeGraphParams {
-    pub max_dependencies_per_task: u16,
-}
+    /// Close a competition by marking winning receipts with payout weights. Pass the winning
+    /// Receipt accounts (writable) as remaining accounts, in the same order as `weights_bps`.
+    pub fn select_winners<'a>(
+        ctx: Context<'_, '_, 'a, 'a, SelectWinners<'a>>,
+        params: SelectWinnersParams,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task.key();
+        let selector = ctx.accounts.selector.key();
+        let task = &ctx.accounts.task;
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct DisputeTaskParams {
-    pub reason: String,
-}
+        require!(task.is_competition(), BountyGraphError::NotACompetition);
+        require!(
+            task.status == TaskStatus::Open,
+            BountyGraphError::TaskNotOpen
+        );
+        require!(
+            Clock::get()?.slot >= task.competition_deadline_slot,
+            BountyGraphError::SubmissionWindowOpen
+        );
+        require!(
+            Clock::get()?.slot > task.reveal_deadline_slot(),
+            BountyGraphError::RevealWindowOpen
+        );
+        require!(
+            selector == task.creator || selector == ctx.accounts.graph.authority,
+            BountyGraphError::UnauthorizedWinnerSelection
+        );
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct ResolveDisputeParams {
-    pub creator_pct: u8,
-    pub worker_pct: u8,
-}
+        let weights = &params.weights_bps;
+        require!(
+            !weights.is_empty() && weights.len() <= Task::MAX_WINNERS,
+            BountyGraphError::InvalidWinners
+        );
+        require!(
+            ctx.remaining_accounts.len() == weights.len(),
+            BountyGraphError::InvalidWinners
+        );
+        require!(
+            weights.iter().all(|w| *w > 0)
+                && weights.iter().map(|w| *w as u32).sum::<u32>() == 10_000,
+            BountyGraphError::InvalidWinners
+        );
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct CreateTaskParams {
-    pub task_id: u64,
+        let mut first_winner: Option<Pubkey> = None;
+        for (receipt_info, weight) in ctx.remaining_accounts.iter().zip(weights.iter()) {
+            let mut receipt: Account<Receipt> = Account::try_from(receipt_info)?;
+            require!(receipt.task == task_key, BountyGraphError::InvalidWinners);
+            // Rejects the same receipt listed twice: the first pass has already written its weight.
+            require!(receipt.payout_bps == 0, BountyGraphError::InvalidWinners);
+
+            receipt.payout_bps = *weight;
+            receipt.exit(&crate::ID)?;
+            first_winner.get_or_insert(receipt.agent);
+        }
+
+        // The pool excludes escrow rent so partial claims never leave the escrow below exemption.
+        let escrow_info = ctx.accounts.escrow.to_account_info();
+        let pool = escrow_info
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(escrow_info.data_len()));
+        let task = &mut ctx.accounts.task;
+        task.status = TaskStatus::Completed;
+        // The first listed winner stands in for dependents and tombstones; competitions cannot be
+        // disputed, so no split is ever made against it alone.
+        task.completed_by = first_winner;
+        task.payout_pool_lamports = pool;
+        task.unclaimed_payout_bps = 10_000;
+
+        Ok(())
+    }
+
+    /// Complete an epic whose children are all completed, splitting any bonus in its escrow
+    /// equally between the children's workers. Permissionless.
+    ///
+    /// Remaining accounts: every child task in increasing task id order, then, if the epic is
+    /// funded, each child's `completed_by` (writable, same order; the epic creator for children
+    /// without a single worker, such as nested epics).
+    pub fn roll_up<'a>(ctx: Context<'_, '_, 'a, 'a, RollUp<'a>>) -> Result<()> {
+        let graph_key = ctx.accounts.graph.key();
+        let policy = ctx.accounts.graph.dependency_dispute_policy;
+        let task = &ctx.accounts.task;
+        require!(task.is_epic, BountyGraphError::InvalidEpic);
+        require!(
+            task.status == TaskStatus::Open,
+            BountyGraphError::TaskNotOpen
+        );
+
+        let child_count = task.child_count as usize;
+        require!(child_count > 0, BountyGraphError::InvalidEpicChildren);
+        require!(
+            ctx.remaining_accounts.len() >= child_count,
+            BountyGraphError::InvalidEpicChildren
+        );
+        let (child_accounts, recipients) = ctx.remaining_accounts.split_at(child_count);
+
+        // Strictly increasing ids plus the stored count mean every child is present exactly once.
+        let mut workers = Vec::with_capacity(child_count);
+        let mut prev: Option<u64> = None;
+        for child_info in child_accounts {
+            let child = load_task_view(child_info)?;
+            require!(
+                child.graph == graph_key
+                    && child.parent_task_id == Some(task.task_id)
+                    && prev.map_or(true, |p| child.task_id > p),
+                BountyGraphError::InvalidEpicChildren
+            );
+            require!(
+                child.satisfies(&policy),
+                BountyGraphError::ChildNotCompleted
+            );
+            prev = Some(child.task_id);
+            workers.push(child.completed_by.unwrap_or(task.creator));
+        }
+
+        let now = Clock::get()?.slot;
+        let task_key = task.key();
+
+        // BONUS: The epic's own reward, net of fees, is shared equally across its children.
+        if task.funded_lamports > 0 {
+            let escrow = ctx
+                .accounts
+                .escrow
+                .as_mut()
+                .ok_or(BountyGraphError::EscrowEmpty)?;
+            let escrow_info = escrow.to_account_info();
+            let bonus = escrow_info.lamports();
+            if bonus > 0 {
+                require!(
+                    recipients.len() == child_count,
+                    BountyGraphError::InvalidEpicChildren
+                );
+                let net = collect_fees(
+                    &escrow_info,
+                    &ctx.accounts.treasury.to_account_info(),
+                    &ctx.accounts.graph_authority.to_account_info(),
+                    FeeSchedule::new(&ctx.accounts.protocol_config, &ctx.accounts.graph),
+                    bonus,
+                    task_key,
+                )?;
+                let share = net / child_count as u64;
+                let mut remaining = net;
+                for (i, (recipient, worker)) in recipients.iter().zip(&workers).enumerate() {
+                    require_keys_eq!(
+                        recipient.key(),
+                        *worker,
+                        BountyGraphError::InvalidEpicChildren
+                    );
+                    let amount = if i + 1 == child_count {
+                        remaining
+                    } else {
+                        share
+                    };
+                    remaining -= amount;
+                    **escrow_info.try_borrow_mut_lamports()? -= amount;
+                    **recipient.try_borrow_mut_lamports()? += amount;
+                }
+            }
+            escrow.task = Pubkey::default();
+            escrow.bump = 0;
+        }
+
+        let task = &mut ctx.accounts.task;
+        task.status = TaskStatus::Completed;
+        task.settled_at_slot = now;
+
+        Ok(())
+    }
+
+    pub fn claim_reward(ctx: Context<ClaimReward>) -> Result<()> {
+        // PAYMENT SAFETY: Verify task is completed
+        require!(
+            ctx.accounts.task.status == TaskStatus::Completed,
+            BountyGraphError::TaskNotCompleted
+        );
+        // PAYMENT SAFETY: Verify no dispute is pending
+        require!(
+            ctx.accounts.task.dispute_status == DisputeStatus::None,
+            BountyGraphError::TaskInDispute
+        );
+
+        require!(
+            ctx.accounts.task.vesting.is_none(),
+            BountyGraphError::VestingTask
+        );
+
+        let escrow_lamports = ctx.accounts.escrow.to_account_info().lamports();
+        require!(escrow_lamports > 0, BountyGraphError::EscrowEmpty);
+
+        let agent_key = ctx.accounts.agent.key();
+        let payout = if ctx.accounts.task.is_competition() {
+            // COMPETITION: Each winner draws its weighted share of the pool snapshotted at selection.
+            let receipt = ctx
+                .accounts
+                .receipt
+                .as_mut()
+                .ok_or(BountyGraphError::NotAWinner)?;
+            require!(
+                receipt.agent == agent_key && receipt.payout_bps > 0,
+                BountyGraphError::NotAWinner
+            );
+            require!(!receipt.claimed, BountyGraphError::RewardAlreadyClaimed);
+            receipt.claimed = true;
+
+            let task = &mut ctx.accounts.task;
+            task.unclaimed_payout_bps = task
+                .unclaimed_payout_bps
+                .checked_sub(receipt.payout_bps)
+                .ok_or(BountyGraphError::ArithmeticOverflow)?;
+            if task.unclaimed_payout_bps == 0 {
+                // Last winner sweeps the escrow, absorbing rounding dust.
+                escrow_lamports
+            } else {
+                let share = (task.payout_pool_lamports as u128)
+                    .checked_mul(receipt.payout_bps as u128)
+                    .ok_or(BountyGraphError::ArithmeticOverflow)?
+                    / 10_000;
+                (share as u64).min(escrow_lamports)
+            }
+        } else {
+            // PAYMENT SAFETY: Verify caller is the worker who completed the task (team
+            // membership is checked below instead)
+            require!(
+                ctx.accounts.task.team_size > 0
+                    || ctx.accounts.task.completed_by == Some(agent_key),
+                BountyGraphError::NotTaskCompleter
+            );
+            escrow_lamports
+        };
+
+        // FEES: Protocol and graph fees come out of the payout before it reaches the worker side.
+        let net = collect_fees(
+            &ctx.accounts.escrow.to_account_info(),
+            &ctx.accounts.treasury.to_account_info(),
+            &ctx.accounts.graph_authority.to_account_info(),
+            FeeSchedule::new(&ctx.accounts.protocol_config, &ctx.accounts.graph),
+            payout,
+            ctx.accounts.task.key(),
+        )?;
+
+        if ctx.accounts.task.team_size > 0 {
+            // TEAM: Any contributor may trigger the split once every member has accepted. Pass
+            // the contributors' accounts (writable) as remaining accounts in receipt order.
+            let receipt = ctx
+                .accounts
+                .receipt
+                .as_ref()
+                .ok_or(BountyGraphError::InvalidContributors)?;
+            require!(
+                ctx.accounts.task.completed_by == Some(receipt.agent),
+                BountyGraphError::InvalidContributors
+            );
+            require!(
+                receipt.contributors.iter().any(|c| c.worker == agent_key),
+                BountyGraphError::NotTaskCompleter
+            );
+            require!(
+                receipt.team_accepted(),
+                BountyGraphError::ContributionsPending
+            );
+            pay_team(
+                &ctx.accounts.escrow.to_account_info(),
+                &receipt.contributors,
+                ctx.remaining_accounts,
+                net,
+            )?;
+        } else {
+            // DESIGN: PDA lamport transfer pattern (not system_instruction::transfer)
+            // Reason: system_instruction::transfer requires a signer for the source account.
+            // Since escrow is a program-owned PDA (not a keypair), we cannot sign with it.
+            // Instead, we directly manipulate lamports via &mut reference (allowed for PDAs).
+            // This is safe because Anchor enforces PDA ownership at the account deserialization layer.
+            **ctx.accounts.escrow.to_account_info().lamports.borrow_mut() -= net;
+            **ctx.accounts.agent.to_account_info().lamports.borrow_mut() += net;
+        }
+
+        if payout == escrow_lamports {
+            // Close escrow account: zero out discriminator and data to reclaim rent
+            ctx.accounts.escrow.task = Pubkey::default();
+            ctx.accounts.escrow.bump = 0;
+
+            ctx.accounts.task.settled_at_slot = Clock::get()?.slot;
+        }
+
+        Ok(())
+    }
+
+    /// Withdraw the portion of a vesting reward unlocked so far. Blocked while a dispute is open.
+    pub fn withdraw_vested(ctx: Context<WithdrawVested>) -> Result<()> {
+        let task = &mut ctx.accounts.task;
+        require!(
+            task.status == TaskStatus::Completed,
+            BountyGraphError::TaskNotCompleted
+        );
+        require!(
+            task.dispute_status == DisputeStatus::None,
+            BountyGraphError::TaskInDispute
+        );
+        require!(task.vesting.is_some(), BountyGraphError::NotVesting);
+        require!(
+            task.completed_by == Some(ctx.accounts.agent.key()),
+            BountyGraphError::NotTaskCompleter
+        );
+
+        let escrow_info = ctx.accounts.escrow.to_account_info();
+        let escrow_lamports = escrow_info.lamports();
+        require!(escrow_lamports > 0, BountyGraphError::EscrowEmpty);
+
+        let now = Clock::get()?.slot;
+        let rent = Rent::get()?.minimum_balance(escrow_info.data_len());
+        let due = task.vested_due(now, escrow_lamports, rent);
+        require!(due > 0, BountyGraphError::NothingVested);
+
+        let net = collect_fees(
+            &escrow_info,
+            &ctx.accounts.treasury.to_account_info(),
+            &ctx.accounts.graph_authority.to_account_info(),
+            FeeSchedule::new(&ctx.accounts.protocol_config, &ctx.accounts.graph),
+            due,
+            task.key(),
+        )?;
+        **escrow_info.try_borrow_mut_lamports()? -= net;
+        **ctx
+            .accounts
+            .agent
+            .to_account_info()
+            .try_borrow_mut_lamports()? += net;
+
+        task.vested_withdrawn_lamports = task
+            .vested_withdrawn_lamports
+            .checked_add(due)
+            .ok_or(BountyGraphError::ArithmeticOverflow)?;
+
+        if due == escrow_lamports {
+            // Close escrow account: zero out discriminator and data to reclaim rent
+            ctx.accounts.escrow.task = Pubkey::default();
+            ctx.accounts.escrow.bump = 0;
+
+            task.settled_at_slot = now;
+        }
+
+        Ok(())
+    }
+
+    /// Reserve a claim-mode task for `claim_duration_slots`.
+    pub fn claim_task(ctx: Context<ClaimTask>) -> Result<()> {
+        let now = Clock::get()?.slot;
+        let agent = ctx.accounts.agent.key();
+        let task = &mut ctx.accounts.task;
+
+        require!(
+            task.status == TaskStatus::Open,
+            BountyGraphError::TaskNotOpen
+        );
+        require!(task.requires_claim(), BountyGraphError::AssignmentDisabled);
+        require!(
+            !task.reservation_active(now) || task.assignee == Some(agent),
+            BountyGraphError::TaskAlreadyClaimed
+        );
+
+        task.assignee = Some(agent);
+        task.claim_expires_at_slot = now
+            .checked_add(task.claim_duration_slots)
+            .ok_or(BountyGraphError::ArithmeticOverflow)?;
+
+        Ok(())
+    }
+
+    /// Give a reserved task back so others can claim it.
+    pub fn unclaim_task(ctx: Context<UnclaimTask>) -> Result<()> {
+        let task = &mut ctx.accounts.task;
+        require!(
+            task.assignee == Some(ctx.accounts.agent.key()),
+            BountyGraphError::NotAssignee
+        );
+
+        task.assignee = None;
+        task.claim_expires_at_slot = 0;
+
+        Ok(())
+    }
+
+    /// Creator assigns (or, with `None`, unassigns) a worker directly. Creator assignments do not
+    /// lapse and override any existing reservation.
+    pub fn assign_task(ctx: Context<AssignTask>, worker: Option<Pubkey>) -> Result<()> {
+        let task = &mut ctx.accounts.task;
+        require!(
+            task.status == TaskStatus::Open,
+            BountyGraphError::TaskNotOpen
+        );
+
+        task.assignee = worker;
+        task.claim_expires_at_slot = 0;
+
+        Ok(())
+    }
+
+    /// Withdraw an Open or Draft task. Escrowed funds go back to the creator, except the share of
+    /// other funders, which `refund_deposit` returns to them. Work already handed in (competition
+    /// entries, commitments that can still be revealed, receipts awaiting attestation or approval)
+    /// must be settled first, so a creator cannot take it for free.
+    pub fn cancel_task(ctx: Context<CancelTask>) -> Result<()> {
+        let now = Clock::get()?.slot;
+        let status = ctx.accounts.task.status;
+        require!(
+            status == TaskStatus::Open || status == TaskStatus::Draft,
+            BountyGraphError::TaskNotOpen
+        );
+        require!(
+            !ctx.accounts.task.has_pending_work(now),
+            BountyGraphError::PendingSubmissions
+        );
+        require!(
+            ctx.accounts.task.pending_completion_agent.is_none(),
+            BountyGraphError::CompletionPending
+        );
+
+        // A funded task must be cancelled with its escrow: a cancelled task has no other way to
+        // drain it, and depositors are refunded from it.
+        let depositor_count = ctx.accounts.task.depositor_count;
+        require!(
+            ctx.accounts.task.funded_lamports == 0 || ctx.accounts.escrow.is_some(),
+            BountyGraphError::EscrowRequired
+        );
+        if let Some(escrow) = ctx.accounts.escrow.as_mut() {
+            let escrow_info = escrow.to_account_info();
+            let escrow_lamports = escrow_info.lamports();
+            let refund = if depositor_count == 0 {
+                escrow.task = Pubkey::default();
+                escrow.bump = 0;
+                escrow_lamports
+            } else {
+                // Other funders get their share of what is left back through refund_deposit, pro
+                // rata to what each side paid in (milestones may already have drawn on it). The
+                // escrow stays open, rent included, until the last of them is refunded.
+                let task = &mut ctx.accounts.task;
+                let rent = Rent::get()?.minimum_balance(escrow_info.data_len());
+                let available = escrow_lamports.saturating_sub(rent);
+                task.refund_pool_lamports =
+                    ((available as u128) * (task.deposited_lamports as u128)
+                        / (task.funded_lamports as u128)) as u64;
+                available - task.refund_pool_lamports
+            };
+            **escrow_info.lamports.borrow_mut() -= refund;
+            **ctx.accounts.creator.to_account_info().lamports.borrow_mut() += refund;
+        }
+
+        // A cancelled child can never complete, so it leaves its epic rather than blocking roll-up.
+        let task = &mut ctx.accounts.task;
+        match (task.parent_task_id, ctx.accounts.parent.as_mut()) {
+            (Some(parent_id), Some(parent)) => {
+                require!(
+                    parent.graph == task.graph && parent.task_id == parent_id,
+                    BountyGraphError::InvalidEpic
+                );
+                parent.child_count -= 1;
+                task.parent_task_id = None;
+            }
+            (None, None) => {}
+            _ => return err!(BountyGraphError::InvalidEpic),
+        }
+
+        task.status = TaskStatus::Cancelled;
+        task.settled_at_slot = now;
+
+        Ok(())
+    }
+
+    /// Close a `Deposit` once its task is terminal. On a cancelled task this pays the funder their
+    /// share of the refund pool; the last refund hands what remains of the escrow, rent and
+    /// rounding dust, to the creator. Anyone may crank it, since the lamports only go back.
+    pub fn refund_deposit(ctx: Context<RefundDeposit>) -> Result<()> {
+        let task = &mut ctx.accounts.task;
+        require!(task.is_terminal(), BountyGraphError::TaskNotTerminal);
+        task.depositor_count -= 1;
+
+        if task.status == TaskStatus::Cancelled {
+            let escrow = ctx
+                .accounts
+                .escrow
+                .as_mut()
+                .ok_or(BountyGraphError::EscrowEmpty)?;
+            let escrow_info = escrow.to_account_info();
+            let refund = ctx.accounts.deposit.refund(task);
+            **escrow_info.try_borrow_mut_lamports()? -= refund;
+            **ctx.accounts.funder.try_borrow_mut_lamports()? += refund;
+
+            if task.depositor_count == 0 {
+                let rest = escrow_info.lamports();
+                **escrow_info.try_borrow_mut_lamports()? -= rest;
+                **ctx.accounts.creator.try_borrow_mut_lamports()? += rest;
+                escrow.task = Pubkey::default();
+                escrow.bump = 0;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Replace a terminal task with a `TaskTombstone`, returning the rent difference (and any
+    /// creation deposit) to the creator. Completed tasks keep satisfying their dependents.
+    pub fn close_task(ctx: Context<CloseTask>) -> Result<()> {
+        let task_info = ctx.accounts.task.to_account_info();
+        require_keys_eq!(
+            *task_info.owner,
+            crate::ID,
+            BountyGraphError::InvalidAccountLayout
+        );
+        let task = {
+            let data = task_info.try_borrow_data()?;
+            Task::try_deserialize(&mut &data[..])?
+        };
+
+        require!(
+            task.graph == ctx.accounts.graph.key(),
+            BountyGraphError::InvalidGraph
+        );
+        require!(
+            task.creator == ctx.accounts.creator.key(),
+            BountyGraphError::InvalidCreator
+        );
+        require!(task.is_terminal(), BountyGraphError::TaskNotTerminal);
+        require!(
+            task.depositor_count == 0,
+            BountyGraphError::DepositsOutstanding
+        );
+
+        let now = Clock::get()?.slot;
+        let retention = ctx.accounts.graph.close_retention_slots;
+        require!(
+            now >= task.settled_at_slot.saturating_add(retention),
+            BountyGraphError::RetentionPeriodActive
+        );
+
+        let tombstone = TaskTombstone {
+            graph: task.graph,
+            task_id: task.task_id,
+            creator: task.creator,
+            completed: task.status == TaskStatus::Completed,
+            worker_pct: task.worker_pct(),
+            parent_task_id: task.parent_task_id,
+            completed_by: task.completed_by,
+            closed_at_slot: now,
+            bump: task.bump,
+            version: ACCOUNT_VERSION,
+        };
+
+        let new_len = 8 + TaskTombstone::INIT_SPACE;
+        let keep = Rent::get()?.minimum_balance(new_len);
+        let refund = task_info
+            .lamports()
+            .checked_sub(keep)
+            .ok_or(BountyGraphError::ArithmeticOverflow)?;
+
+        task_info.realloc(new_len, false)?;
+        {
+            let mut data = task_info.try_borrow_mut_data()?;
+            let mut writer: &mut [u8] = &mut data[..];
+            tombstone.try_serialize(&mut writer)?;
+        }
+
+        **task_info.lamports.borrow_mut() -= refund;
+        **ctx.accounts.creator.to_account_info().lamports.borrow_mut() += refund;
+
+        Ok(())
+    }
+
+    pub fn close_receipt(ctx: Context<CloseReceipt>) -> Result<()> {
+        let task = load_task_view(&ctx.accounts.task.to_account_info())?;
+        require!(
+            task.graph == ctx.accounts.graph.key(),
+            BountyGraphError::InvalidGraph
+        );
+
+        let terminal_since = task
+            .terminal_since
+            .ok_or(BountyGraphError::TaskNotTerminal)?;
+        let retention = ctx.accounts.graph.close_retention_slots;
+        require!(
+            Clock::get()?.slot >= terminal_since.saturating_add(retention),
+            BountyGraphError::RetentionPeriodActive
+        );
+
+        // Receipt rent returns to the agent via the `close` constraint.
+        Ok(())
+    }
+
+    /// Reclaim the rent of a commitment that was never revealed, once it no longer can be.
+    pub fn close_commitment(ctx: Context<CloseCommitment>) -> Result<()> {
+        require!(
+            Clock::get()?.slot > ctx.accounts.commitment.reveal_deadline_slot(),
+            BountyGraphError::RevealWindowOpen
+        );
+
+        // Commitment rent returns to the agent via the `close` constraint.
+        Ok(())
+    }
+
+    pub fn close_dispute(ctx: Context<CloseDispute>) -> Result<()> {
+        let task = load_task_view(&ctx.accounts.task.to_account_info())?;
+        require!(
+            task.graph == ctx.accounts.graph.key(),
+            BountyGraphError::InvalidGraph
+        );
+
+        let dispute = &ctx.accounts.dispute;
+        require!(
+            dispute.status == DisputeStatus::Resolved,
+            BountyGraphError::TaskNotTerminal
+        );
+        let resolved_at_slot = dispute
+            .resolved_at_slot
+            .ok_or(BountyGraphError::TaskNotTerminal)?;
+        let retention = ctx.accounts.graph.close_retention_slots;
+        require!(
+            Clock::get()?.slot >= resolved_at_slot.saturating_add(retention),
+            BountyGraphError::RetentionPeriodActive
+        );
+
+        // Dispute rent returns to whoever raised it via the `close` constraint.
+        Ok(())
+    }
+
+    pub fn dispute_task(ctx: Context<DisputeTask>, params: DisputeTaskParams) -> Result<()> {
+        require!(!params.reason.is_empty(), BountyGraphError::InvalidUri);
+        require!(
+            params.reason.len() <= Dispute::MAX_REASON_LEN,
+            BountyGraphError::InvalidUri
+        );
+
+        let task = &mut ctx.accounts.task;
+        let signer = &ctx.accounts.initiator;
+
+        // DISPUTE STATE: Disputes are only meaningful after a task is completed and before payout.
+        // This also prevents "pre-emptive" disputes that could freeze tasks.
+        require!(
+            task.status == TaskStatus::Completed,
+            BountyGraphError::InvalidTaskStatus
+        );
+        require!(
+            task.dispute_status == DisputeStatus::None,
+            BountyGraphError::DisputeAlreadyRaised
+        );
+        // COMPETITION: Winners are paid per receipt by their weights, which a two-party split
+        // cannot express, and the creator (or graph authority) chose them in the first place.
+        require!(
+            !task.is_competition(),
+            BountyGraphError::CompetitionNotDisputable
+        );
+
+        // AUTHORIZATION: Only creator or the worker who completed the task can initiate dispute.
+        let worker = task
+            .completed_by
+            .ok_or(BountyGraphError::InvalidTaskStatus)?;
+        require!(
+            signer.key() == task.creator || signer.key() == worker,
+            BountyGraphError::UnauthorizedDispute
+        );
+
+        let raised_at_slot = Clock::get()?.slot;
+
+        // Update task dispute flags so reward claims are blocked while dispute is open.
+        task.dispute_status = DisputeStatus::Raised;
+        task.disputed_by = Some(signer.key());
+        task.dispute_raised_at_slot = raised_at_slot;
+
+        let dispute = &mut ctx.accounts.dispute;
+        dispute.task = task.key();
+        dispute.creator = task.creator;
+        dispute.worker = worker;
+        dispute.raised_by = signer.key();
+        dispute.reason = params.reason;
+        dispute.status = DisputeStatus::Raised;
+        dispute.raised_at_slot = raised_at_slot;
+        dispute.resolved_at_slot = None;
+        dispute.arbiter = None;
+        dispute.creator_pct = None;
+        dispute.worker_pct = None;
+        dispute.spec_revision = task.spec_revision;
+        dispute.bump = ctx.bumps.dispute;
+        dispute.version = ACCOUNT_VERSION;
+
+        Ok(())
+    }
+
+    pub fn resolve_dispute(
+        ctx: Context<ResolveDispute>,
+        params: ResolveDisputeParams,
+    ) -> Result<()> {
+        require!(
+            params.creator_pct + params.worker_pct == 100,
+            BountyGraphError::InvalidSplit
+        );
+
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.graph.authority,
+            BountyGraphError::UnauthorizedResolution
+        );
+
+        let task = &mut ctx.accounts.task;
+        let dispute = &mut ctx.accounts.dispute;
+
+        require!(
+            dispute.task == task.key(),
+            BountyGraphError::InvalidResolution
+        );
+        require!(
+            dispute.creator == task.creator,
+            BountyGraphError::InvalidCreator
+        );
+
+        let worker = task
+            .completed_by
+            .ok_or(BountyGraphError::InvalidTaskStatus)?;
+        require!(dispute.worker == worker, BountyGraphError::InvalidWorker);
+        require!(
+            ctx.accounts.creator.key() == task.creator,
+            BountyGraphError::InvalidCreator
+        );
+        require!(
+            ctx.accounts.worker.key() == worker,
+            BountyGraphError::InvalidWorker
+        );
+
+        require!(
+            task.dispute_status == DisputeStatus::Raised,
+            BountyGraphError::NoDisputeRaised
+        );
+        require!(
+            dispute.status == DisputeStatus::Raised,
+            BountyGraphError::InvalidTaskStatus
+        );
+
+        // UNFUNDED: Tasks that were never funded, or whose escrow has already paid out, have no
+        // escrow to split. The outcome is still recorded (dependents read it through the graph's
+        // dependency dispute policy), with nothing awarded. The PDA's own balance decides, so a
+        // stale `funded_lamports` can never leave escrowed funds behind.
+        let escrow = ctx.accounts.escrow.to_account_info();
+        let worker_amount = if *escrow.owner != crate::ID || escrow.lamports() == 0 {
+            0
+        } else {
+            let escrow_lamports = escrow.lamports();
+
+            // VESTING: Whatever has already vested belongs to the worker; only the unvested
+            // remainder is subject to the split.
+            let vested_due = if task.vesting.is_some() {
+                let rent = Rent::get()?.minimum_balance(escrow.data_len());
+                task.vested_due(Clock::get()?.slot, escrow_lamports, rent)
+            } else {
+                0
+            };
+            let creator_amount = (escrow_lamports - vested_due)
+                .checked_mul(params.creator_pct as u64)
+                .ok_or(BountyGraphError::ArithmeticOverflow)?
+                .checked_div(100)
+                .ok_or(BountyGraphError::ArithmeticOverflow)?;
+
+            let worker_amount = escrow_lamports
+                .checked_sub(creator_amount)
+                .ok_or(BountyGraphError::ArithmeticOverflow)?;
+
+            // FEES: Charged on the worker portion only; the creator's portion is a refund.
+            let worker_amount = if worker_amount > 0 {
+                collect_fees(
+                    &escrow,
+                    &ctx.accounts.treasury.to_account_info(),
+                    &ctx.accounts.authority.to_account_info(),
+                    FeeSchedule::new(&ctx.accounts.protocol_config, &ctx.accounts.graph),
+                    worker_amount,
+                    task.key(),
+                )?
+            } else {
+                0
+            };
+
+            // The escrow holds data, so the system program cannot debit it; the program moves
+            // the lamports directly, as in payout.rs.
+            if creator_amount > 0 {
+                **escrow.try_borrow_mut_lamports()? -= creator_amount;
+                **ctx.accounts.creator.try_borrow_mut_lamports()? += creator_amount;
+            }
+
+            if worker_amount > 0 && task.team_size > 0 {
+                // TEAM: The worker portion is split across the team by the receipt's shares. Pass
+                // the contributors' accounts (writable) as remaining accounts in receipt order.
+                let receipt = ctx
+                    .accounts
+                    .receipt
+                    .as_ref()
+                    .ok_or(BountyGraphError::InvalidContributors)?;
+                require!(
+                    receipt.agent == worker,
+                    BountyGraphError::InvalidContributors
+                );
+                pay_team(
+                    &escrow,
+                    &receipt.contributors,
+                    ctx.remaining_accounts,
+                    worker_amount,
+                )?;
+            } else if worker_amount > 0 {
+                **escrow.try_borrow_mut_lamports()? -= worker_amount;
+                **ctx.accounts.worker.try_borrow_mut_lamports()? += worker_amount;
+            }
+
+            worker_amount
+        };
+
+        let resolved_at_slot = Clock::get()?.slot;
+
+        task.dispute_status = DisputeStatus::Resolved;
+        task.resolved_by = Some(ctx.accounts.authority.key());
+        task.dispute_resolved_at_slot = resolved_at_slot;
+        task.worker_award_lamports = worker_amount;
+        task.dispute_worker_pct = params.worker_pct;
+        task.settled_at_slot = resolved_at_slot;
+
+        dispute.status = DisputeStatus::Resolved;
+        dispute.resolved_at_slot = Some(resolved_at_slot);
+        dispute.arbiter = Some(ctx.accounts.authority.key());
+        dispute.creator_pct = Some(params.creator_pct);
+        dispute.worker_pct = Some(params.worker_pct);
+
+        Ok(())
+    }
+
+    // MIGRATIONS: Permissionless, idempotent-by-rejection upgrades of v0 accounts. The payer only
+    // covers the extra rent; ownership and contents are derived entirely from the old account.
+
+    pub fn migrate_graph(ctx: Context<MigrateAccount>) -> Result<()> {
+        migrate::<Graph, GraphV0>(
+            &ctx.accounts.account.to_account_info(),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            GraphV0::upgrade,
+            |_| 8 + Graph::INIT_SPACE,
+        )?;
+        Ok(())
+    }
+
+    pub fn migrate_task(ctx: Context<MigrateTask>) -> Result<()> {
+        let escrowed = v0_escrowed_lamports(&ctx.accounts.escrow.to_account_info())?;
+        migrate::<Task, TaskV0>(
+            &ctx.accounts.account.to_account_info(),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            |legacy| {
+                let reward_lamports = legacy.reward_lamports;
+                let mut task = legacy.upgrade();
+                task.funded_lamports = escrowed.min(reward_lamports);
+                task
+            },
+            |task| {
+                8 + Task::space_for(
+                    &task.dependencies,
+                    &task.dependency_groups,
+                    task.external_dependencies.len(),
+                    task.milestones.len(),
+                    task.verifiers.len(),
+                )
+            },
+        )?;
+        Ok(())
+    }
+
+    pub fn migrate_escrow(ctx: Context<MigrateAccount>) -> Result<()> {
+        migrate::<Escrow, EscrowV0>(
+            &ctx.accounts.account.to_account_info(),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            EscrowV0::upgrade,
+            |_| 8 + Escrow::INIT_SPACE,
+        )?;
+        Ok(())
+    }
+
+    pub fn migrate_receipt(ctx: Context<MigrateAccount>) -> Result<()> {
+        migrate::<Receipt, ReceiptV0>(
+            &ctx.accounts.account.to_account_info(),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            ReceiptV0::upgrade,
+            |_| 8 + Receipt::INIT_SPACE,
+        )?;
+        Ok(())
+    }
+
+    pub fn migrate_dispute(ctx: Context<MigrateAccount>) -> Result<()> {
+        migrate::<Dispute, DisputeV0>(
+            &ctx.accounts.account.to_account_info(),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            DisputeV0::upgrade,
+            |dispute| 8 + Dispute::space_for(&dispute.reason),
+        )?;
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct InitializeGraphParams {
+    /// Caller-chosen identifier, unique per authority. Lets one wallet own several graphs.
+    pub graph_id: u64,
+    pub max_dependencies_per_task: u16,
+    pub creation_policy: CreationPolicy,
+    pub creation_deposit_lamports: u64,
+    pub guardian: Option<Pubkey>,
+}
+
+/// Fields left as `None` keep their current value.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UpdateGraphConfigParams {
+    pub max_dependencies_per_task: Option<u16>,
+    pub min_reward_lamports: Option<u64>,
+    pub max_reward_lamports: Option<u64>,
+    pub close_retention_slots: Option<u64>,
+    pub graph_fee_bps: Option<u16>,
+    pub require_dependent_approval: Option<bool>,
+    pub dependency_dispute_policy: Option<DependencyDisputePolicy>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct InitializeProtocolConfigParams {
+    pub treasury: Pubkey,
+    pub fee_bps: u16,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UpdateProtocolConfigParams {
+    pub admin: Option<Pubkey>,
+    pub treasury: Option<Pubkey>,
+    pub fee_bps: Option<u16>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetCreationPolicyParams {
+    pub creation_policy: CreationPolicy,
+    pub creation_deposit_lamports: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct DisputeTaskParams {
+    pub reason: String,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ResolveDisputeParams {
+    pub creator_pct: u8,
+    pub worker_pct: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateTaskParams {
+    pub task_id: u64,
     pub reward_lamports: u64,
     pub dependencies: Vec<u64>,
+    /// Threshold groups over `dependencies`; dependencies in no group are all required.
+    pub dependency_groups: Vec<DependencyGroup>,
+    /// Tasks in other graphs that must be completed; pass each one's graph and task account.
+    pub external_dependencies: Vec<ExternalTaskRef>,
+    /// Non-zero enables claim-based assignment with reservations of this many slots.
+    pub claim_duration_slots: u64,
+    /// Non-zero makes this a competition accepting submissions until this slot.
+    pub competition_deadline_slot: u64,
+    /// Per-milestone amounts summing to `reward_lamports`; empty for a single payout.
+    pub milestones: Vec<u64>,
+    /// Pay the reward out gradually after completion instead of in one `claim_reward`.
+    pub vesting: Option<VestingSchedule>,
+    /// Let any funder, not just the creator, raise the reward with `increase_reward`.
+    pub crowdfunded: bool,
+    /// Keep the task in `Draft`, refusing receipts, until the reward is fully funded. Off by
+    /// default: the task opens at once and takes receipts even while unfunded, so workers should
+    /// check `funding_status` before starting.
+    pub require_funding: bool,
+    /// Create an epic, completed by `roll_up` once all its children are. Epics take no
+    /// dependencies; prerequisites go on their children.
+    pub epic: bool,
+    /// Register the new task as a child of this epic; pass the epic as `parent`.
+    pub parent_task_id: Option<u64>,
+    /// Where the specification lives; at most `Task::MAX_SPEC_URI_LEN` bytes.
+    pub spec_uri: String,
+    /// Hash of the specification, so agents have an on-chain commitment to what they work on.
+    pub spec_hash: [u8; 32],
+    pub category: u16,
+    /// Up to `Task::MAX_TAGS` non-zero tag codes.
+    pub tags: Vec<u16>,
+    /// Non-zero makes the task repeatable, paying `reward_lamports / max_completions` per
+    /// completion the creator approves.
+    pub max_completions: u16,
+    /// Minimum slots between completions of a repeatable task; 0 for no spacing.
+    pub recurrence_interval_slots: u64,
+    /// Slot at which completions stop being accepted; 0 for no end.
+    pub recurrence_end_slot: u64,
+    /// Oracle keys whose attestations `attest_receipt` accepts; empty for none.
+    pub verifiers: Vec<Pubkey>,
+    /// How many distinct verifiers must attest a receipt; 0 iff `verifiers` is empty.
+    pub verifier_threshold: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct InstantiateTemplateParams {
+    /// Added to each template index to form the task id.
+    pub task_id_offset: u64,
+    /// Scale every reward by this many basis points of the template's amount.
+    pub reward_multiplier_bps: Option<u32>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RevealReceiptParams {
+    pub work_hash: [u8; 32],
+    pub uri: String,
+    pub salt: [u8; 32],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SubmitCompletionParams {
+    pub work_hash: [u8; 32],
+    pub uri: String,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AmendTaskSpecParams {
+    pub spec_uri: String,
+    pub spec_hash: [u8; 32],
+}
+
+/// Returned by `funding_status`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TaskFunding {
+    pub status: TaskStatus,
+    pub reward_lamports: u64,
+    pub funded_lamports: u64,
+    pub fully_funded: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SubmitMilestoneParams {
+    pub index: u8,
+    pub work_hash: [u8; 32],
+    pub uri: String,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SelectWinnersParams {
+    /// Payout weight per winning receipt, in the same order as the receipt accounts.
+    pub weights_bps: Vec<u16>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SubmitReceiptParams {
+    pub work_hash: [u8; 32],
+    pub uri: String,
+    /// Team shares including the submitting agent; empty for a solo submission.
+    pub contributors: Vec<ContributorShare>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ExternalTaskRef {
+    pub graph: Pubkey,
+    pub task_id: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ContributorShare {
+    pub worker: Pubkey,
+    pub share_bps: u16,
+}
+
+#[derive(Accounts)]
+pub struct InitializeProtocolConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + ProtocolConfig::INIT_SPACE,
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key())
+            @ BountyGraphError::UnauthorizedAdmin
+    )]
+    pub program: Program<'info, crate::program::Bountygraph>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key())
+            @ BountyGraphError::UnauthorizedAdmin
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateProtocolConfig<'info> {
+    #[account(
+        mut,
+        has_one = admin @ BountyGraphError::UnauthorizedAdmin,
+        seeds = [ProtocolConfig::SEED_PREFIX],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(params: InitializeGraphParams)]
+pub struct InitializeGraph<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Graph::INIT_SPACE,
+        seeds = [Graph::SEED_PREFIX, authority.key().as_ref(), &params.graph_id.to_le_bytes()],
+        bump
+    )]
+    pub graph: Account<'info, Graph>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateGraphConfig<'info> {
+    // Graphs on an older layout do not deserialize here; upgrade them with `migrate_graph` first.
+    #[account(
+        mut,
+        has_one = authority,
+        seeds = [Graph::SEED_PREFIX, authority.key().as_ref(), &graph.id_seed()],
+        bump = graph.bump
+    )]
+    pub graph: Account<'info, Graph>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    #[account(
+        mut,
+        has_one = authority,
+        seeds = [Graph::SEED_PREFIX, authority.key().as_ref(), &graph.id_seed()],
+        bump = graph.bump
+    )]
+    pub graph: Account<'info, Graph>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct PauseGraph<'info> {
+    #[account(
+        mut,
+        seeds = [Graph::SEED_PREFIX, graph.authority.as_ref(), &graph.id_seed()],
+        bump = graph.bump
+    )]
+    pub graph: Account<'info, Graph>,
+
+    /// Graph authority or guardian.
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UnpauseGraph<'info> {
+    #[account(
+        mut,
+        has_one = authority,
+        seeds = [Graph::SEED_PREFIX, authority.key().as_ref(), &graph.id_seed()],
+        bump = graph.bump
+    )]
+    pub graph: Account<'info, Graph>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetCreationPolicy<'info> {
+    #[account(
+        mut,
+        has_one = authority,
+        seeds = [Graph::SEED_PREFIX, authority.key().as_ref(), &graph.id_seed()],
+        bump = graph.bump
+    )]
+    pub graph: Account<'info, Graph>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AddAllowedCreator<'info> {
+    #[account(
+        has_one = authority,
+        seeds = [Graph::SEED_PREFIX, authority.key().as_ref(), &graph.id_seed()],
+        bump = graph.bump
+    )]
+    pub graph: Account<'info, Graph>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Only the key is recorded; the creator does not need to sign to be allowlisted.
+    pub creator: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + CreatorAllowlist::INIT_SPACE,
+        seeds = [CreatorAllowlist::SEED_PREFIX, graph.key().as_ref(), creator.key().as_ref()],
+        bump
+    )]
+    pub creator_allowlist: Account<'info, CreatorAllowlist>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveAllowedCreator<'info> {
+    #[account(
+        has_one = authority,
+        seeds = [Graph::SEED_PREFIX, authority.key().as_ref(), &graph.id_seed()],
+        bump = graph.bump
+    )]
+    pub graph: Account<'info, Graph>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        close = authority,
+        constraint = creator_allowlist.graph == graph.key() @ BountyGraphError::InvalidGraph,
+        seeds = [CreatorAllowlist::SEED_PREFIX, graph.key().as_ref(), creator_allowlist.creator.as_ref()],
+        bump = creator_allowlist.bump
+    )]
+    pub creator_allowlist: Account<'info, CreatorAllowlist>,
+}
+
+#[derive(Accounts)]
+#[instruction(params: CreateTaskParams)]
+pub struct CreateTask<'info> {
+    #[account(
+        mut,
+        constraint = !graph.paused @ BountyGraphError::GraphPaused,
+        seeds = [Graph::SEED_PREFIX, graph.authority.as_ref(), &graph.id_seed()],
+        bump = graph.bump
+    )]
+    pub graph: Account<'info, Graph>,
+
+    /// Required under `CreationPolicy::AuthorityOnly`; optional otherwise.
+    pub authority: Option<Signer<'info>>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        seeds = [CreatorAllowlist::SEED_PREFIX, graph.key().as_ref(), creator.key().as_ref()],
+        bump = creator_allowlist.bump
+    )]
+    pub creator_allowlist: Option<Account<'info, CreatorAllowlist>>,
+
+    /// The epic named by `params.parent_task_id`.
+    #[account(mut)]
+    pub parent: Option<Account<'info, Task>>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + Task::space_for(
+            &params.dependencies,
+            &params.dependency_groups,
+            params.external_dependencies.len(),
+            params.milestones.len(),
+            params.verifiers.len()
+        ),
+        seeds = [Task::SEED_PREFIX, graph.key().as_ref(), &params.task_id.to_le_bytes()],
+        bump
+    )]
+    pub task: Account<'info, Task>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateTasksBatch<'info> {
+    #[account(
+        mut,
+        constraint = !graph.paused @ BountyGraphError::GraphPaused,
+        seeds = [Graph::SEED_PREFIX, graph.authority.as_ref(), &graph.id_seed()],
+        bump = graph.bump
+    )]
+    pub graph: Account<'info, Graph>,
+
+    /// Required under `CreationPolicy::AuthorityOnly`; optional otherwise.
+    pub authority: Option<Signer<'info>>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        seeds = [CreatorAllowlist::SEED_PREFIX, graph.key().as_ref(), creator.key().as_ref()],
+        bump = creator_allowlist.bump
+    )]
+    pub creator_allowlist: Option<Account<'info, CreatorAllowlist>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(template_id: u64, tasks: Vec<TemplateTask>)]
+pub struct CreateTemplate<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + GraphTemplate::space_for(&tasks),
+        seeds = [GraphTemplate::SEED_PREFIX, authority.key().as_ref(), &template_id.to_le_bytes()],
+        bump
+    )]
+    pub template: Account<'info, GraphTemplate>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseTemplate<'info> {
+    #[account(
+        mut,
+        close = authority,
+        has_one = authority,
+        seeds = [GraphTemplate::SEED_PREFIX, authority.key().as_ref(), &template.template_id.to_le_bytes()],
+        bump = template.bump
+    )]
+    pub template: Account<'info, GraphTemplate>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InstantiateTemplate<'info> {
+    #[account(
+        mut,
+        constraint = !graph.paused @ BountyGraphError::GraphPaused,
+        seeds = [Graph::SEED_PREFIX, graph.authority.as_ref(), &graph.id_seed()],
+        bump = graph.bump
+    )]
+    pub graph: Account<'info, Graph>,
+
+    pub template: Account<'info, GraphTemplate>,
+
+    /// Required under `CreationPolicy::AuthorityOnly`; optional otherwise.
+    pub authority: Option<Signer<'info>>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        seeds = [CreatorAllowlist::SEED_PREFIX, graph.key().as_ref(), creator.key().as_ref()],
+        bump = creator_allowlist.bump
+    )]
+    pub creator_allowlist: Option<Account<'info, CreatorAllowlist>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveExternalDependency<'info> {
+    /// The graph holding the depended-upon task, not the dependent task's graph.
+    #[account(
+        has_one = authority,
+        seeds = [Graph::SEED_PREFIX, authority.key().as_ref(), &graph.id_seed()],
+        bump = graph.bump
+    )]
+    pub graph: Account<'info, Graph>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub task: Account<'info, Task>,
+}
+
+#[derive(Accounts)]
+pub struct FundTask<'info> {
+    #[account(
+        constraint = !graph.paused @ BountyGraphError::GraphPaused,
+        seeds = [Graph::SEED_PREFIX, graph.authority.as_ref(), &graph.id_seed()],
+        bump = graph.bump
+    )]
+    pub graph: Account<'info, Graph>,
+
+    #[account(mut, constraint = task.graph == graph.key() @ BountyGraphError::InvalidGraph)]
+    pub task: Account<'info, Task>,
+
+    #[account(
+        init_if_needed,
+        payer = funder,
+        space = 8 + Escrow::INIT_SPACE,
+        seeds = [Escrow::SEED_PREFIX, task.key().as_ref()],
+        bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    /// Required, and opened on first use, when the funder is not the task's creator.
+    #[account(
+        init_if_needed,
+        payer = funder,
+        space = 8 + Deposit::INIT_SPACE,
+        seeds = [Deposit::SEED_PREFIX, task.key().as_ref(), funder.key().as_ref()],
+        bump
+    )]
+    pub deposit: Option<Account<'info, Deposit>>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FundingStatus<'info> {
+    pub task: Account<'info, Task>,
+}
+
+#[derive(Accounts)]
+pub struct IncreaseReward<'info> {
+    #[account(
+        constraint = !graph.paused @ BountyGraphError::GraphPaused,
+        seeds = [Graph::SEED_PREFIX, graph.authority.as_ref(), &graph.id_seed()],
+        bump = graph.bump
+    )]
+    pub graph: Account<'info, Graph>,
+
+    #[account(mut, constraint = task.graph == graph.key() @ BountyGraphError::InvalidGraph)]
+    pub task: Account<'info, Task>,
+
+    // Top-ups only go to an escrow that `fund_task` has already set up.
+    #[account(
+        mut,
+        seeds = [Escrow::SEED_PREFIX, task.key().as_ref()],
+        bump = escrow.bump,
+        constraint = escrow.task == task.key() @ BountyGraphError::EscrowEmpty
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    /// Required, and opened on first use, when the funder is not the task's creator.
+    #[account(
+        init_if_needed,
+        payer = funder,
+        space = 8 + Deposit::INIT_SPACE,
+        seeds = [Deposit::SEED_PREFIX, task.key().as_ref(), funder.key().as_ref()],
+        bump
+    )]
+    pub deposit: Option<Account<'info, Deposit>>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(params: SubmitReceiptParams)]
+pub struct SubmitReceipt<'info> {
+    #[account(
+        constraint = !graph.paused @ BountyGraphError::GraphPaused,
+        seeds = [Graph::SEED_PREFIX, graph.authority.as_ref(), &graph.id_seed()],
+        bump = graph.bump
+    )]
+    pub graph: Account<'info, Graph>,
+
+    #[account(mut, constraint = task.graph == graph.key() @ BountyGraphError::InvalidGraph)]
+    pub task: Account<'info, Task>,
+
+    #[account(
+        init,
+        payer = agent,
+        space = 8 + Receipt::INIT_SPACE,
+        seeds = [Receipt::SEED_PREFIX, task.key().as_ref(), agent.key().as_ref()],
+        bump
+    )]
+    pub receipt: Account<'info, Receipt>,
+
+    #[account(mut)]
+    pub agent: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RollUp<'info> {
+    #[account(
+        constraint = !graph.paused @ BountyGraphError::GraphPaused,
+        seeds = [Graph::SEED_PREFIX, graph.authority.as_ref(), &graph.id_seed()],
+        bump = graph.bump
+    )]
+    pub graph: Account<'info, Graph>,
+
+    #[account(mut, constraint = task.graph == graph.key() @ BountyGraphError::InvalidGraph)]
+    pub task: Account<'info, Task>,
+
+    /// Required if the epic carries a funded bonus.
+    #[account(
+        mut,
+        seeds = [Escrow::SEED_PREFIX, task.key().as_ref()],
+        bump = escrow.bump,
+        constraint = escrow.task == task.key() @ BountyGraphError::InvalidDependency
+    )]
+    pub escrow: Option<Account<'info, Escrow>>,
+
+    #[account(seeds = [ProtocolConfig::SEED_PREFIX], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// CHECK: Only credited with the protocol fee; pinned to `protocol_config.treasury`.
+    #[account(mut, address = protocol_config.treasury @ BountyGraphError::InvalidTreasury)]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// CHECK: Only credited with the graph fee; pinned to `graph.authority`.
+    #[account(mut, address = graph.authority @ BountyGraphError::InvalidGraph)]
+    pub graph_authority: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimReward<'info> {
+    #[account(
+        constraint = !graph.paused @ BountyGraphError::GraphPaused,
+        seeds = [Graph::SEED_PREFIX, graph.authority.as_ref(), &graph.id_seed()],
+        bump = graph.bump
+    )]
+    pub graph: Account<'info, Graph>,
+
+    #[account(mut, constraint = task.graph == graph.key() @ BountyGraphError::InvalidGraph)]
+    pub task: Account<'info, Task>,
+
+    #[account(
+        mut,
+        constraint = escrow.task == task.key() @ BountyGraphError::InvalidDependency,
+        seeds = [Escrow::SEED_PREFIX, task.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(seeds = [ProtocolConfig::SEED_PREFIX], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// CHECK: Only credited with the protocol fee; pinned to `protocol_config.treasury`.
+    #[account(mut, address = protocol_config.treasury @ BountyGraphError::InvalidTreasury)]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// CHECK: Only credited with the graph fee; pinned to `graph.authority`.
+    #[account(mut, address = graph.authority @ BountyGraphError::InvalidGraph)]
+    pub graph_authority: UncheckedAccount<'info>,
+
+    /// Competition tasks: the caller's winning receipt. Team tasks: the completing receipt.
+    #[account(mut, constraint = receipt.task == task.key() @ BountyGraphError::InvalidContributors)]
+    pub receipt: Option<Account<'info, Receipt>>,
+
+    #[account(mut)]
+    pub agent: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawVested<'info> {
+    #[account(
+        constraint = !graph.paused @ BountyGraphError::GraphPaused,
+        seeds = [Graph::SEED_PREFIX, graph.authority.as_ref(), &graph.id_seed()],
+        bump = graph.bump
+    )]
+    pub graph: Account<'info, Graph>,
+
+    #[account(mut, constraint = task.graph == graph.key() @ BountyGraphError::InvalidGraph)]
+    pub task: Account<'info, Task>,
+
+    #[account(
+        mut,
+        constraint = escrow.task == task.key() @ BountyGraphError::InvalidDependency,
+        seeds = [Escrow::SEED_PREFIX, task.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(seeds = [ProtocolConfig::SEED_PREFIX], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// CHECK: Only credited with the protocol fee; pinned to `protocol_config.treasury`.
+    #[account(mut, address = protocol_config.treasury @ BountyGraphError::InvalidTreasury)]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// CHECK: Only credited with the graph fee; pinned to `graph.authority`.
+    #[account(mut, address = graph.authority @ BountyGraphError::InvalidGraph)]
+    pub graph_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub agent: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SelectWinners<'info> {
+    #[account(
+        seeds = [Graph::SEED_PREFIX, graph.authority.as_ref(), &graph.id_seed()],
+        bump = graph.bump
+    )]
+    pub graph: Account<'info, Graph>,
+
+    #[account(mut, constraint = task.graph == graph.key() @ BountyGraphError::InvalidGraph)]
+    pub task: Account<'info, Task>,
+
+    #[account(
+        constraint = escrow.task == task.key() @ BountyGraphError::InvalidDependency,
+        seeds = [Escrow::SEED_PREFIX, task.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    pub selector: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(params: DisputeTaskParams)]
+pub struct DisputeTask<'info> {
+    #[account(mut)]
+    pub task: Account<'info, Task>,
+
+    #[account(
+        init,
+        payer = initiator,
+        space = 8 + Dispute::space_for(&params.reason),
+        seeds = [Dispute::SEED_PREFIX, task.key().as_ref(), initiator.key().as_ref()],
+        bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(mut)]
+    pub initiator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    #[account(
+        has_one = authority,
+        seeds = [Graph::SEED_PREFIX, authority.key().as_ref(), &graph.id_seed()],
+        bump = graph.bump
+    )]
+    pub graph: Account<'info, Graph>,
+
+    /// Also receives the graph fee on the worker portion.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(mut, constraint = task.graph == graph.key() @ BountyGraphError::InvalidGraph)]
+    pub task: Account<'info, Task>,
+
+    #[account(mut)]
+    pub dispute: Account<'info, Dispute>,
+
+    /// CHECK: The task's escrow PDA. It does not exist if the task was never funded or has been
+    /// paid out; only a program-owned balance is split.
+    #[account(mut, seeds = [Escrow::SEED_PREFIX, task.key().as_ref()], bump)]
+    pub escrow: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub creator: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub worker: SystemAccount<'info>,
+
+    #[account(seeds = [ProtocolConfig::SEED_PREFIX], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// CHECK: Only credited with the protocol fee; pinned to `protocol_config.treasury`.
+    #[account(mut, address = protocol_config.treasury @ BountyGraphError::InvalidTreasury)]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// The completing receipt; required when the task was delivered by a team.
+    #[account(constraint = receipt.task == task.key() @ BountyGraphError::InvalidContributors)]
+    pub receipt: Option<Account<'info, Receipt>>,
+}
+
+#[derive(Accounts)]
+#[instruction(params: SubmitMilestoneParams)]
+pub struct SubmitMilestone<'info> {
+    #[account(
+        constraint = !graph.paused @ BountyGraphError::GraphPaused,
+        seeds = [Graph::SEED_PREFIX, graph.authority.as_ref(), &graph.id_seed()],
+        bump = graph.bump
+    )]
+    pub graph: Account<'info, Graph>,
+
+    #[account(mut, constraint = task.graph == graph.key() @ BountyGraphError::InvalidGraph)]
+    pub task: Account<'info, Task>,
+
+    #[account(
+        init,
+        payer = agent,
+        space = 8 + Receipt::INIT_SPACE,
+        seeds = [
+            Receipt::SEED_PREFIX,
+            task.key().as_ref(),
+            agent.key().as_ref(),
+            &[params.index]
+        ],
+        bump
+    )]
+    pub receipt: Account<'info, Receipt>,
+
+    #[account(mut)]
+    pub agent: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AttestReceipt<'info> {
+    #[account(
+        constraint = !graph.paused @ BountyGraphError::GraphPaused,
+        seeds = [Graph::SEED_PREFIX, graph.authority.as_ref(), &graph.id_seed()],
+        bump = graph.bump
+    )]
+    pub graph: Account<'info, Graph>,
+
+    #[account(mut, constraint = task.graph == graph.key() @ BountyGraphError::InvalidGraph)]
+    pub task: Account<'info, Task>,
+
+    /// A whole-task receipt; milestone and completion receipts have longer seeds.
+    #[account(
+        seeds = [Receipt::SEED_PREFIX, task.key().as_ref(), receipt.agent.as_ref()],
+        bump = receipt.bump
+    )]
+    pub receipt: Account<'info, Receipt>,
+
+    /// CHECK: Pinned to the instructions sysvar, read for the Ed25519 precompile instructions.
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RejectReceipt<'info> {
+    #[account(mut, has_one = creator)]
+    pub task: Account<'info, Task>,
+
+    /// A whole-task receipt; milestone and completion receipts have longer seeds.
+    #[account(
+        mut,
+        close = agent,
+        has_one = task,
+        has_one = agent,
+        seeds = [Receipt::SEED_PREFIX, task.key().as_ref(), agent.key().as_ref()],
+        bump = receipt.bump
+    )]
+    pub receipt: Account<'info, Receipt>,
+
+    pub creator: Signer<'info>,
+
+    #[account(mut)]
+    pub agent: SystemAccount<'info>,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct SubmitReceiptParams {
-    pub work_hash: [u8; 32],
-    pub uri: String,
+#[derive(Accounts)]
+pub struct CommitReceipt<'info> {
+    #[account(
+        constraint = !graph.paused @ BountyGraphError::GraphPaused,
+        seeds = [Graph::SEED_PREFIX, graph.authority.as_ref(), &graph.id_seed()],
+        bump = graph.bump
+    )]
+    pub graph: Account<'info, Graph>,
+
+    #[account(mut, constraint = task.graph == graph.key() @ BountyGraphError::InvalidGraph)]
+    pub task: Account<'info, Task>,
+
+    #[account(
+        init,
+        payer = agent,
+        space = 8 + ReceiptCommitment::INIT_SPACE,
+        seeds = [ReceiptCommitment::SEED_PREFIX, task.key().as_ref(), agent.key().as_ref()],
+        bump
+    )]
+    pub commitment: Account<'info, ReceiptCommitment>,
+
+    #[account(mut)]
+    pub agent: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct InitializeGraph<'info> {
+pub struct RevealReceipt<'info> {
+    #[account(
+        constraint = !graph.paused @ BountyGraphError::GraphPaused,
+        seeds = [Graph::SEED_PREFIX, graph.authority.as_ref(), &graph.id_seed()],
+        bump = graph.bump
+    )]
+    pub graph: Account<'info, Graph>,
+
+    #[account(mut, constraint = task.graph == graph.key() @ BountyGraphError::InvalidGraph)]
+    pub task: Account<'info, Task>,
+
+    /// Closed on reveal, returning its rent to the agent.
+    #[account(
+        mut,
+        close = agent,
+        seeds = [ReceiptCommitment::SEED_PREFIX, task.key().as_ref(), agent.key().as_ref()],
+        bump = commitment.bump
+    )]
+    pub commitment: Account<'info, ReceiptCommitment>,
+
     #[account(
         init,
-        payer = authority,
-        space = 8 + Graph::INIT_SPACE,
-        seeds = [Graph::SEED_PREFIX, authority.key().as_ref()],
+        payer = agent,
+        space = 8 + Receipt::INIT_SPACE,
+        seeds = [Receipt::SEED_PREFIX, task.key().as_ref(), agent.key().as_ref()],
         bump
     )]
-    pub graph: Account<'info, Graph>,
+    pub receipt: Account<'info, Receipt>,
 
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub agent: Signer<'info>,
 
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-#[instruction(params: CreateTaskParams)]
-pub struct CreateTask<'info> {
+pub struct SubmitCompletion<'info> {
     #[account(
-        mut,
-        has_one = authority,
-        seeds = [Graph::SEED_PREFIX, authority.key().as_ref()],
+        constraint = !graph.paused @ BountyGraphError::GraphPaused,
+        seeds = [Graph::SEED_PREFIX, graph.authority.as_ref(), &graph.id_seed()],
         bump = graph.bump
     )]
     pub graph: Account<'info, Graph>,
 
-    pub authority: Signer<'info>,
-
-    #[account(mut)]
-    pub creator: Signer<'info>,
+    #[account(mut, constraint = task.graph == graph.key() @ BountyGraphError::InvalidGraph)]
+    pub task: Account<'info, Task>,
 
+    /// One receipt per completion, so the same agent can complete a recurring task repeatedly.
     #[account(
         init,
-        payer = creator,
-        space = 8 + Task::space_for(&params.dependencies),
-        seeds = [Task::SEED_PREFIX, graph.key().as_ref(), &params.task_id.to_le_bytes()],
+        payer = agent,
+        space = 8 + Receipt::INIT_SPACE,
+        seeds = [
+            Receipt::SEED_PREFIX,
+            task.key().as_ref(),
+            agent.key().as_ref(),
+            &task.completion_count.to_le_bytes()
+        ],
         bump
     )]
-    pub task: Account<'info, Task>,
+    pub receipt: Account<'info, Receipt>,
+
+    #[account(mut)]
+    pub agent: Signer<'info>,
 
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct FundTask<'info> {
-    #[account(mut)]
+pub struct ApproveCompletion<'info> {
+    #[account(
+        constraint = !graph.paused @ BountyGraphError::GraphPaused,
+        seeds = [Graph::SEED_PREFIX, graph.authority.as_ref(), &graph.id_seed()],
+        bump = graph.bump
+    )]
+    pub graph: Account<'info, Graph>,
+
+    #[account(
+        mut,
+        has_one = creator,
+        constraint = task.graph == graph.key() @ BountyGraphError::InvalidGraph
+    )]
     pub task: Account<'info, Task>,
 
     #[account(
-        init_if_needed,
-        payer = funder,
-        space = 8 + Escrow::INIT_SPACE,
+        mut,
+        constraint = receipt.agent == worker.key() @ BountyGraphError::InvalidWorker,
+        seeds = [
+            Receipt::SEED_PREFIX,
+            task.key().as_ref(),
+            worker.key().as_ref(),
+            &task.completion_count.to_le_bytes()
+        ],
+        bump = receipt.bump
+    )]
+    pub receipt: Account<'info, Receipt>,
+
+    #[account(
+        mut,
+        constraint = escrow.task == task.key() @ BountyGraphError::InvalidDependency,
         seeds = [Escrow::SEED_PREFIX, task.key().as_ref()],
-        bump
+        bump = escrow.bump
     )]
     pub escrow: Account<'info, Escrow>,
 
+    #[account(seeds = [ProtocolConfig::SEED_PREFIX], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// CHECK: Only credited with the protocol fee; pinned to `protocol_config.treasury`.
+    #[account(mut, address = protocol_config.treasury @ BountyGraphError::InvalidTreasury)]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// CHECK: Only credited with the graph fee; pinned to `graph.authority`.
+    #[account(mut, address = graph.authority @ BountyGraphError::InvalidGraph)]
+    pub graph_authority: UncheckedAccount<'info>,
+
+    pub creator: Signer<'info>,
+
     #[account(mut)]
-    pub funder: Signer<'info>,
+    pub worker: SystemAccount<'info>,
+}
 
-    pub system_program: Program<'info, System>,
+#[derive(Accounts)]
+pub struct RejectCompletion<'info> {
+    #[account(mut, has_one = creator)]
+    pub task: Account<'info, Task>,
+
+    #[account(
+        mut,
+        close = worker,
+        constraint = receipt.agent == worker.key() @ BountyGraphError::InvalidWorker,
+        seeds = [
+            Receipt::SEED_PREFIX,
+            task.key().as_ref(),
+            worker.key().as_ref(),
+            &task.completion_count.to_le_bytes()
+        ],
+        bump = receipt.bump
+    )]
+    pub receipt: Account<'info, Receipt>,
+
+    pub creator: Signer<'info>,
+
+    #[account(mut)]
+    pub worker: SystemAccount<'info>,
 }
 
 #[derive(Accounts)]
-#[instruction(params: SubmitReceiptParams)]
-pub struct SubmitReceipt<'info> {
+pub struct ApproveMilestone<'info> {
+    #[account(
+        constraint = !graph.paused @ BountyGraphError::GraphPaused,
+        seeds = [Graph::SEED_PREFIX, graph.authority.as_ref(), &graph.id_seed()],
+        bump = graph.bump
+    )]
+    pub graph: Account<'info, Graph>,
+
+    #[account(
+        mut,
+        has_one = creator,
+        constraint = task.graph == graph.key() @ BountyGraphError::InvalidGraph
+    )]
+    pub task: Account<'info, Task>,
+
+    #[account(
+        mut,
+        constraint = escrow.task == task.key() @ BountyGraphError::InvalidDependency,
+        seeds = [Escrow::SEED_PREFIX, task.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(seeds = [ProtocolConfig::SEED_PREFIX], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// CHECK: Only credited with the protocol fee; pinned to `protocol_config.treasury`.
+    #[account(mut, address = protocol_config.treasury @ BountyGraphError::InvalidTreasury)]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// CHECK: Only credited with the graph fee; pinned to `graph.authority`.
+    #[account(mut, address = graph.authority @ BountyGraphError::InvalidGraph)]
+    pub graph_authority: UncheckedAccount<'info>,
+
+    pub creator: Signer<'info>,
+
     #[account(mut)]
+    pub worker: SystemAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(index: u8)]
+pub struct RejectMilestone<'info> {
+    #[account(mut, has_one = creator)]
     pub task: Account<'info, Task>,
 
     #[account(
-        init,
-        payer = agent,
-        space = 8 + Receipt::INIT_SPACE,
-        seeds = [Receipt::SEED_PREFIX, task.key().as_ref(), agent.key().as_ref()],
-        bump
+        mut,
+        close = worker,
+        seeds = [
+            Receipt::SEED_PREFIX,
+            task.key().as_ref(),
+            worker.key().as_ref(),
+            &[index]
+        ],
+        bump = receipt.bump
     )]
     pub receipt: Account<'info, Receipt>,
 
+    pub creator: Signer<'info>,
+
     #[account(mut)]
-    pub agent: Signer<'info>,
+    pub worker: SystemAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptContribution<'info> {
+    #[account(mut)]
+    pub receipt: Account<'info, Receipt>,
+
+    pub contributor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    /// CHECK: Owner, discriminator and layout are verified by `migration::migrate`.
+    #[account(mut)]
+    pub account: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
 
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct ClaimReward<'info> {
+pub struct MigrateTask<'info> {
+    /// CHECK: Owner, discriminator and layout are verified by `migration::migrate`.
+    #[account(mut)]
+    pub account: UncheckedAccount<'info>,
+
+    /// CHECK: The task's escrow PDA, read for its balance; it need not exist.
+    #[account(seeds = [Escrow::SEED_PREFIX, account.key().as_ref()], bump)]
+    pub escrow: UncheckedAccount<'info>,
+
     #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AmendTaskSpec<'info> {
+    #[account(
+        constraint = !graph.paused @ BountyGraphError::GraphPaused,
+        seeds = [Graph::SEED_PREFIX, graph.authority.as_ref(), &graph.id_seed()],
+        bump = graph.bump
+    )]
+    pub graph: Account<'info, Graph>,
+
+    #[account(
+        mut,
+        has_one = creator,
+        constraint = task.graph == graph.key() @ BountyGraphError::InvalidGraph
+    )]
+    pub task: Account<'info, Task>,
+
+    pub creator: Signer<'info>,
+
+    /// Countersignature of the engaged worker; see `Task::engaged_worker`.
+    pub worker: Option<Signer<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct CancelTask<'info> {
+    #[account(mut, has_one = creator)]
     pub task: Account<'info, Task>,
 
     #[account(
@@ -589,64 +3453,159 @@ pub struct ClaimReward<'info> {
         seeds = [Escrow::SEED_PREFIX, task.key().as_ref()],
         bump = escrow.bump
     )]
-    pub escrow: Account<'info, Escrow>,
+    pub escrow: Option<Account<'info, Escrow>>,
 
+    /// The epic named by `task.parent_task_id`, if any.
     #[account(mut)]
-    pub agent: Signer<'info>,
+    pub parent: Option<Account<'info, Task>>,
 
-    pub system_program: Program<'info, System>,
+    #[account(mut)]
+    pub creator: Signer<'info>,
 }
 
 #[derive(Accounts)]
-#[instruction(params: DisputeTaskParams)]
-pub struct DisputeTask<'info> {
+pub struct RefundDeposit<'info> {
     #[account(mut)]
     pub task: Account<'info, Task>,
 
+    /// Required when the task was cancelled.
     #[account(
-        init,
-        payer = initiator,
-        space = 8 + Dispute::space_for(&params.reason),
-        seeds = [Dispute::SEED_PREFIX, task.key().as_ref(), initiator.key().as_ref()],
-        bump
+        mut,
+        constraint = escrow.task == task.key() @ BountyGraphError::InvalidDependency,
+        seeds = [Escrow::SEED_PREFIX, task.key().as_ref()],
+        bump = escrow.bump
     )]
-    pub dispute: Account<'info, Dispute>,
+    pub escrow: Option<Account<'info, Escrow>>,
+
+    #[account(
+        mut,
+        close = funder,
+        has_one = task,
+        has_one = funder,
+        seeds = [Deposit::SEED_PREFIX, task.key().as_ref(), funder.key().as_ref()],
+        bump = deposit.bump
+    )]
+    pub deposit: Account<'info, Deposit>,
 
     #[account(mut)]
-    pub initiator: Signer<'info>,
+    pub funder: SystemAccount<'info>,
 
-    pub system_program: Program<'info, System>,
+    /// Receives what is left of the escrow after the last refund.
+    #[account(mut, address = task.creator @ BountyGraphError::InvalidCreator)]
+    pub creator: SystemAccount<'info>,
 }
 
 #[derive(Accounts)]
-pub struct ResolveDispute<'info> {
+pub struct CloseTask<'info> {
     #[account(
-        has_one = authority,
-        seeds = [Graph::SEED_PREFIX, authority.key().as_ref()],
+        seeds = [Graph::SEED_PREFIX, graph.authority.as_ref(), &graph.id_seed()],
         bump = graph.bump
     )]
     pub graph: Account<'info, Graph>,
 
-    pub authority: Signer<'info>,
+    /// CHECK: Decoded as a `Task` in the handler; rewritten in place as a `TaskTombstone`.
+    #[account(mut)]
+    pub task: UncheckedAccount<'info>,
 
-    #[account(mut, constraint = task.graph == graph.key() @ BountyGraphError::InvalidGraph)]
-    pub task: Account<'info, Task>,
+    #[account(mut)]
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseReceipt<'info> {
+    #[account(
+        seeds = [Graph::SEED_PREFIX, graph.authority.as_ref(), &graph.id_seed()],
+        bump = graph.bump
+    )]
+    pub graph: Account<'info, Graph>,
+
+    /// CHECK: Live task or tombstone; decoded by `load_task_view`.
+    pub task: UncheckedAccount<'info>,
+
+    /// Whole-task, milestone or completion receipt; the seeds follow its recorded index.
+    #[account(
+        mut,
+        close = agent,
+        has_one = task,
+        has_one = agent,
+        seeds = [
+            Receipt::SEED_PREFIX,
+            task.key().as_ref(),
+            agent.key().as_ref(),
+            &receipt.index_seed()
+        ],
+        bump = receipt.bump
+    )]
+    pub receipt: Account<'info, Receipt>,
 
     #[account(mut)]
-    pub dispute: Account<'info, Dispute>,
+    pub agent: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseCommitment<'info> {
+    /// CHECK: Only a seed; the task may since have been closed to a tombstone.
+    pub task: UncheckedAccount<'info>,
 
     #[account(
         mut,
-        seeds = [Escrow::SEED_PREFIX, task.key().as_ref()],
-        bump = escrow.bump
+        close = agent,
+        has_one = task,
+        has_one = agent,
+        seeds = [ReceiptCommitment::SEED_PREFIX, task.key().as_ref(), agent.key().as_ref()],
+        bump = commitment.bump
     )]
-    pub escrow: Account<'info, Escrow>,
+    pub commitment: Account<'info, ReceiptCommitment>,
 
     #[account(mut)]
-    pub creator: SystemAccount<'info>,
+    pub agent: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseDispute<'info> {
+    #[account(
+        seeds = [Graph::SEED_PREFIX, graph.authority.as_ref(), &graph.id_seed()],
+        bump = graph.bump
+    )]
+    pub graph: Account<'info, Graph>,
+
+    /// CHECK: Live task or tombstone; decoded by `load_task_view`.
+    pub task: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = initiator,
+        has_one = task,
+        constraint = dispute.raised_by == initiator.key() @ BountyGraphError::UnauthorizedDispute,
+        seeds = [Dispute::SEED_PREFIX, task.key().as_ref(), initiator.key().as_ref()],
+        bump = dispute.bump
+    )]
+    pub dispute: Account<'info, Dispute>,
 
     #[account(mut)]
-    pub worker: SystemAccount<'info>,
+    pub initiator: Signer<'info>,
+}
 
-    pub system_program: Program<'info, System>,
+#[derive(Accounts)]
+pub struct ClaimTask<'info> {
+    #[account(mut)]
+    pub task: Account<'info, Task>,
+
+    pub agent: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UnclaimTask<'info> {
+    #[account(mut)]
+    pub task: Account<'info, Task>,
+
+    pub agent: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AssignTask<'info> {
+    #[account(mut, has_one = creator)]
+    pub task: Account<'info, Task>,
+
+    pub creator: Signer<'info>,
 }
@@ -0,0 +1,339 @@
+//! In-place upgrades from the v0 (pre-versioning) account layouts.
+//!
+//! v0 accounts keep their discriminator, so they are recognised by type, decoded with the frozen
+//! layouts below, upgraded in memory, and written back after a realloc. Read-only paths that must
+//! keep working mid-migration (dependency checks) go through [`load_task`], which accepts both.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{program::invoke, system_instruction};
+use anchor_lang::Discriminator;
+
+use crate::error::BountyGraphError;
+use crate::state::*;
+
+#[derive(AnchorDeserialize)]
+pub struct GraphV0 {
+    pub authority: Pubkey,
+    pub max_dependencies_per_task: u16,
+    pub task_count: u64,
+    pub bump: u8,
+}
+
+#[derive(AnchorDeserialize)]
+pub struct TaskV0 {
+    pub graph: Pubkey,
+    pub task_id: u64,
+    pub creator: Pubkey,
+    pub reward_lamports: u64,
+    pub status: TaskStatus,
+    pub dispute_status: DisputeStatus,
+    pub dependencies: Vec<u64>,
+    pub created_at_slot: u64,
+    pub completed_by: Option<Pubkey>,
+    pub disputed_by: Option<Pubkey>,
+    pub dispute_raised_at_slot: u64,
+    pub resolved_by: Option<Pubkey>,
+    pub dispute_resolved_at_slot: u64,
+    pub worker_award_lamports: u64,
+    pub bump: u8,
+}
+
+#[derive(AnchorDeserialize)]
+pub struct EscrowV0 {
+    pub task: Pubkey,
+    pub bump: u8,
+}
+
+#[derive(AnchorDeserialize)]
+pub struct ReceiptV0 {
+    pub task: Pubkey,
+    pub agent: Pubkey,
+    pub work_hash: [u8; 32],
+    pub uri: String,
+    pub submitted_at_slot: u64,
+    pub bump: u8,
+}
+
+#[derive(AnchorDeserialize)]
+pub struct DisputeV0 {
+    pub task: Pubkey,
+    pub creator: Pubkey,
+    pub worker: Pubkey,
+    pub raised_by: Pubkey,
+    pub reason: String,
+    pub status: DisputeStatus,
+    pub raised_at_slot: u64,
+    pub resolved_at_slot: Option<u64>,
+    pub arbiter: Option<Pubkey>,
+    pub creator_pct: Option<u8>,
+    pub worker_pct: Option<u8>,
+    pub bump: u8,
+}
+
+impl GraphV0 {
+    pub fn upgrade(self) -> Graph {
+        Graph {
+            authority: self.authority,
+            graph_id: Graph::LEGACY_GRAPH_ID,
+            max_dependencies_per_task: self.max_dependencies_per_task,
+            task_count: self.task_count,
+            creation_policy: CreationPolicy::AuthorityOnly,
+            creation_deposit_lamports: 0,
+            guardian: None,
+            paused: false,
+            pause_reason: 0,
+            paused_at_slot: 0,
+            min_reward_lamports: Graph::DEFAULT_MIN_REWARD_LAMPORTS,
+            max_reward_lamports: 0,
+            config_version: 1,
+            close_retention_slots: Graph::DEFAULT_CLOSE_RETENTION_SLOTS,
+            graph_fee_bps: 0,
+            require_dependent_approval: false,
+            dependency_dispute_policy: Graph::DEFAULT_DEPENDENCY_DISPUTE_POLICY,
+            bump: self.bump,
+            version: ACCOUNT_VERSION,
+        }
+    }
+}
+
+impl TaskV0 {
+    pub fn upgrade(self) -> Task {
+        Task {
+            graph: self.graph,
+            task_id: self.task_id,
+            creator: self.creator,
+            reward_lamports: self.reward_lamports,
+            status: self.status,
+            dispute_status: self.dispute_status,
+            dependencies: self.dependencies,
+            created_at_slot: self.created_at_slot,
+            completed_by: self.completed_by,
+            disputed_by: self.disputed_by,
+            dispute_raised_at_slot: self.dispute_raised_at_slot,
+            resolved_by: self.resolved_by,
+            dispute_resolved_at_slot: self.dispute_resolved_at_slot,
+            worker_award_lamports: self.worker_award_lamports,
+            creation_deposit_lamports: 0,
+            // Predates graph config versioning.
+            config_version: 0,
+            settled_at_slot: 0,
+            claim_duration_slots: 0,
+            assignee: None,
+            claim_expires_at_slot: 0,
+            competition_deadline_slot: 0,
+            submission_count: 0,
+            payout_pool_lamports: 0,
+            unclaimed_payout_bps: 0,
+            team_size: 0,
+            milestones: Vec::new(),
+            vesting: None,
+            vesting_start_slot: 0,
+            vested_withdrawn_lamports: 0,
+            crowdfunded: false,
+            require_funding: false,
+            // Predates funding tracking; `migrate_task` sets it from the escrow balance.
+            funded_lamports: 0,
+            dependency_groups: Vec::new(),
+            external_dependencies: Vec::new(),
+            // v0 resolutions did not record the split on the task; treat them as upheld.
+            dispute_worker_pct: 100,
+            is_epic: false,
+            parent_task_id: None,
+            child_count: 0,
+            spec_uri: String::new(),
+            spec_hash: [0; 32],
+            category: 0,
+            tags: [0; Task::MAX_TAGS],
+            spec_revision: 0,
+            max_completions: 0,
+            completion_count: 0,
+            recurrence_interval_slots: 0,
+            recurrence_end_slot: 0,
+            last_completed_slot: 0,
+            pending_completion_agent: None,
+            last_commit_slot: 0,
+            verifiers: Vec::new(),
+            verifier_threshold: 0,
+            deposited_lamports: 0,
+            depositor_count: 0,
+            refund_pool_lamports: 0,
+            bump: self.bump,
+            version: ACCOUNT_VERSION,
+        }
+    }
+}
+
+impl EscrowV0 {
+    pub fn upgrade(self) -> Escrow {
+        Escrow {
+            task: self.task,
+            bump: self.bump,
+            version: ACCOUNT_VERSION,
+        }
+    }
+}
+
+impl ReceiptV0 {
+    pub fn upgrade(self) -> Receipt {
+        Receipt {
+            task: self.task,
+            agent: self.agent,
+            work_hash: self.work_hash,
+            uri: self.uri,
+            submitted_at_slot: self.submitted_at_slot,
+            payout_bps: 0,
+            claimed: false,
+            contributors: Vec::new(),
+            milestone_index: None,
+            completion_index: None,
+            bump: self.bump,
+            version: ACCOUNT_VERSION,
+        }
+    }
+}
+
+impl DisputeV0 {
+    pub fn upgrade(self) -> Dispute {
+        Dispute {
+            task: self.task,
+            creator: self.creator,
+            worker: self.worker,
+            raised_by: self.raised_by,
+            reason: self.reason,
+            status: self.status,
+            raised_at_slot: self.raised_at_slot,
+            resolved_at_slot: self.resolved_at_slot,
+            arbiter: self.arbiter,
+            creator_pct: self.creator_pct,
+            worker_pct: self.worker_pct,
+            spec_revision: 0,
+            bump: self.bump,
+            version: ACCOUNT_VERSION,
+        }
+    }
+}
+
+/// Implemented by every versioned account so the migration helpers can tell v0 from current.
+pub trait Versioned {
+    fn version(&self) -> u8;
+}
+
+macro_rules! impl_versioned {
+    ($($ty:ty),*) => {
+        $(impl Versioned for $ty {
+            fn version(&self) -> u8 {
+                self.version
+            }
+        })*
+    };
+}
+
+impl_versioned!(Graph, Task, Escrow, Receipt, Dispute);
+
+/// Returns true if `data` already holds a current-layout `T`.
+fn is_current<T>(data: &[u8]) -> bool
+where
+    T: AccountDeserialize + Versioned,
+{
+    let mut slice = data;
+    matches!(T::try_deserialize(&mut slice), Ok(a) if a.version() == ACCOUNT_VERSION)
+}
+
+/// Decode `info` as a v0 `L` after checking owner and discriminator for `T`.
+fn decode_v0<T, L>(info: &AccountInfo) -> Result<L>
+where
+    T: Discriminator + AccountDeserialize + Versioned,
+    L: AnchorDeserialize,
+{
+    require_keys_eq!(
+        *info.owner,
+        crate::ID,
+        BountyGraphError::InvalidAccountLayout
+    );
+    let data = info.try_borrow_data()?;
+    require!(
+        data.len() >= 8 && data[..8] == T::DISCRIMINATOR,
+        BountyGraphError::InvalidAccountLayout
+    );
+    require!(!is_current::<T>(&data), BountyGraphError::AlreadyMigrated);
+
+    let mut body: &[u8] = &data[8..];
+    L::deserialize(&mut body).map_err(|_| error!(BountyGraphError::InvalidAccountLayout))
+}
+
+/// Resize `info` to `new_len`, topping up rent from `payer`, and write `account` into it.
+fn write_upgraded<'info, T>(
+    info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    account: &T,
+    new_len: usize,
+) -> Result<()>
+where
+    T: AccountSerialize,
+{
+    let rent_needed = Rent::get()?.minimum_balance(new_len);
+    let shortfall = rent_needed.saturating_sub(info.lamports());
+    if shortfall > 0 {
+        invoke(
+            &system_instruction::transfer(payer.key, info.key, shortfall),
+            &[payer.clone(), info.clone(), system_program.clone()],
+        )?;
+    }
+
+    if info.data_len() < new_len {
+        info.realloc(new_len, true)?;
+    }
+
+    let mut data = info.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut data[..];
+    account.try_serialize(&mut writer)
+}
+
+/// What a v0 task's escrow holds above rent. v0 tasks did not record their funding, and were
+/// funded with a single deposit, so this is what `migrate_task` records as `funded_lamports`.
+pub fn v0_escrowed_lamports(escrow: &AccountInfo) -> Result<u64> {
+    if *escrow.owner != crate::ID {
+        return Ok(0);
+    }
+    let rent = Rent::get()?.minimum_balance(escrow.data_len());
+    Ok(escrow.lamports().saturating_sub(rent))
+}
+
+/// Upgrade a v0 account of type `T` (decoded as `L`) in place.
+pub fn migrate<'info, T, L>(
+    info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    upgrade: impl FnOnce(L) -> T,
+    space: impl FnOnce(&T) -> usize,
+) -> Result<T>
+where
+    T: Discriminator + AccountSerialize + AccountDeserialize + Versioned,
+    L: AnchorDeserialize,
+{
+    let legacy = decode_v0::<T, L>(info)?;
+    let account = upgrade(legacy);
+    let new_len = space(&account);
+    write_upgraded(info, payer, system_program, &account, new_len)?;
+    Ok(account)
+}
+
+/// Load a Task in either layout. Used for read-only dependency accounts, so prerequisites keep
+/// unlocking dependents while the graph is only partially migrated.
+pub fn load_task(info: &AccountInfo) -> Result<Task> {
+    require_keys_eq!(
+        *info.owner,
+        crate::ID,
+        BountyGraphError::InvalidAccountLayout
+    );
+    let data = info.try_borrow_data()?;
+    let mut slice: &[u8] = &data;
+    if let Ok(task) = Task::try_deserialize(&mut slice) {
+        if task.version == ACCOUNT_VERSION {
+            return Ok(task);
+        }
+    }
+    drop(data);
+    Ok(decode_v0::<Task, TaskV0>(info)?.upgrade())
+}
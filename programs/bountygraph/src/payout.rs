@@ -0,0 +1,153 @@
+//! Team payout shares and lamport movements out of program-owned escrow PDAs.
+//!
+//! Escrow accounts are owned by this program, so payouts debit them directly rather than going
+//! through `system_instruction::transfer` (which would need the escrow to sign as a system account).
+
+use anchor_lang::prelude::*;
+
+use crate::error::BountyGraphError;
+use crate::events::FeesCollected;
+use crate::state::*;
+use crate::ContributorShare;
+
+/// Protocol and graph fee rates applied to a worker payout.
+#[derive(Clone, Copy)]
+pub struct FeeSchedule {
+    pub protocol_fee_bps: u16,
+    pub graph_fee_bps: u16,
+}
+
+impl FeeSchedule {
+    pub fn new(config: &ProtocolConfig, graph: &Graph) -> Self {
+        Self {
+            protocol_fee_bps: config.fee_bps,
+            graph_fee_bps: graph.graph_fee_bps,
+        }
+    }
+}
+
+/// Move the fees on a `gross` worker payout out of `escrow` and return what is left for the
+/// worker side. Emits `FeesCollected`.
+pub fn collect_fees(
+    escrow: &AccountInfo,
+    treasury: &AccountInfo,
+    graph_fee_recipient: &AccountInfo,
+    fees: FeeSchedule,
+    gross: u64,
+    task: Pubkey,
+) -> Result<u64> {
+    let bps_of = |bps: u16| ((gross as u128) * (bps as u128) / 10_000) as u64;
+    let protocol_fee = bps_of(fees.protocol_fee_bps);
+    let graph_fee = bps_of(fees.graph_fee_bps);
+    let net = gross
+        .checked_sub(protocol_fee)
+        .and_then(|rest| rest.checked_sub(graph_fee))
+        .ok_or(BountyGraphError::ArithmeticOverflow)?;
+
+    if protocol_fee > 0 {
+        **escrow.try_borrow_mut_lamports()? -= protocol_fee;
+        **treasury.try_borrow_mut_lamports()? += protocol_fee;
+    }
+    if graph_fee > 0 {
+        **escrow.try_borrow_mut_lamports()? -= graph_fee;
+        **graph_fee_recipient.try_borrow_mut_lamports()? += graph_fee;
+    }
+
+    emit!(FeesCollected {
+        task,
+        gross_lamports: gross,
+        protocol_fee_lamports: protocol_fee,
+        graph_fee_lamports: graph_fee,
+        treasury: treasury.key(),
+        graph_fee_recipient: graph_fee_recipient.key(),
+    });
+
+    Ok(net)
+}
+
+/// Split `amount` out of `from` across a team by basis-point share.
+///
+/// `recipients` must be the contributors' accounts, writable, in receipt order. The last
+/// contributor absorbs rounding dust so the full `amount` always leaves `from`.
+pub fn pay_team(
+    from: &AccountInfo,
+    contributors: &[Contributor],
+    recipients: &[AccountInfo],
+    amount: u64,
+) -> Result<()> {
+    require!(
+        !contributors.is_empty() && recipients.len() == contributors.len(),
+        BountyGraphError::InvalidContributors
+    );
+
+    let mut remaining = amount;
+    for (i, (contributor, recipient)) in contributors.iter().zip(recipients).enumerate() {
+        require_keys_eq!(
+            recipient.key(),
+            contributor.worker,
+            BountyGraphError::InvalidContributors
+        );
+        let share = if i + 1 == contributors.len() {
+            remaining
+        } else {
+            ((amount as u128) * (contributor.share_bps as u128) / 10_000) as u64
+        };
+        remaining = remaining
+            .checked_sub(share)
+            .ok_or(BountyGraphError::ArithmeticOverflow)?;
+
+        **from.try_borrow_mut_lamports()? -= share;
+        **recipient.try_borrow_mut_lamports()? += share;
+    }
+
+    Ok(())
+}
+
+/// Validate submitted team shares and mark the submitter and any co-signers as accepted.
+///
+/// Returns an empty list for a solo submission, in which case no co-signers may be passed.
+pub fn build_contributors(
+    shares: &[ContributorShare],
+    submitter: Pubkey,
+    cosigners: &[AccountInfo],
+) -> Result<Vec<Contributor>> {
+    require!(
+        shares.len() <= Receipt::MAX_CONTRIBUTORS,
+        BountyGraphError::InvalidContributors
+    );
+    if !shares.is_empty() {
+        require!(
+            shares.iter().all(|s| s.share_bps > 0)
+                && shares.iter().map(|s| s.share_bps as u32).sum::<u32>() == 10_000,
+            BountyGraphError::InvalidContributors
+        );
+        require!(
+            shares.iter().any(|s| s.worker == submitter),
+            BountyGraphError::InvalidContributors
+        );
+    }
+
+    let mut contributors: Vec<Contributor> = Vec::with_capacity(shares.len());
+    for share in shares {
+        require!(
+            contributors.iter().all(|c| c.worker != share.worker),
+            BountyGraphError::InvalidContributors
+        );
+        contributors.push(Contributor {
+            worker: share.worker,
+            share_bps: share.share_bps,
+            accepted: share.worker == submitter,
+        });
+    }
+
+    for cosigner in cosigners {
+        require!(cosigner.is_signer, BountyGraphError::InvalidContributors);
+        let entry = contributors
+            .iter_mut()
+            .find(|c| c.worker == cosigner.key())
+            .ok_or(BountyGraphError::InvalidContributors)?;
+        entry.accepted = true;
+    }
+
+    Ok(contributors)
+}
@@ -1,22 +1,135 @@
 use anchor_lang::prelude::*;
 
+use crate::error::BountyGraphError;
+
+/// Layout version written to every account this program creates.
+///
+/// Accounts created before versioning existed are treated as version 0 and must go through the
+/// matching `migrate_*` instruction. `version` is always the trailing field, so a v0 account can
+/// never be misread as a current one: its bytes either run out early or read back as 0.
+///
+/// Every layout change made before the first release is folded into version 1. From here on, any
+/// change to a versioned account must bump this constant and add the outgoing layout to
+/// `migration.rs`, with an upgrade that fills in the new fields. Handlers only read the current
+/// layout (dependency reads through `load_task` excepted), so accounts are migrated before use,
+/// in the order given in DEPLOYMENT.md.
+pub const ACCOUNT_VERSION: u8 = 1;
+
 #[account]
 #[derive(InitSpace)]
 pub struct Graph {
     pub authority: Pubkey,
+    pub graph_id: u64,
     pub max_dependencies_per_task: u16,
     pub task_count: u64,
+    pub creation_policy: CreationPolicy,
+    pub creation_deposit_lamports: u64,
+    /// Optional key that may pause (but never unpause) the graph.
+    pub guardian: Option<Pubkey>,
+    pub paused: bool,
+    pub pause_reason: u16,
+    pub paused_at_slot: u64,
+    pub min_reward_lamports: u64,
+    /// Upper bound on `Task.reward_lamports`; 0 means uncapped.
+    pub max_reward_lamports: u64,
+    /// Incremented on every `update_graph_config`; tasks snapshot it at creation.
+    pub config_version: u32,
+    /// Slots a finished task, receipt or dispute must age before it can be closed.
+    pub close_retention_slots: u64,
+    /// Additional fee on worker payouts in this graph, paid to `authority`.
+    pub graph_fee_bps: u16,
+    /// If set, tasks in other graphs that depend on this graph's tasks stay locked until
+    /// `authority` approves the edge with `approve_external_dependency`.
+    pub require_dependent_approval: bool,
+    /// How disputes on a prerequisite affect whether it unlocks tasks in this graph.
+    pub dependency_dispute_policy: DependencyDisputePolicy,
     pub bump: u8,
+    pub version: u8,
 }
 
 impl Graph {
     pub const SEED_PREFIX: &'static [u8] = b"graph";
+    pub const DEFAULT_MIN_REWARD_LAMPORTS: u64 = 1_000;
+    /// Roughly two days at 400ms slots.
+    pub const DEFAULT_CLOSE_RETENTION_SLOTS: u64 = 432_000;
+    pub const DEFAULT_DEPENDENCY_DISPUTE_POLICY: DependencyDisputePolicy =
+        DependencyDisputePolicy {
+            block_while_raised: true,
+            min_worker_pct: 1,
+        };
+    /// Reserved id for graphs migrated from v0, whose PDA was seeded without a graph id.
+    pub const LEGACY_GRAPH_ID: u64 = u64::MAX;
+
+    /// The graph-id component of this graph's PDA seeds.
+    pub fn id_seed(&self) -> Vec<u8> {
+        if self.graph_id == Self::LEGACY_GRAPH_ID {
+            Vec::new()
+        } else {
+            self.graph_id.to_le_bytes().to_vec()
+        }
+    }
+}
+
+/// Program-wide fee settings. A single PDA, created by the program's upgrade authority.
+#[account]
+#[derive(InitSpace)]
+pub struct ProtocolConfig {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    /// Protocol fee on worker payouts; never above `MAX_FEE_BPS`.
+    pub fee_bps: u16,
+    pub bump: u8,
+    pub version: u8,
+}
+
+impl ProtocolConfig {
+    pub const SEED_PREFIX: &'static [u8] = b"protocol_config";
+    /// Hard cap for both the protocol fee and any graph fee.
+    pub const MAX_FEE_BPS: u16 = 1_000;
+}
+
+/// Who may create tasks in a graph.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum CreationPolicy {
+    /// Every `create_task` must be co-signed by the graph authority.
+    AuthorityOnly,
+    /// The authority, or any creator holding a `CreatorAllowlist` entry for this graph.
+    Allowlist,
+    /// Anyone, provided they lock `graph.creation_deposit_lamports` in the new task account.
+    Open,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorAllowlist {
+    pub graph: Pubkey,
+    pub creator: Pubkey,
+    pub added_at_slot: u64,
+    pub bump: u8,
+    pub version: u8,
+}
+
+impl CreatorAllowlist {
+    pub const SEED_PREFIX: &'static [u8] = b"creator_allowlist";
+}
+
+/// When a completed prerequisite that went through a dispute still satisfies its dependents.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct DependencyDisputePolicy {
+    /// A prerequisite with a `Raised` dispute does not satisfy dependents until it is resolved.
+    pub block_while_raised: bool,
+    /// A resolved prerequisite satisfies dependents only if the worker was awarded at least this
+    /// percentage. 0 accepts any resolution.
+    pub min_worker_pct: u8,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
 pub enum TaskStatus {
     Open,
     Completed,
+    Cancelled,
+    /// Created with `require_funding` and not yet fully funded; receipts are not accepted.
+    Draft,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
@@ -42,16 +155,450 @@ pub struct Task {
     pub resolved_by: Option<Pubkey>,
     pub dispute_resolved_at_slot: u64,
     pub worker_award_lamports: u64,
+    pub creation_deposit_lamports: u64,
+    pub config_version: u32,
+    /// Slot at which escrow was paid out, refunded or split by dispute; 0 while unsettled.
+    pub settled_at_slot: u64,
+    /// Reservation length for `claim_task`; 0 means the task is open to any submitter.
+    pub claim_duration_slots: u64,
+    /// Worker holding the reservation (via `claim_task`) or assigned by the creator.
+    pub assignee: Option<Pubkey>,
+    /// Slot at which the reservation lapses; 0 for creator assignments, which do not lapse.
+    pub claim_expires_at_slot: u64,
+    /// End of the submission window for competition tasks; 0 for single-submission tasks.
+    pub competition_deadline_slot: u64,
+    /// Receipts revealed into a competition, or submitted to a task awaiting attestation.
+    pub submission_count: u32,
+    /// Escrow balance above rent, snapshotted at winner selection; winners draw shares of it.
+    pub payout_pool_lamports: u64,
+    /// Basis points of the pool not yet claimed by winners.
+    pub unclaimed_payout_bps: u16,
+    /// Number of contributors on the completing receipt; 0 for a solo submission.
+    pub team_size: u8,
+    /// Ordered reward tranches; empty for a task paid in one `claim_reward`.
+    pub milestones: Vec<Milestone>,
+    /// If set, the reward unlocks gradually after completion via `withdraw_vested`.
+    pub vesting: Option<VestingSchedule>,
+    /// Slot the vesting schedule started (task completion).
+    pub vesting_start_slot: u64,
+    pub vested_withdrawn_lamports: u64,
+    /// If set, anyone may top up the reward with `increase_reward`; otherwise only the creator.
+    pub crowdfunded: bool,
+    /// If set, the task starts as `Draft` and opens once `funded_lamports` covers the reward.
+    pub require_funding: bool,
+    /// Total deposited through `fund_task` and `increase_reward`.
+    pub funded_lamports: u64,
+    /// k-of-n alternatives over disjoint subsets of `dependencies`; ungrouped dependencies are
+    /// all required.
+    pub dependency_groups: Vec<DependencyGroup>,
+    /// Edges to tasks tracked in other graphs; always required, never part of a group.
+    pub external_dependencies: Vec<ExternalDependency>,
+    /// Worker's percentage in the dispute resolution; only meaningful once `Resolved`.
+    pub dispute_worker_pct: u8,
+    /// Epics take no submissions; `roll_up` completes them once all their children are.
+    pub is_epic: bool,
+    /// The epic this task belongs to, if any.
+    pub parent_task_id: Option<u64>,
+    /// Children registered under this epic by `create_task`.
+    pub child_count: u16,
+    /// Where the specification lives, with `spec_hash` committing to its content.
+    pub spec_uri: String,
+    pub spec_hash: [u8; 32],
+    /// Graph-defined category code for indexers; 0 if uncategorized.
+    pub category: u16,
+    /// Graph-defined tag codes, unused slots zero.
+    pub tags: [u16; Task::MAX_TAGS],
+    /// Bumped by each `amend_task_spec`; 0 is the spec the task was created with.
+    pub spec_revision: u32,
+    /// Non-zero makes the task repeatable: each completion approved with `approve_completion`
+    /// pays `reward_lamports / max_completions`, and the task completes with the last one.
+    pub max_completions: u16,
+    pub completion_count: u16,
+    /// Non-zero requires this many slots between completions of a repeatable task.
+    pub recurrence_interval_slots: u64,
+    /// Non-zero stops accepting completions at this slot.
+    pub recurrence_end_slot: u64,
+    pub last_completed_slot: u64,
+    /// Agent whose `submit_completion` awaits `approve_completion` or `reject_completion`.
+    pub pending_completion_agent: Option<Pubkey>,
+    /// Slot of the latest `commit_receipt`; 0 if none.
+    pub last_commit_slot: u64,
+    /// Oracle keys that can attest receipts; empty if the first receipt completes the task.
+    pub verifiers: Vec<Pubkey>,
+    /// Distinct verifier attestations `attest_receipt` needs; 0 iff `verifiers` is empty.
+    pub verifier_threshold: u8,
+    /// Lamports deposited by funders other than the creator, each recorded in a `Deposit`.
+    pub deposited_lamports: u64,
+    /// `Deposit` accounts still open for this task; `close_task` waits for them.
+    pub depositor_count: u16,
+    /// Share of the escrow `cancel_task` set aside for depositors, refunded pro rata to
+    /// `deposited_lamports` by `refund_deposit`.
+    pub refund_pool_lamports: u64,
     pub bump: u8,
+    pub version: u8,
 }
 
 impl Task {
+    /// The worker whose countersignature an `amend_task_spec` needs: an assignee holding an
+    /// active reservation (including a non-lapsing `assign_task`), or else the worker on the latest
+    /// milestone taken up.
+    pub fn engaged_worker(&self, now: u64) -> Option<Pubkey> {
+        self.assignee
+            .filter(|_| self.reservation_active(now))
+            .or_else(|| self.milestones.iter().rev().find_map(|m| m.worker))
+    }
+
     pub const SEED_PREFIX: &'static [u8] = b"task";
+    pub const MAX_WINNERS: usize = 10;
+    pub const MAX_MILESTONES: usize = 10;
+    pub const MAX_DEPENDENCY_GROUPS: usize = 4;
+    pub const MAX_EXTERNAL_DEPENDENCIES: usize = 4;
+    pub const MAX_EPIC_CHILDREN: u16 = 24;
+    /// Per `create_tasks_batch`, sized so a batch still fits in a 1232-byte transaction. A
+    /// minimal `CreateTaskParams` serializes to about 118 bytes, and each task adds its PDA to the
+    /// remaining accounts: about 151 bytes per task on top of about 290 bytes of fixed overhead
+    /// (signatures, graph, creator and program accounts, compute-budget instruction), so
+    /// 290 + 5 * 151 = 1045 bytes leaves room for a few dependencies or a spec URI.
+    pub const MAX_BATCH_TASKS: usize = 5;
+    pub const MAX_SPEC_URI_LEN: usize = 200;
+    pub const MAX_TAGS: usize = 4;
+    pub const MAX_VERIFIERS: usize = 5;
+
+    pub fn space_for(
+        dependencies: &[u64],
+        dependency_groups: &[DependencyGroup],
+        external_dependency_count: usize,
+        milestone_count: usize,
+        verifier_count: usize,
+    ) -> usize {
+        // graph + task_id + creator + reward + status + dispute_status + created_at_slot
+        let core = 32 + 8 + 32 + 8 + 1 + 1 + 8;
+        // completed_by + disputed_by + dispute_raised_at_slot + resolved_by
+        // + dispute_resolved_at_slot + worker_award + dispute_worker_pct
+        let outcome = (1 + 32) + (1 + 32) + 8 + (1 + 32) + 8 + 8 + 1;
+        // creation_deposit + config_version + settled_at_slot + require_funding + funded_lamports
+        let accounting = 8 + 4 + 8 + 1 + 8;
+        // claim_duration_slots + assignee + claim_expires_at_slot
+        let assignment = 8 + (1 + 32) + 8;
+        // competition_deadline_slot + submission_count + payout_pool + unclaimed_payout_bps
+        let competition = 8 + 4 + 8 + 2;
+        // team_size + crowdfunded
+        let team = 1 + 1;
+        // is_epic + parent_task_id + child_count
+        let epic = 1 + (1 + 8) + 2;
+        // spec_uri (allocated at its maximum, like `Receipt::uri`) + spec_hash + category + tags
+        // + spec_revision
+        let spec = (4 + Self::MAX_SPEC_URI_LEN) + 32 + 2 + 2 * Self::MAX_TAGS + 4;
+        // max_completions + completion_count + recurrence_interval_slots + recurrence_end_slot
+        // + last_completed_slot + pending_completion_agent
+        let recurrence = 2 + 2 + 8 + 8 + 8 + (1 + 32);
+        // last_commit_slot
+        let commit = 8;
+        // verifier_threshold
+        let attestation = 1;
+        // deposited_lamports + depositor_count + refund_pool_lamports
+        let deposits = 8 + 2 + 8;
+        // vesting + vesting_start_slot + vested_withdrawn
+        let vesting = (1 + VestingSchedule::INIT_SPACE) + 8 + 8;
+        // bump + version
+        let trailer = 1 + 1;
+        // dependency_groups: threshold + member vec per group
+        let groups = 4 + dependency_groups
+            .iter()
+            .map(DependencyGroup::space)
+            .sum::<usize>();
+        // dependencies + external_dependencies + milestones + dependency_groups
+        let vecs = (4 + dependencies.len() * 8)
+            + (4 + external_dependency_count * ExternalDependency::INIT_SPACE)
+            + (4 + milestone_count * Milestone::INIT_SPACE)
+            + (4 + verifier_count * 32)
+            + groups;
+        core + outcome
+            + accounting
+            + assignment
+            + competition
+            + team
+            + epic
+            + spec
+            + recurrence
+            + commit
+            + attestation
+            + deposits
+            + vesting
+            + trailer
+            + vecs
+    }
+
+    /// Percentage of the reward the worker kept: the resolved split, or 100 if never disputed.
+    pub fn worker_pct(&self) -> u8 {
+        if self.dispute_status == DisputeStatus::Resolved {
+            self.dispute_worker_pct
+        } else {
+            100
+        }
+    }
+
+    pub fn is_fully_funded(&self) -> bool {
+        self.funded_lamports >= self.reward_lamports
+    }
+
+    pub fn is_competition(&self) -> bool {
+        self.competition_deadline_slot > 0
+    }
+
+    pub fn is_repeatable(&self) -> bool {
+        self.max_completions > 0
+    }
+
+    /// Earliest slot at which a repeatable task accepts its next completion.
+    pub fn next_completion_slot(&self) -> u64 {
+        if self.completion_count == 0 {
+            0
+        } else {
+            self.last_completed_slot
+                .saturating_add(self.recurrence_interval_slots)
+        }
+    }
+
+    /// Receipts complete the task only once `attest_receipt` verifies enough oracle signatures.
+    pub fn requires_attestation(&self) -> bool {
+        self.verifier_threshold > 0
+    }
+
+    /// Slot after which every commitment made so far can no longer be revealed.
+    pub fn reveal_deadline_slot(&self) -> u64 {
+        if self.last_commit_slot == 0 {
+            0
+        } else {
+            self.last_commit_slot
+                .saturating_add(ReceiptCommitment::REVEAL_WINDOW_SLOTS)
+        }
+    }
+
+    /// Work has been handed in that the task has not settled yet: competition entries, receipts
+    /// awaiting attestation, commitments still inside their reveal window, or a submitted
+    /// milestone.
+    pub fn has_pending_work(&self, now: u64) -> bool {
+        self.submission_count > 0
+            || now <= self.reveal_deadline_slot()
+            || self
+                .milestones
+                .iter()
+                .any(|m| m.status == MilestoneStatus::Submitted)
+    }
+
+    /// Index of the first milestone not yet paid, if any remain.
+    pub fn next_milestone(&self) -> Option<usize> {
+        self.milestones
+            .iter()
+            .position(|m| m.status != MilestoneStatus::Paid)
+    }
+
+    /// Lamports the worker may withdraw now from an escrow holding `escrow_lamports`.
+    ///
+    /// Only the balance above `rent` vests linearly, so partial withdrawals keep the escrow alive;
+    /// everything, rent included, is due once the schedule has run its full duration.
+    pub fn vested_due(&self, now: u64, escrow_lamports: u64, rent: u64) -> u64 {
+        let schedule = match self.vesting {
+            Some(schedule) => schedule,
+            None => return escrow_lamports,
+        };
+        let elapsed = now.saturating_sub(self.vesting_start_slot);
+        if elapsed >= schedule.duration_slots {
+            return escrow_lamports;
+        }
+        if elapsed < schedule.cliff_slots {
+            return 0;
+        }
+        let total = self
+            .vested_withdrawn_lamports
+            .saturating_add(escrow_lamports.saturating_sub(rent));
+        let vested =
+            ((total as u128) * (elapsed as u128) / (schedule.duration_slots as u128)) as u64;
+        vested.saturating_sub(self.vested_withdrawn_lamports)
+    }
+
+    /// Claim-mode tasks and active reservations only accept work from the assignee.
+    pub fn may_submit(&self, agent: &Pubkey, now: u64) -> bool {
+        !(self.requires_claim() || self.reservation_active(now)) || self.assignee == Some(*agent)
+    }
+
+    /// Whether this epic can take another child from `creator`; the graph authority may attach
+    /// children to any epic.
+    pub fn accepts_child(&self, creator: &Pubkey, authority_signed: bool) -> bool {
+        self.is_epic
+            && self.status == TaskStatus::Open
+            && self.child_count < Self::MAX_EPIC_CHILDREN
+            && (authority_signed || self.creator == *creator)
+    }
+
+    pub fn requires_claim(&self) -> bool {
+        self.claim_duration_slots > 0
+    }
+
+    /// True while `assignee` holds an exclusive right to submit.
+    pub fn reservation_active(&self, now: u64) -> bool {
+        self.assignee.is_some()
+            && (self.claim_expires_at_slot == 0 || now < self.claim_expires_at_slot)
+    }
+
+    /// Account for `lamports` paid in by `funder`, in their `Deposit` unless they are the creator.
+    pub fn record_funder(
+        &mut self,
+        task: Pubkey,
+        deposit: Option<&mut Account<Deposit>>,
+        funder: Pubkey,
+        lamports: u64,
+        bump: u8,
+    ) -> Result<()> {
+        let deposit = match (funder == self.creator, deposit) {
+            (true, None) => return Ok(()),
+            (false, Some(deposit)) => {
+                if deposit.task == Pubkey::default() {
+                    deposit.task = task;
+                    deposit.funder = funder;
+                    deposit.bump = bump;
+                    deposit.version = ACCOUNT_VERSION;
+                    self.depositor_count = self
+                        .depositor_count
+                        .checked_add(1)
+                        .ok_or(BountyGraphError::ArithmeticOverflow)?;
+                }
+                deposit
+            }
+            _ => return err!(BountyGraphError::InvalidDeposit),
+        };
+        deposit.lamports = deposit
+            .lamports
+            .checked_add(lamports)
+            .ok_or(BountyGraphError::ArithmeticOverflow)?;
+        self.deposited_lamports = self
+            .deposited_lamports
+            .checked_add(lamports)
+            .ok_or(BountyGraphError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Paid, refunded, or resolved: nothing left in escrow and no open dispute.
+    pub fn is_terminal(&self) -> bool {
+        match self.status {
+            TaskStatus::Cancelled => true,
+            TaskStatus::Completed => {
+                self.settled_at_slot > 0 && self.dispute_status != DisputeStatus::Raised
+            }
+            TaskStatus::Open | TaskStatus::Draft => false,
+        }
+    }
+}
+
+/// Satisfied once at least `threshold` of `task_ids` are completed, e.g. "any 2 of these 3 audits".
+/// Every member is also listed in `Task::dependencies`, so cycle checks see group edges as
+/// ordinary edges.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct DependencyGroup {
+    pub threshold: u8,
+    pub task_ids: Vec<u64>,
+}
 
-    pub fn space_for(dependencies: &[u64]) -> usize {
-        let fixed = 32 + 8 + 32 + 8 + 1 + 1 + 8 + (1 + 32) + (1 + 32) + 8 + (1 + 32) + 8 + 8 + 1;
-        let vec = 4 + dependencies.len() * 8;
-        fixed + vec
+impl DependencyGroup {
+    pub fn space(&self) -> usize {
+        1 + 4 + self.task_ids.len() * 8
+    }
+}
+
+/// Edge to a task in another graph, identified by `(graph, task_id)`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct ExternalDependency {
+    pub graph: Pubkey,
+    pub task_id: u64,
+    /// False until the foreign graph's authority approves, if that graph requires approval.
+    pub approved: bool,
+}
+
+/// Linear unlock of a completed task's reward, measured from completion.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct VestingSchedule {
+    /// Nothing can be withdrawn until this many slots after completion.
+    pub cliff_slots: u64,
+    /// Slots after completion at which the full reward has vested. Must be >= `cliff_slots`.
+    pub duration_slots: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum MilestoneStatus {
+    Pending,
+    Submitted,
+    Paid,
+}
+
+/// One tranche of a milestone task's reward, paid from the task's escrow on approval.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct Milestone {
+    pub amount_lamports: u64,
+    pub status: MilestoneStatus,
+    /// Agent whose milestone receipt is awaiting approval or was paid.
+    pub worker: Option<Pubkey>,
+}
+
+/// What remains of a `Task` after `close_task`. Occupies the same PDA, so the task id cannot be
+/// reused, and still satisfies dependents if the task was completed.
+#[account]
+#[derive(InitSpace)]
+pub struct TaskTombstone {
+    pub graph: Pubkey,
+    pub task_id: u64,
+    pub creator: Pubkey,
+    pub completed: bool,
+    /// `Task::worker_pct` at close, so dispute policies still apply to closed prerequisites.
+    pub worker_pct: u8,
+    /// Kept so a closed child still counts towards its epic's `roll_up`.
+    pub parent_task_id: Option<u64>,
+    pub completed_by: Option<Pubkey>,
+    pub closed_at_slot: u64,
+    pub bump: u8,
+    pub version: u8,
+}
+
+/// A reusable DAG of task specs, created as tasks in any graph by `instantiate_template`.
+#[account]
+pub struct GraphTemplate {
+    pub authority: Pubkey,
+    pub template_id: u64,
+    pub tasks: Vec<TemplateTask>,
+    pub bump: u8,
+    pub version: u8,
+}
+
+impl GraphTemplate {
+    pub const SEED_PREFIX: &'static [u8] = b"graph_template";
+    /// Instantiated in one batch, so bounded like `create_tasks_batch`.
+    pub const MAX_TASKS: usize = Task::MAX_BATCH_TASKS;
+
+    pub fn space_for(tasks: &[TemplateTask]) -> usize {
+        32 + 8 + 4 + tasks.iter().map(TemplateTask::space).sum::<usize>() + 1 + 1
+    }
+}
+
+/// One task in a `GraphTemplate`. Instantiated with task id `task_id_offset + index`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct TemplateTask {
+    pub reward_lamports: u64,
+    /// Indices of earlier tasks in the template, strictly increasing.
+    pub dependencies: Vec<u8>,
+    pub claim_duration_slots: u64,
+    pub require_funding: bool,
+    /// Spec metadata copied to every instantiated task, bounded as in `CreateTaskParams`.
+    pub spec_uri: String,
+    pub spec_hash: [u8; 32],
+    pub category: u16,
+    pub tags: Vec<u16>,
+}
+
+impl TemplateTask {
+    pub fn space(&self) -> usize {
+        // spec_uri + spec_hash + category + tags
+        let spec = (4 + self.spec_uri.len()) + 32 + 2 + (4 + 2 * self.tags.len());
+        8 + (4 + self.dependencies.len()) + 8 + 1 + spec
     }
 }
 
@@ -60,12 +607,38 @@ impl Task {
 pub struct Escrow {
     pub task: Pubkey,
     pub bump: u8,
+    pub version: u8,
 }
 
 impl Escrow {
     pub const SEED_PREFIX: &'static [u8] = b"escrow";
 }
 
+/// What one funder other than the creator has put into a task's escrow, so `cancel_task` can
+/// give it back rather than hand it to the creator.
+#[account]
+#[derive(InitSpace)]
+pub struct Deposit {
+    pub task: Pubkey,
+    pub funder: Pubkey,
+    pub lamports: u64,
+    pub bump: u8,
+    pub version: u8,
+}
+
+impl Deposit {
+    pub const SEED_PREFIX: &'static [u8] = b"deposit";
+
+    /// This deposit's pro rata share of the refund pool of a cancelled `task`.
+    pub fn refund(&self, task: &Task) -> u64 {
+        if task.deposited_lamports == 0 {
+            return 0;
+        }
+        ((task.refund_pool_lamports as u128) * (self.lamports as u128)
+            / (task.deposited_lamports as u128)) as u64
+    }
+}
+
 #[account]
 pub struct Receipt {
     pub task: Pubkey,
@@ -73,14 +646,91 @@ pub struct Receipt {
     pub work_hash: [u8; 32],
     pub uri: String,
     pub submitted_at_slot: u64,
+    /// Winner's share of the competition pool in basis points; 0 for non-winning receipts.
+    pub payout_bps: u16,
+    pub claimed: bool,
+    /// Team members sharing the reward; empty when the submitting agent worked alone.
+    pub contributors: Vec<Contributor>,
+    /// Milestone this receipt delivers; `None` for a whole-task receipt.
+    pub milestone_index: Option<u8>,
+    /// Which completion of a repeatable task this receipt records.
+    pub completion_index: Option<u16>,
     pub bump: u8,
+    pub version: u8,
 }
 
 impl Receipt {
     pub const SEED_PREFIX: &'static [u8] = b"receipt";
     pub const MAX_URI_LEN: usize = 200;
+    pub const MAX_CONTRIBUTORS: usize = 8;
+
+    pub const INIT_SPACE: usize = 32
+        + 32
+        + 32
+        + 4
+        + Self::MAX_URI_LEN
+        + 8
+        + 2
+        + 1
+        + (4 + Self::MAX_CONTRIBUTORS * Contributor::INIT_SPACE)
+        + (1 + 1)
+        + (1 + 2)
+        + 1
+        + 1;
+
+    /// True once every listed contributor has co-signed or accepted their share.
+    pub fn team_accepted(&self) -> bool {
+        self.contributors.iter().all(|c| c.accepted)
+    }
+
+    /// The seed after `[SEED_PREFIX, task, agent]` this receipt was created with: the milestone
+    /// index byte, the little-endian completion index, or nothing for a whole-task receipt.
+    pub fn index_seed(&self) -> Vec<u8> {
+        match (self.milestone_index, self.completion_index) {
+            (Some(index), _) => vec![index],
+            (None, Some(index)) => index.to_le_bytes().to_vec(),
+            (None, None) => Vec::new(),
+        }
+    }
+}
+
+/// First phase of a commit-reveal submission: `commitment` is
+/// `sha256(work_hash || uri || salt || agent)`, opened by `reveal_receipt`.
+#[account]
+#[derive(InitSpace)]
+pub struct ReceiptCommitment {
+    pub task: Pubkey,
+    pub agent: Pubkey,
+    pub commitment: [u8; 32],
+    pub committed_at_slot: u64,
+    pub bump: u8,
+    pub version: u8,
+}
+
+impl ReceiptCommitment {
+    pub const SEED_PREFIX: &'static [u8] = b"receipt_commitment";
+    /// Slots after the commit during which it can be revealed (~1 minute).
+    pub const REVEAL_WINDOW_SLOTS: u64 = 150;
+
+    /// Last slot at which this commitment can still be revealed.
+    pub fn reveal_deadline_slot(&self) -> u64 {
+        self.committed_at_slot
+            .saturating_add(Self::REVEAL_WINDOW_SLOTS)
+    }
 
-    pub const INIT_SPACE: usize = 32 + 32 + 32 + 4 + Self::MAX_URI_LEN + 8 + 1;
+    pub fn compute(work_hash: &[u8; 32], uri: &str, salt: &[u8; 32], agent: &Pubkey) -> [u8; 32] {
+        anchor_lang::solana_program::hash::hashv(&[work_hash, uri.as_bytes(), salt, agent.as_ref()])
+            .to_bytes()
+    }
+}
+
+/// One team member's share of a receipt's payout.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct Contributor {
+    pub worker: Pubkey,
+    pub share_bps: u16,
+    /// Set when the contributor co-signed the submission or later called `accept_contribution`.
+    pub accepted: bool,
 }
 
 #[account]
@@ -96,7 +746,10 @@ pub struct Dispute {
     pub arbiter: Option<Pubkey>,
     pub creator_pct: Option<u8>,
     pub worker_pct: Option<u8>,
+    /// The task's `spec_revision` when the dispute was raised.
+    pub spec_revision: u32,
     pub bump: u8,
+    pub version: u8,
 }
 
 impl Dispute {
@@ -105,8 +758,8 @@ impl Dispute {
 
     pub fn space_for(reason: &str) -> usize {
         // discriminator + task + creator + worker + raised_by + reason + status + raised_at_slot
-        // + resolved_at_slot + arbiter + creator_pct + worker_pct + bump
-        let fixed = 32 + 32 + 32 + 32 + 1 + 8 + (1 + 32) + (1 + 8) + (1 + 1) + (1 + 1) + 1;
+        // + resolved_at_slot + arbiter + creator_pct + worker_pct + spec_revision + bump + version
+        let fixed = 32 + 32 + 32 + 32 + 1 + 8 + (1 + 32) + (1 + 8) + (1 + 1) + (1 + 1) + 4 + 1 + 1;
         let reason_size = 4 + reason.len();
         fixed + reason_size
     }
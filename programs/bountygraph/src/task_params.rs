@@ -0,0 +1,511 @@
+//! Task creation rules shared by `create_task`, `create_tasks_batch` and `instantiate_template`.
+//!
+//! `create_task` takes its dependency accounts in `params` order; the batch paths share
+//! `create_tasks`, which takes each dependency account once for the whole batch.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::solana_program::system_instruction;
+use anchor_lang::Discriminator;
+
+use crate::error::BountyGraphError;
+use crate::events::TaskCreated;
+use crate::state::*;
+use crate::task_view::*;
+use crate::CreateTaskParams;
+
+/// CREATION POLICY: The authority may always create tasks. Other creators are admitted
+/// according to the graph's policy; under `Open` they must lock a creation deposit, which is
+/// returned here.
+pub fn creation_deposit(
+    graph: &Graph,
+    graph_key: Pubkey,
+    authority_signed: bool,
+    creator: Pubkey,
+    allowlist: Option<&CreatorAllowlist>,
+) -> Result<u64> {
+    if authority_signed {
+        return Ok(0);
+    }
+    match graph.creation_policy {
+        CreationPolicy::AuthorityOnly => err!(BountyGraphError::UnauthorizedCreator),
+        CreationPolicy::Allowlist => {
+            let entry = allowlist.ok_or(BountyGraphError::UnauthorizedCreator)?;
+            require!(
+                entry.graph == graph_key && entry.creator == creator,
+                BountyGraphError::UnauthorizedCreator
+            );
+            Ok(0)
+        }
+        CreationPolicy::Open => Ok(graph.creation_deposit_lamports),
+    }
+}
+
+/// SPEC: Agents work against the hash; URI and tags are bounded by the fixed allocation. Also
+/// checked when a template is stored, so a bad template cannot be instantiated.
+pub fn validate_spec(spec_uri: &str, tags: &[u16]) -> Result<()> {
+    require!(
+        spec_uri.len() <= Task::MAX_SPEC_URI_LEN
+            && tags.len() <= Task::MAX_TAGS
+            && tags.iter().all(|tag| *tag != 0),
+        BountyGraphError::InvalidSpec
+    );
+    Ok(())
+}
+
+/// Validate `params` against the graph's config: reward bounds, dependency counts and ordering,
+/// and the task mode (claims, competition, milestones, vesting, epic).
+pub fn validate_task_params(graph: &Graph, params: &CreateTaskParams, now: u64) -> Result<()> {
+    let deps = &params.dependencies;
+
+    // An epic's reward is an optional completion bonus on top of its children's rewards.
+    require!(
+        params.reward_lamports >= graph.min_reward_lamports
+            || (params.epic && params.reward_lamports == 0),
+        BountyGraphError::InvalidReward
+    );
+    require!(
+        graph.max_reward_lamports == 0 || params.reward_lamports <= graph.max_reward_lamports,
+        BountyGraphError::InvalidReward
+    );
+    let external = &params.external_dependencies;
+    require!(
+        external.len() <= Task::MAX_EXTERNAL_DEPENDENCIES
+            && ((deps.len() + external.len()) as u16) <= graph.max_dependencies_per_task,
+        BountyGraphError::TooManyDependencies
+    );
+
+    // Competitions take many submissions, so exclusive claims make no sense for them.
+    if params.competition_deadline_slot > 0 {
+        require!(
+            params.competition_deadline_slot > now,
+            BountyGraphError::InvalidConfig
+        );
+        require!(
+            params.claim_duration_slots == 0,
+            BountyGraphError::InvalidConfig
+        );
+    }
+
+    // Milestones split the reward into ordered tranches; competitions pay winners instead.
+    if !params.milestones.is_empty() {
+        require!(
+            params.milestones.len() <= Task::MAX_MILESTONES
+                && params.milestones.iter().all(|amount| *amount > 0),
+            BountyGraphError::InvalidMilestones
+        );
+        let total = params
+            .milestones
+            .iter()
+            .try_fold(0u64, |acc, amount| acc.checked_add(*amount))
+            .ok_or(BountyGraphError::ArithmeticOverflow)?;
+        require!(
+            total == params.reward_lamports,
+            BountyGraphError::InvalidMilestones
+        );
+        require!(
+            params.competition_deadline_slot == 0,
+            BountyGraphError::InvalidConfig
+        );
+    }
+
+    // Vesting applies to a single worker's reward, so it excludes competitions and milestones.
+    if let Some(schedule) = params.vesting {
+        require!(
+            schedule.duration_slots > 0 && schedule.cliff_slots <= schedule.duration_slots,
+            BountyGraphError::InvalidConfig
+        );
+        require!(
+            params.competition_deadline_slot == 0 && params.milestones.is_empty(),
+            BountyGraphError::InvalidConfig
+        );
+    }
+
+    // EPICS: Completed by `roll_up` from their children, so no mode that pays a submitter. Nor
+    // dependencies of their own, which `roll_up` does not check; put them on the children.
+    if params.epic {
+        require!(
+            params.claim_duration_slots == 0
+                && params.competition_deadline_slot == 0
+                && params.milestones.is_empty()
+                && params.vesting.is_none()
+                && params.dependencies.is_empty()
+                && params.external_dependencies.is_empty(),
+            BountyGraphError::InvalidConfig
+        );
+    }
+
+    // REPEATABLE: Each completion is paid when the creator approves it, so no claims, winners,
+    // tranches, vesting or children. Recurrence needs a completion count to size each slice.
+    if params.max_completions > 0 {
+        require!(
+            params.reward_lamports >= params.max_completions as u64
+                && params.claim_duration_slots == 0
+                && params.competition_deadline_slot == 0
+                && params.milestones.is_empty()
+                && params.vesting.is_none()
+                && !params.epic,
+            BountyGraphError::InvalidConfig
+        );
+    }
+    require!(
+        params.recurrence_interval_slots == 0 || params.max_completions > 0,
+        BountyGraphError::InvalidConfig
+    );
+    require!(
+        params.recurrence_end_slot == 0
+            || (params.recurrence_interval_slots > 0 && params.recurrence_end_slot > now),
+        BountyGraphError::InvalidConfig
+    );
+
+    // ATTESTATION: Distinct verifiers and a reachable threshold. Attested completion stands in for
+    // winner selection, tranche approval, roll-up and per-completion payouts, so those take none.
+    let verifiers = &params.verifiers;
+    require!(
+        verifiers.len() <= Task::MAX_VERIFIERS
+            && (params.verifier_threshold == 0) == verifiers.is_empty()
+            && params.verifier_threshold as usize <= verifiers.len()
+            && verifiers
+                .iter()
+                .enumerate()
+                .all(|(i, v)| !verifiers[..i].contains(v)),
+        BountyGraphError::InvalidVerifiers
+    );
+    require!(
+        verifiers.is_empty()
+            || (params.competition_deadline_slot == 0
+                && params.milestones.is_empty()
+                && !params.epic
+                && params.max_completions == 0),
+        BountyGraphError::InvalidVerifiers
+    );
+
+    validate_spec(&params.spec_uri, &params.tags)?;
+
+    // SECURITY: Validate dependency array is sorted and contains no self-references
+    // Sorting requirement ensures O(log n) binary search during dependency queries
+    let mut prev: Option<u64> = None;
+    for dep in deps.iter() {
+        require!(*dep != params.task_id, BountyGraphError::InvalidDependency);
+        if let Some(p) = prev {
+            require!(*dep > p, BountyGraphError::InvalidDependency);
+        }
+        prev = Some(*dep);
+    }
+
+    // DEPENDENCY GROUPS: k-of-n alternatives over disjoint subsets of the dependencies above.
+    // Members stay in `dependencies`, so the cycle checks cover them like any other edge.
+    require!(
+        params.dependency_groups.len() <= Task::MAX_DEPENDENCY_GROUPS,
+        BountyGraphError::InvalidDependencyGroups
+    );
+    let mut grouped: Vec<u64> = Vec::new();
+    for group in params.dependency_groups.iter() {
+        require!(
+            group.threshold > 0 && (group.threshold as usize) <= group.task_ids.len(),
+            BountyGraphError::InvalidDependencyGroups
+        );
+        for id in group.task_ids.iter() {
+            require!(
+                deps.binary_search(id).is_ok() && !grouped.contains(id),
+                BountyGraphError::InvalidDependencyGroups
+            );
+            grouped.push(*id);
+        }
+    }
+
+    Ok(())
+}
+
+pub fn emit_task_created(task: &Task, key: Pubkey) {
+    emit!(TaskCreated {
+        graph: task.graph,
+        task: key,
+        task_id: task.task_id,
+        creator: task.creator,
+        category: task.category,
+        tags: task.tags,
+        spec_hash: task.spec_hash,
+    });
+}
+
+/// A freshly created task for validated `params`.
+#[allow(clippy::too_many_arguments)]
+pub fn new_task(
+    params: &CreateTaskParams,
+    graph_key: Pubkey,
+    graph: &Graph,
+    creator: Pubkey,
+    external_dependencies: Vec<ExternalDependency>,
+    creation_deposit: u64,
+    now: u64,
+    bump: u8,
+) -> Task {
+    Task {
+        graph: graph_key,
+        task_id: params.task_id,
+        creator,
+        reward_lamports: params.reward_lamports,
+        status: if params.require_funding {
+            TaskStatus::Draft
+        } else {
+            TaskStatus::Open
+        },
+        dispute_status: DisputeStatus::None,
+        dependencies: params.dependencies.clone(),
+        created_at_slot: now,
+        completed_by: None,
+        disputed_by: None,
+        dispute_raised_at_slot: 0,
+        resolved_by: None,
+        dispute_resolved_at_slot: 0,
+        worker_award_lamports: 0,
+        creation_deposit_lamports: creation_deposit,
+        config_version: graph.config_version,
+        settled_at_slot: 0,
+        claim_duration_slots: params.claim_duration_slots,
+        assignee: None,
+        claim_expires_at_slot: 0,
+        competition_deadline_slot: params.competition_deadline_slot,
+        submission_count: 0,
+        payout_pool_lamports: 0,
+        unclaimed_payout_bps: 0,
+        team_size: 0,
+        milestones: params
+            .milestones
+            .iter()
+            .map(|amount| Milestone {
+                amount_lamports: *amount,
+                status: MilestoneStatus::Pending,
+                worker: None,
+            })
+            .collect(),
+        vesting: params.vesting,
+        vesting_start_slot: 0,
+        vested_withdrawn_lamports: 0,
+        crowdfunded: params.crowdfunded,
+        require_funding: params.require_funding,
+        funded_lamports: 0,
+        dependency_groups: params.dependency_groups.clone(),
+        external_dependencies,
+        dispute_worker_pct: 0,
+        is_epic: params.epic,
+        parent_task_id: params.parent_task_id,
+        child_count: 0,
+        spec_uri: params.spec_uri.clone(),
+        spec_hash: params.spec_hash,
+        category: params.category,
+        tags: {
+            let mut tags = [0; Task::MAX_TAGS];
+            tags[..params.tags.len()].copy_from_slice(&params.tags);
+            tags
+        },
+        spec_revision: 0,
+        max_completions: params.max_completions,
+        completion_count: 0,
+        recurrence_interval_slots: params.recurrence_interval_slots,
+        recurrence_end_slot: params.recurrence_end_slot,
+        last_completed_slot: 0,
+        pending_completion_agent: None,
+        last_commit_slot: 0,
+        verifiers: params.verifiers.clone(),
+        verifier_threshold: params.verifier_threshold,
+        deposited_lamports: 0,
+        depositor_count: 0,
+        refund_pool_lamports: 0,
+        bump,
+        version: ACCOUNT_VERSION,
+    }
+}
+
+/// Create `params` as new tasks in `graph`, for `create_tasks_batch` and `instantiate_template`.
+/// Same rules as `create_task`; a task may depend on earlier entries (topological order), and
+/// every account outside the batch is loaded and validated once, however many tasks reference it.
+///
+/// `remaining_accounts`: the new task PDAs in `params` order, then each distinct existing
+/// dependency: same-graph tasks, and for cross-graph edges the foreign graph and task, in any
+/// order. An epic named as `parent_task_id` must be earlier in the same batch.
+pub fn create_tasks<'a>(
+    graph: &mut Account<'a, Graph>,
+    authority_signed: bool,
+    creator: &AccountInfo<'a>,
+    allowlist: Option<&CreatorAllowlist>,
+    system_program: &AccountInfo<'a>,
+    remaining_accounts: &'a [AccountInfo<'a>],
+    params: &[CreateTaskParams],
+) -> Result<()> {
+    require!(
+        !params.is_empty() && params.len() <= Task::MAX_BATCH_TASKS,
+        BountyGraphError::InvalidBatch
+    );
+    require!(
+        remaining_accounts.len() >= params.len(),
+        BountyGraphError::MissingDependencyAccounts
+    );
+    let graph_key = graph.key();
+    let now = Clock::get()?.slot;
+
+    let creator_key = creator.key();
+    let creation_deposit =
+        creation_deposit(graph, graph_key, authority_signed, creator_key, allowlist)?;
+
+    let (task_accounts, pool_accounts) = remaining_accounts.split_at(params.len());
+    let mut pool_graphs: Vec<(Pubkey, bool)> = Vec::new();
+    let mut pool_tasks: Vec<TaskView> = Vec::with_capacity(pool_accounts.len());
+    for info in pool_accounts {
+        let is_graph = {
+            let data = info.try_borrow_data()?;
+            data.len() >= 8 && data[..8] == Graph::DISCRIMINATOR
+        };
+        if is_graph {
+            let foreign_graph = Account::<Graph>::try_from(info)?;
+            pool_graphs.push((info.key(), foreign_graph.require_dependent_approval));
+        } else {
+            pool_tasks.push(load_task_view(info)?);
+        }
+    }
+
+    let mut tasks: Vec<Task> = Vec::with_capacity(params.len());
+    for (i, p) in params.iter().enumerate() {
+        validate_task_params(graph, p, now)?;
+        require!(
+            tasks.iter().all(|t| t.task_id != p.task_id),
+            BountyGraphError::InvalidBatch
+        );
+
+        // Edges to earlier batch tasks cannot close a cycle, since those were created without
+        // knowing this id; edges forward in the batch are rejected outright.
+        for dep in p.dependencies.iter() {
+            if tasks.iter().any(|t| t.task_id == *dep) {
+                continue;
+            }
+            require!(
+                params[i + 1..].iter().all(|later| later.task_id != *dep),
+                BountyGraphError::InvalidBatch
+            );
+            let dep_task = pool_tasks
+                .iter()
+                .find(|v| v.graph == graph_key && v.task_id == *dep)
+                .ok_or(BountyGraphError::MissingDependencyAccounts)?;
+            require!(
+                !dep_task.dependencies.contains(&p.task_id),
+                BountyGraphError::CircularDependency
+            );
+        }
+
+        let mut external_dependencies: Vec<ExternalDependency> =
+            Vec::with_capacity(p.external_dependencies.len());
+        for edge in p.external_dependencies.iter() {
+            require!(
+                edge.graph != graph_key
+                    && external_dependencies
+                        .iter()
+                        .all(|d| d.graph != edge.graph || d.task_id != edge.task_id),
+                BountyGraphError::InvalidDependency
+            );
+            let (_, require_approval) = pool_graphs
+                .iter()
+                .find(|(key, _)| *key == edge.graph)
+                .ok_or(BountyGraphError::MissingDependencyAccounts)?;
+            let dep_task = pool_tasks
+                .iter()
+                .find(|v| v.graph == edge.graph && v.task_id == edge.task_id)
+                .ok_or(BountyGraphError::MissingDependencyAccounts)?;
+            require!(
+                !dep_task
+                    .external_dependencies
+                    .iter()
+                    .any(|d| d.graph == graph_key && d.task_id == p.task_id),
+                BountyGraphError::CircularDependency
+            );
+            external_dependencies.push(ExternalDependency {
+                graph: edge.graph,
+                task_id: edge.task_id,
+                approved: !require_approval,
+            });
+        }
+
+        if let Some(parent_id) = p.parent_task_id {
+            let parent = tasks
+                .iter_mut()
+                .find(|t| t.task_id == parent_id)
+                .ok_or(BountyGraphError::InvalidEpic)?;
+            require!(
+                parent.accepts_child(&creator_key, authority_signed),
+                BountyGraphError::InvalidEpic
+            );
+            parent.child_count += 1;
+        }
+
+        tasks.push(new_task(
+            p,
+            graph_key,
+            graph,
+            creator_key,
+            external_dependencies,
+            creation_deposit,
+            now,
+            0,
+        ));
+    }
+
+    // Tasks are written only once the whole batch has validated, as epics pick up children
+    // from later entries. Rent and any creation deposit are paid in the same transfer.
+    let rent = Rent::get()?;
+    for (task, info) in tasks.iter_mut().zip(task_accounts) {
+        let id_seed = task.task_id.to_le_bytes();
+        let (address, bump) = Pubkey::find_program_address(
+            &[Task::SEED_PREFIX, graph_key.as_ref(), &id_seed],
+            &crate::ID,
+        );
+        require_keys_eq!(info.key(), address, BountyGraphError::InvalidBatch);
+        task.bump = bump;
+
+        let space = 8 + Task::space_for(
+            &task.dependencies,
+            &task.dependency_groups,
+            task.external_dependencies.len(),
+            task.milestones.len(),
+            task.verifiers.len(),
+        );
+        let lamports = rent
+            .minimum_balance(space)
+            .checked_add(creation_deposit)
+            .ok_or(BountyGraphError::ArithmeticOverflow)?;
+        // Not `create_account`: it fails on an address that already holds lamports, so anyone
+        // could block a batch by sending dust to one of its task PDAs. Top the account up to
+        // what it needs instead, then allocate and assign it.
+        let shortfall = lamports.saturating_sub(info.lamports());
+        if shortfall > 0 {
+            invoke(
+                &system_instruction::transfer(&creator_key, &address, shortfall),
+                &[creator.clone(), info.clone(), system_program.clone()],
+            )?;
+        }
+        let signer_seeds: &[&[u8]] = &[Task::SEED_PREFIX, graph_key.as_ref(), &id_seed, &[bump]];
+        invoke_signed(
+            &system_instruction::allocate(&address, space as u64),
+            &[info.clone(), system_program.clone()],
+            &[signer_seeds],
+        )?;
+        invoke_signed(
+            &system_instruction::assign(&address, &crate::ID),
+            &[info.clone(), system_program.clone()],
+            &[signer_seeds],
+        )?;
+
+        {
+            let mut data = info.try_borrow_mut_data()?;
+            let mut writer: &mut [u8] = &mut data[..];
+            task.try_serialize(&mut writer)?;
+        }
+        emit_task_created(task, address);
+    }
+
+    graph.task_count = graph
+        .task_count
+        .checked_add(tasks.len() as u64)
+        .ok_or(BountyGraphError::ArithmeticOverflow)?;
+
+    Ok(())
+}
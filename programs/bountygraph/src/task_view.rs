@@ -0,0 +1,187 @@
+//! Read-only view over the accounts that can occupy a Task PDA.
+//!
+//! A task id's PDA holds either a live `Task` (current or v0 layout) or, once closed, a
+//! `TaskTombstone`. Dependency checks and close checks only need the common subset below.
+
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+use crate::error::BountyGraphError;
+use crate::migration::load_task;
+use crate::state::*;
+
+pub struct TaskView {
+    pub graph: Pubkey,
+    pub task_id: u64,
+    /// True if the task's work was accepted (live `Completed` task, or a completed tombstone).
+    pub completed: bool,
+    /// True while a dispute on the task is open.
+    pub dispute_raised: bool,
+    /// See `Task::worker_pct`.
+    pub worker_pct: u8,
+    /// Slot since which the task has been terminal, if it is.
+    pub terminal_since: Option<u64>,
+    /// Outgoing edges. Empty for tombstones: a closed task can never gain new dependents' back-edges.
+    pub dependencies: Vec<u64>,
+    /// Outgoing cross-graph edges. Empty for tombstones, as above.
+    pub external_dependencies: Vec<ExternalDependency>,
+    pub parent_task_id: Option<u64>,
+    pub completed_by: Option<Pubkey>,
+}
+
+impl From<Task> for TaskView {
+    fn from(task: Task) -> Self {
+        let terminal_since = task.is_terminal().then_some(task.settled_at_slot);
+        Self {
+            graph: task.graph,
+            task_id: task.task_id,
+            completed: task.status == TaskStatus::Completed,
+            dispute_raised: task.dispute_status == DisputeStatus::Raised,
+            worker_pct: task.worker_pct(),
+            terminal_since,
+            dependencies: task.dependencies,
+            external_dependencies: task.external_dependencies,
+            parent_task_id: task.parent_task_id,
+            completed_by: task.completed_by,
+        }
+    }
+}
+
+impl From<TaskTombstone> for TaskView {
+    fn from(tombstone: TaskTombstone) -> Self {
+        Self {
+            graph: tombstone.graph,
+            task_id: tombstone.task_id,
+            completed: tombstone.completed,
+            // Only terminal tasks are closed, so no dispute is open.
+            dispute_raised: false,
+            worker_pct: tombstone.worker_pct,
+            terminal_since: Some(tombstone.closed_at_slot),
+            dependencies: Vec::new(),
+            external_dependencies: Vec::new(),
+            parent_task_id: tombstone.parent_task_id,
+            completed_by: tombstone.completed_by,
+        }
+    }
+}
+
+impl TaskView {
+    /// Whether this task unlocks its dependents under `policy`.
+    pub fn satisfies(&self, policy: &DependencyDisputePolicy) -> bool {
+        self.completed
+            && !(policy.block_while_raised && self.dispute_raised)
+            && self.worker_pct >= policy.min_worker_pct
+    }
+}
+
+pub fn load_task_view(info: &AccountInfo) -> Result<TaskView> {
+    require_keys_eq!(
+        *info.owner,
+        crate::ID,
+        BountyGraphError::InvalidAccountLayout
+    );
+    let is_tombstone = {
+        let data = info.try_borrow_data()?;
+        data.len() >= 8 && data[..8] == TaskTombstone::DISCRIMINATOR
+    };
+    if is_tombstone {
+        let data = info.try_borrow_data()?;
+        let tombstone = TaskTombstone::try_deserialize(&mut &data[..])?;
+        return Ok(tombstone.into());
+    }
+    Ok(load_task(info)?.into())
+}
+
+/// Require that `accounts` are exactly the tasks listed in `dependencies`, in order, that every
+/// ungrouped dependency is satisfied under `policy`, and that each group has at least `threshold`
+/// satisfied.
+pub fn require_dependencies_satisfied(
+    graph: Pubkey,
+    policy: &DependencyDisputePolicy,
+    dependencies: &[u64],
+    dependency_groups: &[DependencyGroup],
+    accounts: &[AccountInfo],
+) -> Result<()> {
+    require!(
+        accounts.len() == dependencies.len(),
+        BountyGraphError::MissingDependencyAccounts
+    );
+
+    // TOPOLOGICAL CONSTRAINT: Verify dependencies are marked Completed before allowing this task to complete.
+    // This enforces the DAG execution rule: a task cannot be completed until its prerequisites are satisfied.
+    let mut satisfied = Vec::with_capacity(dependencies.len());
+    for (dep_task_info, expected_dep_id) in accounts.iter().zip(dependencies) {
+        let dep_task = load_task_view(dep_task_info)?;
+        // Verify dependency belongs to same graph
+        require!(dep_task.graph == graph, BountyGraphError::InvalidDependency);
+        // Verify task ID matches (prevents account substitution attacks)
+        require!(
+            dep_task.task_id == *expected_dep_id,
+            BountyGraphError::InvalidDependency
+        );
+        // CRITICAL: Ungrouped dependencies are AND-edges and must ALL be Completed (and clear of
+        // disputes per the graph's policy). This is the enforcement mechanism that prevents
+        // parallel execution of dependent tasks. A closed prerequisite is represented by its
+        // tombstone, which keeps its completion and dispute outcome.
+        let grouped = dependency_groups
+            .iter()
+            .any(|group| group.task_ids.contains(expected_dep_id));
+        if !grouped {
+            require_satisfies(&dep_task, policy)?;
+        }
+        satisfied.push(dep_task.satisfies(policy));
+    }
+
+    // Each group only needs `threshold` of its alternatives.
+    for group in dependency_groups {
+        let done = dependencies
+            .iter()
+            .zip(&satisfied)
+            .filter(|(dep_id, done)| **done && group.task_ids.contains(dep_id))
+            .count();
+        require!(
+            done >= group.threshold as usize,
+            BountyGraphError::DependencyNotCompleted
+        );
+    }
+
+    Ok(())
+}
+
+/// Require that `accounts` are exactly the foreign tasks in `external_dependencies`, in order,
+/// and that every edge is approved and satisfied under the dependent graph's `policy`.
+pub fn require_external_dependencies_satisfied(
+    policy: &DependencyDisputePolicy,
+    external_dependencies: &[ExternalDependency],
+    accounts: &[AccountInfo],
+) -> Result<()> {
+    require!(
+        accounts.len() == external_dependencies.len(),
+        BountyGraphError::MissingDependencyAccounts
+    );
+
+    for (dep_task_info, edge) in accounts.iter().zip(external_dependencies) {
+        require!(
+            edge.approved,
+            BountyGraphError::ExternalDependencyNotApproved
+        );
+        let dep_task = load_task_view(dep_task_info)?;
+        // The foreign task must be the one recorded on the edge, in the graph recorded on it.
+        require!(
+            dep_task.graph == edge.graph && dep_task.task_id == edge.task_id,
+            BountyGraphError::InvalidDependency
+        );
+        require_satisfies(&dep_task, policy)?;
+    }
+
+    Ok(())
+}
+
+fn require_satisfies(dep_task: &TaskView, policy: &DependencyDisputePolicy) -> Result<()> {
+    require!(dep_task.completed, BountyGraphError::DependencyNotCompleted);
+    require!(
+        dep_task.satisfies(policy),
+        BountyGraphError::DependencyDisputed
+    );
+    Ok(())
+}
@@ -0,0 +1,165 @@
+#!/usr/bin/env python3
+"""Write the v0 (pre-versioning) account fixtures loaded by Anchor.toml for tests/migration.test.ts.
+
+Each account is encoded with the frozen layouts in programs/bountygraph/src/migration.rs, behind the
+current Anchor discriminator, and owned by the program. Re-run after changing a fixture:
+
+    python3 scripts/gen-v0-fixtures.py
+"""
+
+import base64
+import hashlib
+import json
+import os
+import struct
+
+PROGRAM_ID = "Ghm5zPnHy5yJwQ6P22NYgNVrqPokDqAV3otdut3DSbSS"
+OUT_DIR = os.path.join(os.path.dirname(__file__), "..", "tests", "fixtures")
+ALPHABET = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz"
+
+
+def b58encode(raw: bytes) -> str:
+    n = int.from_bytes(raw, "big")
+    out = ""
+    while n:
+        n, r = divmod(n, 58)
+        out = ALPHABET[r] + out
+    return "1" * (len(raw) - len(raw.lstrip(b"\0"))) + out
+
+
+def b58decode(text: str) -> bytes:
+    n = 0
+    for ch in text:
+        n = n * 58 + ALPHABET.index(ch)
+    raw = n.to_bytes((n.bit_length() + 7) // 8, "big")
+    return b"\0" * (len(text) - len(text.lstrip("1"))) + raw
+
+
+def key(label: str) -> bytes:
+    """Deterministic stand-in address; migrations check owner and discriminator, not seeds."""
+    return hashlib.sha256(f"bountygraph-v0-{label}".encode()).digest()
+
+
+# ed25519 field prime and curve constant, for the off-curve check in find_program_address.
+P = 2**255 - 19
+D = -121665 * pow(121666, P - 2, P) % P
+
+
+def on_curve(point: bytes) -> bool:
+    y = int.from_bytes(point, "little") & ((1 << 255) - 1)
+    if y >= P:
+        return False
+    x2 = (y * y - 1) * pow(D * y * y + 1, P - 2, P) % P
+    return x2 == 0 or pow(x2, (P - 1) // 2, P) == 1
+
+
+def find_program_address(seeds: list) -> tuple:
+    program = b58decode(PROGRAM_ID)
+    for bump in range(255, -1, -1):
+        digest = hashlib.sha256(
+            b"".join(seeds) + bytes([bump]) + program + b"ProgramDerivedAddress"
+        ).digest()
+        if not on_curve(digest):
+            return digest, bump
+    raise ValueError("no viable bump")
+
+
+def discriminator(name: str) -> bytes:
+    return hashlib.sha256(f"account:{name}".encode()).digest()[:8]
+
+
+u8 = lambda v: struct.pack("<B", v)
+u16 = lambda v: struct.pack("<H", v)
+u32 = lambda v: struct.pack("<I", v)
+u64 = lambda v: struct.pack("<Q", v)
+string = lambda s: u32(len(s.encode())) + s.encode()
+none = b"\0"
+some = lambda raw: b"\1" + raw
+
+GRAPH, TASK, RECEIPT, DISPUTE = (key(n) for n in ("graph", "task", "receipt", "dispute"))
+# The escrow is the task's real PDA: migrate_task reads the funding from it.
+ESCROW, ESCROW_BUMP = find_program_address([b"escrow", TASK])
+# What the v0 task's escrow holds above rent: its full reward.
+ESCROWED_LAMPORTS = 1_000_000
+AUTHORITY, CREATOR, WORKER = key("authority"), key("creator"), key("worker")
+
+ACCOUNTS = {
+    # authority, max_dependencies_per_task, task_count, bump
+    "graph": ("Graph", GRAPH, AUTHORITY + u16(8) + u64(1) + u8(254)),
+    # graph, task_id, creator, reward, status (Completed), dispute_status (Raised), dependencies,
+    # created_at_slot, completed_by, disputed_by, dispute_raised_at_slot, resolved_by,
+    # dispute_resolved_at_slot, worker_award_lamports, bump
+    "task": (
+        "Task",
+        TASK,
+        GRAPH
+        + u64(7)
+        + CREATOR
+        + u64(1_000_000)
+        + u8(1)
+        + u8(1)
+        + u32(2) + u64(3) + u64(5)
+        + u64(100)
+        + some(WORKER)
+        + some(CREATOR)
+        + u64(120)
+        + none
+        + u64(0)
+        + u64(0)
+        + u8(253),
+    ),
+    # task, bump
+    "escrow": ("Escrow", ESCROW, TASK + u8(ESCROW_BUMP)),
+    # task, agent, work_hash, uri, submitted_at_slot, bump
+    "receipt": (
+        "Receipt",
+        RECEIPT,
+        TASK + WORKER + bytes([7] * 32) + string("ipfs://v0-receipt") + u64(110) + u8(251),
+    ),
+    # task, creator, worker, raised_by, reason, status (Raised), raised_at_slot, resolved_at_slot,
+    # arbiter, creator_pct, worker_pct, bump
+    "dispute": (
+        "Dispute",
+        DISPUTE,
+        TASK
+        + CREATOR
+        + WORKER
+        + CREATOR
+        + string("Work does not match the spec")
+        + u8(1)
+        + u64(120)
+        + none
+        + none
+        + none
+        + none
+        + u8(250),
+    ),
+}
+
+
+def main() -> None:
+    os.makedirs(OUT_DIR, exist_ok=True)
+    for name, (account, address, body) in ACCOUNTS.items():
+        data = discriminator(account) + body
+        fixture = {
+            "pubkey": b58encode(address),
+            "account": {
+                # Rent-exempt minimum at the default rent rate.
+                "lamports": (len(data) + 128) * 6960
+                + (ESCROWED_LAMPORTS if name == "escrow" else 0),
+                "data": [base64.b64encode(data).decode(), "base64"],
+                "owner": PROGRAM_ID,
+                "executable": False,
+                "rentEpoch": 0,
+                "space": len(data),
+            },
+        }
+        path = os.path.join(OUT_DIR, f"v0-{name}.json")
+        with open(path, "w") as f:
+            json.dump(fixture, f, indent=2)
+            f.write("\n")
+        print(f"{path}: {fixture['pubkey']}")
+
+
+if __name__ == "__main__":
+    main()
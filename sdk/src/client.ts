@@ -1,30 +1,158 @@
 import * as anchor from "@coral-xyz/anchor";
-import { SystemProgram } from "@solana/web3.js";
+import { createHash } from "crypto";
+import {
+  ComputeBudgetProgram,
+  Ed25519Program,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from "@solana/web3.js";
 import { BOUNTYGRAPH_PROGRAM_ID } from "./programId.ts";
 import { BOUNTYGRAPH_IDL } from "./idl/bountygraph.ts";
 import {
+  findCreatorAllowlistPda,
+  findDepositPda,
   findDisputePda,
   findEscrowPda,
   findGraphPda,
+  findGraphTemplatePda,
+  findMilestoneReceiptPda,
+  findCompletionReceiptPda,
+  findProtocolConfigPda,
+  findReceiptCommitmentPda,
   findReceiptPda,
   findTaskPda,
 } from "./pdas.ts";
 
 export type BountyGraphProgram = any;
 
+export type CreationPolicy = "authorityOnly" | "allowlist" | "open";
+
 export type InitializeGraphArgs = {
+  graphId: bigint | number;
   maxDependenciesPerTask: number;
+  creationPolicy?: CreationPolicy;
+  creationDepositLamports?: bigint | number;
+  guardian?: any | null;
+};
+
+/** Fields left undefined keep their current on-chain value. */
+export type UpdateGraphConfigArgs = {
+  maxDependenciesPerTask?: number;
+  minRewardLamports?: bigint | number;
+  maxRewardLamports?: bigint | number;
+  closeRetentionSlots?: bigint | number;
+  /** Additional fee on worker payouts, paid to the graph authority. */
+  graphFeeBps?: number;
+  /** Require this graph's approval before tasks in other graphs can depend on its tasks. */
+  requireDependentApproval?: boolean;
+  /**
+   * Whether disputed prerequisites unlock dependents: block while a dispute is open, and require
+   * the worker to have kept at least `minWorkerPct` of a resolved one.
+   */
+  dependencyDisputePolicy?: { blockWhileRaised: boolean; minWorkerPct: number };
+};
+
+export type InitializeProtocolConfigArgs = {
+  treasury: any;
+  feeBps: number;
+};
+
+export type UpdateProtocolConfigArgs = {
+  admin?: any;
+  treasury?: any;
+  feeBps?: number;
+};
+
+export type SetCreationPolicyArgs = {
+  creationPolicy: CreationPolicy;
+  creationDepositLamports: bigint | number;
+};
+
+export type CreateTaskOptions = {
+  /** Whether the graph authority co-signs. Defaults to true. */
+  authoritySigns?: boolean;
+  /** Pass the creator's allowlist entry (for `allowlist` graphs). Defaults to false. */
+  useAllowlist?: boolean;
 };
 
 export type CreateTaskArgs = {
   taskId: bigint | number;
   rewardLamports: bigint | number;
   dependencies: Array<bigint | number>;
+  /**
+   * k-of-n alternatives over `dependencies`, e.g. `{ threshold: 2, taskIds: [audit1, audit2, audit3] }`.
+   * Dependencies in no group are all required.
+   */
+  dependencyGroups?: Array<{ threshold: number; taskIds: Array<bigint | number> }>;
+  /** Tasks in other graphs that must be completed first, by graph address and task id. */
+  externalDependencies?: Array<{ graph: any; taskId: bigint | number }>;
+  /** Non-zero enables claim-based assignment with reservations of this many slots. */
+  claimDurationSlots?: bigint | number;
+  /** Non-zero makes the task a competition accepting committed entries until this slot. */
+  competitionDeadlineSlot?: bigint | number;
+  /** Ordered milestone amounts summing to `rewardLamports`. Omit for a single payout. */
+  milestones?: Array<bigint | number>;
+  /** Unlock the reward linearly after completion; withdraw it with `withdrawVested`. */
+  vesting?: { cliffSlots: bigint | number; durationSlots: bigint | number };
+  /** Allow anyone, not just the creator, to raise the reward with `increaseReward`. */
+  crowdfunded?: boolean;
+  /**
+   * Keep the task in `Draft`, refusing receipts, until the reward is fully funded. Without it the
+   * task opens at once and accepts work while unfunded; check `fundingStatus` before starting.
+   */
+  requireFunding?: boolean;
+  /** Create an epic: no submissions, completed by `rollUp` once all its children are. */
+  epic?: boolean;
+  /** Register the task as a child of this epic in the same graph. */
+  parentTaskId?: bigint | number;
+  /** Where the specification lives (at most 200 bytes), e.g. an IPFS URI. */
+  specUri?: string;
+  /** 32-byte hash of the specification; agents can verify the spec they fetch against it. */
+  specHash?: Uint8Array;
+  /** Graph-defined category code, surfaced in the `TaskCreated` event for indexers. */
+  category?: number;
+  /** Up to 4 non-zero graph-defined tag codes. */
+  tags?: number[];
+  /** Make the task repeatable: each approved completion pays `rewardLamports / maxCompletions`. */
+  maxCompletions?: number;
+  /** Minimum slots between completions of a repeatable task. */
+  recurrenceIntervalSlots?: bigint | number;
+  /** Stop accepting completions at this slot; requires `recurrenceIntervalSlots`. */
+  recurrenceEndSlot?: bigint | number;
+  /** Up to 5 oracle keys; receipts then complete the task only through `attestReceipt`. */
+  verifiers?: PublicKey[];
+  /** Distinct verifier attestations required; defaults to 1 when `verifiers` are given. */
+  verifierThreshold?: number;
+};
+
+export type TemplateTaskArgs = {
+  rewardLamports: bigint | number;
+  /** Indices of earlier tasks in the template, in increasing order. */
+  dependencies?: number[];
+  claimDurationSlots?: bigint | number;
+  requireFunding?: boolean;
+  /** Spec metadata copied to every instantiated task; bounded as in `CreateTaskArgs`. */
+  specUri?: string;
+  specHash?: Uint8Array;
+  category?: number;
+  tags?: number[];
 };
 
 export type SubmitReceiptArgs = {
   workHash: Uint8Array; // 32 bytes
   uri: string;
+  /** Team shares in basis points, including the submitting agent. Omit for a solo submission. */
+  contributors?: Array<{ worker: any; shareBps: number }>;
+  /** Contributors co-signing this submission; others accept later via `acceptContribution`. */
+  coSigners?: anchor.web3.Keypair[];
+};
+
+export type RevealReceiptArgs = {
+  workHash: Uint8Array; // 32 bytes
+  uri: string;
+  /** 32 random bytes, kept secret until the reveal. */
+  salt: Uint8Array;
 };
 
 export type DisputeTaskArgs = {
@@ -45,10 +173,65 @@ function u64(x: bigint | number): any {
   return new anchor.BN(x.toString());
 }
 
+function creationPolicyArg(policy: CreationPolicy): any {
+  return { [policy]: {} };
+}
+
 function normalizeDeps(deps: Array<bigint | number>): anchor.BN[] {
   return deps.map((d) => u64(d));
 }
 
+function toCreateTaskParams(args: CreateTaskArgs) {
+  return {
+    taskId: u64(args.taskId),
+    rewardLamports: u64(args.rewardLamports),
+    dependencies: normalizeDeps(args.dependencies),
+    dependencyGroups: (args.dependencyGroups ?? []).map((group) => ({
+      threshold: group.threshold,
+      taskIds: normalizeDeps(group.taskIds),
+    })),
+    externalDependencies: (args.externalDependencies ?? []).map((edge) => ({
+      graph: edge.graph,
+      taskId: u64(edge.taskId),
+    })),
+    claimDurationSlots: u64(args.claimDurationSlots ?? 0),
+    competitionDeadlineSlot: u64(args.competitionDeadlineSlot ?? 0),
+    milestones: (args.milestones ?? []).map(u64),
+    vesting: args.vesting
+      ? { cliffSlots: u64(args.vesting.cliffSlots), durationSlots: u64(args.vesting.durationSlots) }
+      : null,
+    crowdfunded: args.crowdfunded ?? false,
+    requireFunding: args.requireFunding ?? false,
+    epic: args.epic ?? false,
+    parentTaskId: args.parentTaskId === undefined ? null : u64(args.parentTaskId),
+    specUri: args.specUri ?? "",
+    specHash: args.specHash ? asWorkHash32(args.specHash) : new Array(32).fill(0),
+    category: args.category ?? 0,
+    tags: args.tags ?? [],
+    maxCompletions: args.maxCompletions ?? 0,
+    recurrenceIntervalSlots: u64(args.recurrenceIntervalSlots ?? 0),
+    recurrenceEndSlot: u64(args.recurrenceEndSlot ?? 0),
+    verifiers: args.verifiers ?? [],
+    verifierThreshold: args.verifierThreshold ?? (args.verifiers?.length ? 1 : 0),
+  };
+}
+
+/** The value `commitReceipt` stores: sha256(workHash || uri || salt || agent). */
+export function computeReceiptCommitment(args: RevealReceiptArgs, agent: PublicKey): number[] {
+  const digest = createHash("sha256")
+    .update(Buffer.from(asWorkHash32(args.workHash)))
+    .update(Buffer.from(args.uri, "utf8"))
+    .update(Buffer.from(asWorkHash32(args.salt)))
+    .update(agent.toBuffer())
+    .digest();
+  return Array.from(digest);
+}
+
+/** What a task verifier signs to attest a receipt: task || agent || workHash. */
+export function attestationMessage(task: PublicKey, agent: PublicKey, workHash: Uint8Array): Buffer {
+  return Buffer.concat([task.toBuffer(), agent.toBuffer(), Buffer.from(asWorkHash32(workHash))]);
+}
+
 function asWorkHash32(workHash: Uint8Array): number[] {
   if (workHash.length !== 32) throw new Error(`workHash must be 32 bytes; got ${workHash.length}`);
   return Array.from(workHash);
@@ -78,65 +261,390 @@ export class BountyGraphClient {
   }
 
   pdas = {
-    graph: (authority: any) => findGraphPda(authority, this.programId),
+    graph: (authority: any, graphId: bigint | number) =>
+      findGraphPda(authority, graphId, this.programId),
     task: (graph: any, taskId: bigint | number) => findTaskPda(graph, taskId, this.programId),
     escrow: (task: any) => findEscrowPda(task, this.programId),
+    deposit: (task: any, funder: any) => findDepositPda(task, funder, this.programId),
     receipt: (task: any, agent: any) => findReceiptPda(task, agent, this.programId),
+    receiptCommitment: (task: any, agent: any) =>
+      findReceiptCommitmentPda(task, agent, this.programId),
+    milestoneReceipt: (task: any, agent: any, index: number) =>
+      findMilestoneReceiptPda(task, agent, index, this.programId),
+    completionReceipt: (task: any, agent: any, index: number) =>
+      findCompletionReceiptPda(task, agent, index, this.programId),
     dispute: (task: any, initiator: any) => findDisputePda(task, initiator, this.programId),
+    creatorAllowlist: (graph: any, creator: any) =>
+      findCreatorAllowlistPda(graph, creator, this.programId),
+    protocolConfig: () => findProtocolConfigPda(this.programId),
+    graphTemplate: (authority: any, templateId: bigint | number) =>
+      findGraphTemplatePda(authority, templateId, this.programId),
   };
 
+  /**
+   * Create the protocol fee config. `admin` must be the program's upgrade authority.
+   */
+  async initializeProtocolConfig(admin: any, args: InitializeProtocolConfigArgs): Promise<string> {
+    const [protocolConfig] = this.pdas.protocolConfig();
+    const [programData] = PublicKey.findProgramAddressSync(
+      [this.programId.toBuffer()],
+      new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+    );
+    return this.program.methods
+      .initializeProtocolConfig({ treasury: args.treasury, feeBps: args.feeBps })
+      .accounts({
+        protocolConfig,
+        program: this.programId,
+        programData,
+        admin,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  }
+
+  async updateProtocolConfig(admin: any, args: UpdateProtocolConfigArgs): Promise<string> {
+    const [protocolConfig] = this.pdas.protocolConfig();
+    return this.program.methods
+      .updateProtocolConfig({
+        admin: args.admin ?? null,
+        treasury: args.treasury ?? null,
+        feeBps: args.feeBps ?? null,
+      })
+      .accounts({ protocolConfig, admin })
+      .rpc();
+  }
+
+  /** Fee destination accounts required by every instruction that pays a worker. */
+  private async feeAccounts(graph: any): Promise<{ protocolConfig: any; treasury: any; graphAuthority: any }> {
+    const [protocolConfig] = this.pdas.protocolConfig();
+    const config = await this.program.account.protocolConfig.fetch(protocolConfig);
+    const graphAccount = await this.program.account.graph.fetch(graph);
+    return { protocolConfig, treasury: config.treasury, graphAuthority: graphAccount.authority };
+  }
+
   async initializeGraph(authority: any, args: InitializeGraphArgs): Promise<string> {
-    const [graph] = this.pdas.graph(authority);
+    const [graph] = this.pdas.graph(authority, args.graphId);
+    return this.program.methods
+      .initializeGraph({
+        graphId: u64(args.graphId),
+        maxDependenciesPerTask: args.maxDependenciesPerTask,
+        creationPolicy: creationPolicyArg(args.creationPolicy ?? "authorityOnly"),
+        creationDepositLamports: u64(args.creationDepositLamports ?? 0),
+        guardian: args.guardian ?? null,
+      })
+      .accounts({
+        graph,
+        authority,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  }
+
+  /** Fetch the graph a task belongs to (needed for instructions gated on graph state). */
+  async graphOf(task: any): Promise<any> {
+    const account = await this.program.account.task.fetch(task);
+    return account.graph;
+  }
+
+  async updateGraphConfig(
+    authority: any,
+    graphId: bigint | number,
+    args: UpdateGraphConfigArgs
+  ): Promise<string> {
+    const [graph] = this.pdas.graph(authority, graphId);
+    return this.program.methods
+      .updateGraphConfig({
+        maxDependenciesPerTask: args.maxDependenciesPerTask ?? null,
+        minRewardLamports: args.minRewardLamports === undefined ? null : u64(args.minRewardLamports),
+        maxRewardLamports: args.maxRewardLamports === undefined ? null : u64(args.maxRewardLamports),
+        closeRetentionSlots:
+          args.closeRetentionSlots === undefined ? null : u64(args.closeRetentionSlots),
+        graphFeeBps: args.graphFeeBps ?? null,
+        requireDependentApproval: args.requireDependentApproval ?? null,
+        dependencyDisputePolicy: args.dependencyDisputePolicy ?? null,
+      })
+      .accounts({ graph, authority })
+      .rpc();
+  }
+
+  /**
+   * Upgrade a v0 account in place. `kind` selects the expected account type; `payer` covers the
+   * extra rent for the larger layout.
+   */
+  async migrateAccount(
+    kind: "graph" | "task" | "escrow" | "receipt" | "dispute",
+    account: any,
+    payer: any
+  ): Promise<string> {
+    const method = {
+      graph: "migrateGraph",
+      task: "migrateTask",
+      escrow: "migrateEscrow",
+      receipt: "migrateReceipt",
+      dispute: "migrateDispute",
+    }[kind];
+    // A task's funding is read from its escrow, which need not exist.
+    const escrow = kind === "task" ? { escrow: this.pdas.escrow(account)[0] } : {};
+    return this.program.methods[method]()
+      .accounts({ account, ...escrow, payer, systemProgram: SystemProgram.programId })
+      .rpc();
+  }
+
+  async setGuardian(authority: any, graphId: bigint | number, guardian: any | null): Promise<string> {
+    const [graph] = this.pdas.graph(authority, graphId);
+    return this.program.methods.setGuardian(guardian).accounts({ graph, authority }).rpc();
+  }
+
+  /** Pause a graph. `signer` may be the graph authority or its guardian. */
+  async pauseGraph(graph: any, signer: any, reasonCode: number): Promise<string> {
+    return this.program.methods.pauseGraph(reasonCode).accounts({ graph, signer }).rpc();
+  }
+
+  async unpauseGraph(authority: any, graphId: bigint | number): Promise<string> {
+    const [graph] = this.pdas.graph(authority, graphId);
+    return this.program.methods.unpauseGraph().accounts({ graph, authority }).rpc();
+  }
+
+  async setCreationPolicy(
+    authority: any,
+    graphId: bigint | number,
+    args: SetCreationPolicyArgs
+  ): Promise<string> {
+    const [graph] = this.pdas.graph(authority, graphId);
     return this.program.methods
-      .initializeGraph({ maxDependenciesPerTask: args.maxDependenciesPerTask })
+      .setCreationPolicy({
+        creationPolicy: creationPolicyArg(args.creationPolicy),
+        creationDepositLamports: u64(args.creationDepositLamports),
+      })
+      .accounts({ graph, authority })
+      .rpc();
+  }
+
+  async addAllowedCreator(
+    authority: any,
+    graphId: bigint | number,
+    creator: any
+  ): Promise<{ creatorAllowlist: any; signature: string }> {
+    const [graph] = this.pdas.graph(authority, graphId);
+    const [creatorAllowlist] = this.pdas.creatorAllowlist(graph, creator);
+    const signature = await this.program.methods
+      .addAllowedCreator()
       .accounts({
         graph,
         authority,
+        creator,
+        creatorAllowlist,
         systemProgram: SystemProgram.programId,
       })
       .rpc();
+
+    return { creatorAllowlist, signature };
+  }
+
+  async removeAllowedCreator(
+    authority: any,
+    graphId: bigint | number,
+    creator: any
+  ): Promise<string> {
+    const [graph] = this.pdas.graph(authority, graphId);
+    const [creatorAllowlist] = this.pdas.creatorAllowlist(graph, creator);
+    return this.program.methods
+      .removeAllowedCreator()
+      .accounts({ graph, authority, creatorAllowlist })
+      .rpc();
   }
 
   async createTask(
     authority: any,
+    graphId: bigint | number,
     creator: any,
-    args: CreateTaskArgs
+    args: CreateTaskArgs,
+    opts: CreateTaskOptions = {}
   ): Promise<{ task: any; signature: string }> {
-    const [graph] = this.pdas.graph(authority);
+    const [graph] = this.pdas.graph(authority, graphId);
     const [task] = this.pdas.task(graph, args.taskId);
+    const creatorAllowlist = opts.useAllowlist
+      ? this.pdas.creatorAllowlist(graph, creator)[0]
+      : null;
 
-    const deps = normalizeDeps(args.dependencies);
+    const external = args.externalDependencies ?? [];
+    const readonly = (pubkey: any) => ({ pubkey, isSigner: false, isWritable: false });
 
     const signature = await this.program.methods
-      .createTask({
-        taskId: u64(args.taskId),
-        rewardLamports: u64(args.rewardLamports),
-        dependencies: deps,
-      })
+      .createTask(toCreateTaskParams(args))
       .accounts({
         graph,
-        authority,
+        authority: opts.authoritySigns === false ? null : authority,
         creator,
+        creatorAllowlist,
+        parent: args.parentTaskId === undefined ? null : this.pdas.task(graph, args.parentTaskId)[0],
         task,
         systemProgram: SystemProgram.programId,
       })
+      .remainingAccounts([
+        ...args.dependencies.map((id) => readonly(this.pdas.task(graph, id)[0])),
+        ...external.flatMap((edge) => [readonly(edge.graph), readonly(this.pdas.task(edge.graph, edge.taskId)[0])]),
+      ])
       .rpc();
 
     return { task, signature };
   }
 
+  /**
+   * Create several tasks in one transaction. Tasks may depend on earlier entries in `tasks`, and
+   * an epic's children may follow it in the same batch. Each existing dependency account is passed
+   * once, however many entries reference it.
+   */
+  async createTasksBatch(
+    authority: any,
+    graphId: bigint | number,
+    creator: any,
+    tasks: CreateTaskArgs[],
+    opts: CreateTaskOptions & { computeUnitLimit?: number } = {}
+  ): Promise<{ tasks: any[]; signature: string }> {
+    const [graph] = this.pdas.graph(authority, graphId);
+    const creatorAllowlist = opts.useAllowlist
+      ? this.pdas.creatorAllowlist(graph, creator)[0]
+      : null;
+    const taskPdas = tasks.map((t) => this.pdas.task(graph, t.taskId)[0]);
+
+    const inBatch = new Set(tasks.map((t) => BigInt(t.taskId)));
+    const pool = new Map<string, any>();
+    const add = (pubkey: any) => pool.set(pubkey.toBase58(), pubkey);
+    for (const t of tasks) {
+      t.dependencies.filter((id) => !inBatch.has(BigInt(id))).forEach((id) => add(this.pdas.task(graph, id)[0]));
+      for (const edge of t.externalDependencies ?? []) {
+        add(edge.graph);
+        add(this.pdas.task(edge.graph, edge.taskId)[0]);
+      }
+    }
+
+    const builder = this.program.methods
+      .createTasksBatch(tasks.map(toCreateTaskParams))
+      .accounts({
+        graph,
+        authority: opts.authoritySigns === false ? null : authority,
+        creator,
+        creatorAllowlist,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts([
+        ...taskPdas.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true })),
+        ...[...pool.values()].map((pubkey) => ({ pubkey, isSigner: false, isWritable: false })),
+      ]);
+    const signature = await (opts.computeUnitLimit
+      ? builder.preInstructions([
+          ComputeBudgetProgram.setComputeUnitLimit({ units: opts.computeUnitLimit }),
+        ])
+      : builder
+    ).rpc();
+
+    return { tasks: taskPdas, signature };
+  }
+
+  /** Store a reusable DAG of task specs; each task may depend on earlier ones by index. */
+  async createTemplate(
+    authority: any,
+    templateId: bigint | number,
+    tasks: TemplateTaskArgs[]
+  ): Promise<{ template: any; signature: string }> {
+    const [template] = this.pdas.graphTemplate(authority, templateId);
+    const signature = await this.program.methods
+      .createTemplate(
+        u64(templateId),
+        tasks.map((t) => ({
+          rewardLamports: u64(t.rewardLamports),
+          dependencies: Buffer.from(t.dependencies ?? []),
+          claimDurationSlots: u64(t.claimDurationSlots ?? 0),
+          requireFunding: t.requireFunding ?? false,
+          specUri: t.specUri ?? "",
+          specHash: t.specHash ? asWorkHash32(t.specHash) : new Array(32).fill(0),
+          category: t.category ?? 0,
+          tags: t.tags ?? [],
+        }))
+      )
+      .accounts({ template, authority, systemProgram: SystemProgram.programId })
+      .rpc();
+    return { template, signature };
+  }
+
+  async closeTemplate(template: any, authority: any): Promise<string> {
+    return this.program.methods.closeTemplate().accounts({ template, authority }).rpc();
+  }
+
+  /**
+   * Create a template's tasks in a graph, with task ids `taskIdOffset + index`. Pass
+   * `rewardMultiplierBps` to scale every reward (10_000 = unchanged).
+   */
+  async instantiateTemplate(
+    authority: any,
+    graphId: bigint | number,
+    creator: any,
+    template: any,
+    args: { taskIdOffset: bigint | number; rewardMultiplierBps?: number },
+    opts: CreateTaskOptions = {}
+  ): Promise<{ tasks: any[]; signature: string }> {
+    const [graph] = this.pdas.graph(authority, graphId);
+    const account = await this.program.account.graphTemplate.fetch(template);
+    const tasks = account.tasks.map(
+      (_: unknown, i: number) => this.pdas.task(graph, BigInt(args.taskIdOffset) + BigInt(i))[0]
+    );
+    const creatorAllowlist = opts.useAllowlist
+      ? this.pdas.creatorAllowlist(graph, creator)[0]
+      : null;
+
+    const signature = await this.program.methods
+      .instantiateTemplate({
+        taskIdOffset: u64(args.taskIdOffset),
+        rewardMultiplierBps: args.rewardMultiplierBps ?? null,
+      })
+      .accounts({
+        graph,
+        template,
+        authority: opts.authoritySigns === false ? null : authority,
+        creator,
+        creatorAllowlist,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(tasks.map((pubkey: any) => ({ pubkey, isSigner: false, isWritable: true })))
+      .rpc();
+
+    return { tasks, signature };
+  }
+
+  /** As the authority of `graphId`, approve a cross-graph edge from `task` into that graph. */
+  async approveExternalDependency(
+    authority: any,
+    graphId: bigint | number,
+    task: any,
+    index: number
+  ): Promise<string> {
+    const [graph] = this.pdas.graph(authority, graphId);
+    return this.program.methods
+      .approveExternalDependency(index)
+      .accounts({ graph, authority, task })
+      .rpc();
+  }
+
   async fundTask(
     task: any,
     funder: any,
     lamports: bigint | number
   ): Promise<{ escrow: any; signature: string }> {
     const [escrow] = this.pdas.escrow(task);
+    const { graph, creator } = await this.program.account.task.fetch(task);
+    // Funders other than the creator are recorded so a cancellation can refund them.
+    const deposit = new PublicKey(funder).equals(creator)
+      ? null
+      : this.pdas.deposit(task, funder)[0];
 
     const signature = await this.program.methods
       .fundTask(u64(lamports))
       .accounts({
+        graph,
         task,
         escrow,
+        deposit,
         funder,
         systemProgram: SystemProgram.programId,
       })
@@ -145,6 +653,48 @@ export class BountyGraphClient {
     return { escrow, signature };
   }
 
+  /** Status, reward and amount funded so far, computed on-chain via a simulated `funding_status`. */
+  async fundingStatus(task: any): Promise<{
+    status: any;
+    rewardLamports: anchor.BN;
+    fundedLamports: anchor.BN;
+    fullyFunded: boolean;
+  }> {
+    return this.program.methods.fundingStatus().accounts({ task }).view();
+  }
+
+  /** Raise an open task's reward, depositing the extra lamports into its escrow. */
+  async increaseReward(
+    task: any,
+    funder: any,
+    additionalLamports: bigint | number
+  ): Promise<{ signature: string }> {
+    const [escrow] = this.pdas.escrow(task);
+    const { graph, creator } = await this.program.account.task.fetch(task);
+    // Funders other than the creator are recorded so a cancellation can refund them.
+    const deposit = new PublicKey(funder).equals(creator)
+      ? null
+      : this.pdas.deposit(task, funder)[0];
+
+    const signature = await this.program.methods
+      .increaseReward(u64(additionalLamports))
+      .accounts({
+        graph,
+        task,
+        escrow,
+        deposit,
+        funder,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    return { signature };
+  }
+
+  /**
+   * `dependencyTasks` lists the task's dependencies in order, followed by the task accounts of its
+   * external dependencies in order.
+   */
   async submitReceipt(
     task: any,
     agent: any,
@@ -152,14 +702,109 @@ export class BountyGraphClient {
     dependencyTasks: any[]
   ): Promise<{ receipt: any; signature: string }> {
     const [receipt] = this.pdas.receipt(task, agent);
+    const graph = await this.graphOf(task);
 
     const signature = await this.program.methods
       .submitReceipt({
         workHash: asWorkHash32(args.workHash),
         uri: args.uri,
+        contributors: args.contributors ?? [],
+      })
+      .accounts({
+        graph,
+        task,
+        receipt,
+        agent,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts([
+        ...dependencyTasks.map((pk) => ({ pubkey: pk, isSigner: false, isWritable: false })),
+        ...(args.coSigners ?? []).map((kp) => ({ pubkey: kp.publicKey, isSigner: true, isWritable: false })),
+      ])
+      .signers(args.coSigners ?? [])
+      .rpc();
+
+    return { receipt, signature };
+  }
+
+  /**
+   * Complete a task with verifiers from `agent`'s receipt. Each attestation is a verifier's
+   * Ed25519 signature over `attestationMessage(task, agent, workHash)`, produced off-chain by the
+   * oracle; they are checked by Ed25519 program instructions in the same transaction.
+   */
+  async attestReceipt(
+    task: any,
+    agent: any,
+    attestations: Array<{ verifier: PublicKey; signature: Uint8Array }>
+  ): Promise<string> {
+    const [receipt] = this.pdas.receipt(task, agent);
+    const receiptAccount = await this.program.account.receipt.fetch(receipt);
+    const message = attestationMessage(
+      new PublicKey(task),
+      new PublicKey(agent),
+      Uint8Array.from(receiptAccount.workHash)
+    );
+    const graph = await this.graphOf(task);
+
+    return this.program.methods
+      .attestReceipt()
+      .accounts({ graph, task, receipt, instructions: SYSVAR_INSTRUCTIONS_PUBKEY })
+      .preInstructions(
+        attestations.map(({ verifier, signature }) =>
+          Ed25519Program.createInstructionWithPublicKey({
+            publicKey: verifier.toBytes(),
+            message,
+            signature,
+          })
+        )
+      )
+      .rpc();
+  }
+
+  /** Reject `agent`'s receipt while it awaits attestation, closing it back to the agent. */
+  async rejectReceipt(task: any, creator: any, agent: any): Promise<string> {
+    const [receipt] = this.pdas.receipt(task, agent);
+    return this.program.methods.rejectReceipt().accounts({ task, receipt, creator, agent }).rpc();
+  }
+
+  /**
+   * Enter a competition without publishing the entry; reveal with `revealReceipt` within
+   * `REVEAL_WINDOW_SLOTS` (150). The receipt then ranks by this commit's slot.
+   */
+  async commitReceipt(task: any, agent: any, args: RevealReceiptArgs): Promise<string> {
+    const graph = await this.graphOf(task);
+    return this.program.methods
+      .commitReceipt(computeReceiptCommitment(args, new PublicKey(agent)))
+      .accounts({
+        graph,
+        task,
+        commitment: this.pdas.receiptCommitment(task, agent)[0],
+        agent,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  }
+
+  /** Open a `commitReceipt` commitment. `dependencyTasks` are passed as for `submitReceipt`. */
+  async revealReceipt(
+    task: any,
+    agent: any,
+    args: RevealReceiptArgs,
+    dependencyTasks: any[]
+  ): Promise<{ receipt: any; signature: string }> {
+    const [receipt] = this.pdas.receipt(task, agent);
+    const graph = await this.graphOf(task);
+
+    const signature = await this.program.methods
+      .revealReceipt({
+        workHash: asWorkHash32(args.workHash),
+        uri: args.uri,
+        salt: asWorkHash32(args.salt),
       })
       .accounts({
+        graph,
         task,
+        commitment: this.pdas.receiptCommitment(task, agent)[0],
         receipt,
         agent,
         systemProgram: SystemProgram.programId,
@@ -170,16 +815,327 @@ export class BountyGraphClient {
     return { receipt, signature };
   }
 
+  /**
+   * Complete an epic once all of its children are completed. Permissionless; any funded bonus is
+   * split equally between the children's workers. `childTaskIds` must list every child.
+   */
+  async rollUp(epic: any, childTaskIds: Array<bigint | number>): Promise<string> {
+    const account = await this.program.account.task.fetch(epic);
+    const graph = account.graph;
+    const children = [...childTaskIds]
+      .sort((x, y) => (BigInt(x) < BigInt(y) ? -1 : 1))
+      .map((id) => this.pdas.task(graph, id)[0]);
+    const funded = !account.fundedLamports.isZero();
+
+    // Closed children are tombstones, which still record who completed them.
+    const workers = funded
+      ? await Promise.all(
+          children.map(async (child) => {
+            const view: any = await this.program.account.task
+              .fetch(child)
+              .catch(() => this.program.account.taskTombstone.fetch(child));
+            return view.completedBy ?? account.creator;
+          })
+        )
+      : [];
+
+    return this.program.methods
+      .rollUp()
+      .accounts({
+        graph,
+        task: epic,
+        escrow: funded ? this.pdas.escrow(epic)[0] : null,
+        ...(await this.feeAccounts(graph)),
+      })
+      .remainingAccounts([
+        ...children.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false })),
+        ...workers.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true })),
+      ])
+      .rpc();
+  }
+
   async claimReward(task: any, agent: any): Promise<string> {
     const [escrow] = this.pdas.escrow(task);
+    const account = await this.program.account.task.fetch(task);
+    // Competition winners claim against their own receipt; teams are paid per the completing one.
+    const team = await this.teamAccounts(account, task);
+    const receipt = account.competitionDeadlineSlot.isZero()
+      ? team.receipt
+      : this.pdas.receipt(task, agent)[0];
     return this.program.methods
       .claimReward()
       .accounts({
+        graph: account.graph,
         task,
         escrow,
+        ...(await this.feeAccounts(account.graph)),
+        receipt,
+        agent,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(team.recipients)
+      .rpc();
+  }
+
+  async submitMilestone(
+    task: any,
+    agent: any,
+    index: number,
+    args: SubmitReceiptArgs,
+    dependencyTasks: any[]
+  ): Promise<{ receipt: any; signature: string }> {
+    const [receipt] = this.pdas.milestoneReceipt(task, agent, index);
+    const graph = await this.graphOf(task);
+
+    const signature = await this.program.methods
+      .submitMilestone({ index, workHash: asWorkHash32(args.workHash), uri: args.uri })
+      .accounts({
+        graph,
+        task,
+        receipt,
+        agent,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(dependencyTasks.map((pk) => ({ pubkey: pk, isSigner: false, isWritable: false })))
+      .rpc();
+
+    return { receipt, signature };
+  }
+
+  /**
+   * Record the next completion of a repeatable task. Its slice of the escrow is paid once the
+   * creator calls `approveCompletion`. `dependencyTasks` are passed as for `submitReceipt`.
+   */
+  async submitCompletion(
+    task: any,
+    agent: any,
+    args: SubmitReceiptArgs,
+    dependencyTasks: any[]
+  ): Promise<{ receipt: any; signature: string }> {
+    const account = await this.program.account.task.fetch(task);
+    const [receipt] = this.pdas.completionReceipt(task, agent, account.completionCount);
+
+    const signature = await this.program.methods
+      .submitCompletion({ workHash: asWorkHash32(args.workHash), uri: args.uri })
+      .accounts({
+        graph: account.graph,
+        task,
+        receipt,
         agent,
         systemProgram: SystemProgram.programId,
       })
+      .remainingAccounts(dependencyTasks.map((pk) => ({ pubkey: pk, isSigner: false, isWritable: false })))
+      .rpc();
+
+    return { receipt, signature };
+  }
+
+  /** Pay the pending completion's slice to the agent who submitted it. */
+  async approveCompletion(task: any, creator: any): Promise<string> {
+    const account = await this.program.account.task.fetch(task);
+    const worker = account.pendingCompletionAgent;
+    return this.program.methods
+      .approveCompletion()
+      .accounts({
+        graph: account.graph,
+        task,
+        receipt: this.pdas.completionReceipt(task, worker, account.completionCount)[0],
+        escrow: this.pdas.escrow(task)[0],
+        ...(await this.feeAccounts(account.graph)),
+        creator,
+        worker,
+      })
+      .rpc();
+  }
+
+  /** Reject the pending completion, closing its receipt back to the agent. */
+  async rejectCompletion(task: any, creator: any): Promise<string> {
+    const account = await this.program.account.task.fetch(task);
+    const worker = account.pendingCompletionAgent;
+    return this.program.methods
+      .rejectCompletion()
+      .accounts({
+        task,
+        receipt: this.pdas.completionReceipt(task, worker, account.completionCount)[0],
+        creator,
+        worker,
+      })
+      .rpc();
+  }
+
+  /** Withdraw whatever part of a vesting reward has unlocked since the last withdrawal. */
+  async withdrawVested(task: any, agent: any): Promise<string> {
+    const [escrow] = this.pdas.escrow(task);
+    const graph = await this.graphOf(task);
+    return this.program.methods
+      .withdrawVested()
+      .accounts({ graph, task, escrow, ...(await this.feeAccounts(graph)), agent })
+      .rpc();
+  }
+
+  /** Approve and pay milestone `index` to the worker who submitted it. */
+  async approveMilestone(task: any, creator: any, index: number): Promise<string> {
+    const [escrow] = this.pdas.escrow(task);
+    const account = await this.program.account.task.fetch(task);
+    return this.program.methods
+      .approveMilestone(index)
+      .accounts({
+        graph: account.graph,
+        task,
+        escrow,
+        ...(await this.feeAccounts(account.graph)),
+        creator,
+        worker: account.milestones[index].worker,
+      })
+      .rpc();
+  }
+
+  /** Send submitted milestone `index` back to Pending so its worker can resubmit. */
+  async rejectMilestone(task: any, creator: any, index: number): Promise<string> {
+    const account = await this.program.account.task.fetch(task);
+    const worker = account.milestones[index].worker;
+    return this.program.methods
+      .rejectMilestone(index)
+      .accounts({
+        task,
+        receipt: this.pdas.milestoneReceipt(task, worker, index)[0],
+        creator,
+        worker,
+      })
+      .rpc();
+  }
+
+  /** Accept a share on a team receipt that `contributor` did not co-sign. */
+  async acceptContribution(receipt: any, contributor: any): Promise<string> {
+    return this.program.methods.acceptContribution().accounts({ receipt, contributor }).rpc();
+  }
+
+  /** Completing receipt and writable contributor accounts for a team task; empty for solo tasks. */
+  private async teamAccounts(taskAccount: any, task: any): Promise<{ receipt: any; recipients: any[] }> {
+    if (taskAccount.teamSize === 0 || !taskAccount.completedBy) {
+      return { receipt: null, recipients: [] };
+    }
+    const [receipt] = this.pdas.receipt(task, taskAccount.completedBy);
+    const receiptAccount = await this.program.account.receipt.fetch(receipt);
+    const recipients = receiptAccount.contributors.map((c: any) => ({
+      pubkey: c.worker,
+      isSigner: false,
+      isWritable: true,
+    }));
+    return { receipt, recipients };
+  }
+
+  /**
+   * Pick competition winners after the deadline. `winners` are the agents whose receipts win,
+   * paired with their share of the pool in basis points (must sum to 10000).
+   */
+  async selectWinners(
+    task: any,
+    selector: any,
+    winners: Array<{ agent: any; weightBps: number }>
+  ): Promise<string> {
+    const [escrow] = this.pdas.escrow(task);
+    const graph = await this.graphOf(task);
+    return this.program.methods
+      .selectWinners({ weightsBps: winners.map((w) => w.weightBps) })
+      .accounts({ graph, task, escrow, selector })
+      .remainingAccounts(
+        winners.map((w) => ({
+          pubkey: this.pdas.receipt(task, w.agent)[0],
+          isSigner: false,
+          isWritable: true,
+        }))
+      )
+      .rpc();
+  }
+
+  async claimTask(task: any, agent: any): Promise<string> {
+    return this.program.methods.claimTask().accounts({ task, agent }).rpc();
+  }
+
+  async unclaimTask(task: any, agent: any): Promise<string> {
+    return this.program.methods.unclaimTask().accounts({ task, agent }).rpc();
+  }
+
+  /** Assign `worker` directly (or clear the assignment with `null`). */
+  async assignTask(task: any, creator: any, worker: any | null): Promise<string> {
+    return this.program.methods.assignTask(worker).accounts({ task, creator }).rpc();
+  }
+
+  /**
+   * Replace a task's spec and bump its revision. If a worker holds a claim or has taken up a
+   * milestone, pass them as `worker`; they must sign the transaction too.
+   */
+  async amendTaskSpec(
+    task: any,
+    creator: any,
+    args: { specUri: string; specHash: Uint8Array },
+    worker: any = null
+  ): Promise<string> {
+    const graph = await this.graphOf(task);
+    return this.program.methods
+      .amendTaskSpec({ specUri: args.specUri, specHash: asWorkHash32(args.specHash) })
+      .accounts({ graph, task, creator, worker })
+      .rpc();
+  }
+
+  /**
+   * Cancel an Open task, refunding its escrow if it was funded. A child of an epic must pass the
+   * epic as `parent`.
+   */
+  async cancelTask(task: any, creator: any, parent: any = null): Promise<string> {
+    // A funded task must be cancelled with its escrow.
+    const account: any = await this.program.account.task.fetch(task);
+    const escrow = account.fundedLamports.gtn(0) ? this.pdas.escrow(task)[0] : null;
+    return this.program.methods.cancelTask().accounts({ task, escrow, parent, creator }).rpc();
+  }
+
+  /**
+   * Close `funder`'s Deposit on a terminal task. On a cancelled task this pays back their share of
+   * the escrow; anyone may send it.
+   */
+  async refundDeposit(task: any, funder: any): Promise<string> {
+    const { creator, status } = await this.program.account.task.fetch(task);
+    const escrow = status.cancelled ? this.pdas.escrow(task)[0] : null;
+    return this.program.methods
+      .refundDeposit()
+      .accounts({ task, escrow, deposit: this.pdas.deposit(task, funder)[0], funder, creator })
+      .rpc();
+  }
+
+  /** Shrink a terminal task to its tombstone and reclaim the rent difference. */
+  async closeTask(task: any, creator: any): Promise<string> {
+    const graph = await this.graphOf(task);
+    return this.program.methods.closeTask().accounts({ graph, task, creator }).rpc();
+  }
+
+  /** Close a whole-task receipt, or a milestone or completion receipt when `index` names it. */
+  async closeReceipt(
+    graph: any,
+    task: any,
+    agent: any,
+    index?: { milestone: number } | { completion: number }
+  ): Promise<string> {
+    const [receipt] =
+      index === undefined
+        ? this.pdas.receipt(task, agent)
+        : "milestone" in index
+          ? this.pdas.milestoneReceipt(task, agent, index.milestone)
+          : this.pdas.completionReceipt(task, agent, index.completion);
+    return this.program.methods.closeReceipt().accounts({ graph, task, receipt, agent }).rpc();
+  }
+
+  /** Reclaim the rent of a commitment whose reveal window has passed unrevealed. */
+  async closeCommitment(task: any, agent: any): Promise<string> {
+    const [commitment] = this.pdas.receiptCommitment(task, agent);
+    return this.program.methods.closeCommitment().accounts({ task, commitment, agent }).rpc();
+  }
+
+  async closeDispute(graph: any, task: any, initiator: any): Promise<string> {
+    const [dispute] = this.pdas.dispute(task, initiator);
+    return this.program.methods
+      .closeDispute()
+      .accounts({ graph, task, dispute, initiator })
       .rpc();
   }
 
@@ -200,14 +1156,19 @@ export class BountyGraphClient {
 
   async resolveDispute(
     authority: any,
+    graphId: bigint | number,
     task: any,
     dispute: any,
     creator: any,
     worker: any,
     args: ResolveDisputeArgs
   ) {
-    const [graph] = this.pdas.graph(authority);
+    const [graph] = this.pdas.graph(authority, graphId);
+    const taskAccount: any = await this.program.account.task.fetch(task);
+    // Passed even when it does not exist; the outcome is then recorded without a payout.
     const [escrow] = this.pdas.escrow(task);
+    const team = await this.teamAccounts(taskAccount, task);
+    const fees = await this.feeAccounts(graph);
     const signature = await this.program.methods
       .resolveDispute({ creatorPct: args.creatorPct, workerPct: args.workerPct })
       .accounts({
@@ -218,8 +1179,11 @@ export class BountyGraphClient {
         escrow,
         creator,
         worker,
-        systemProgram: SystemProgram.programId,
+        protocolConfig: fees.protocolConfig,
+        treasury: fees.treasury,
+        receipt: team.receipt,
       })
+      .remainingAccounts(team.recipients)
       .rpc();
 
     return { escrow, signature };
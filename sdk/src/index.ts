@@ -1,10 +1,24 @@
 export { BOUNTYGRAPH_PROGRAM_ID } from "./programId.ts";
 export { BOUNTYGRAPH_IDL } from "./idl/bountygraph.ts";
 export {
+  findCompletionReceiptPda,
+  findCreatorAllowlistPda,
+  findDepositPda,
   findDisputePda,
   findEscrowPda,
   findGraphPda,
+  findGraphTemplatePda,
+  findLegacyGraphPda,
+  findMilestoneReceiptPda,
+  findProtocolConfigPda,
+  findReceiptCommitmentPda,
   findReceiptPda,
   findTaskPda,
+  LEGACY_GRAPH_ID,
 } from "./pdas.ts";
-export { BountyGraphClient, createBountyGraphProgram } from "./client.ts";
+export {
+  attestationMessage,
+  BountyGraphClient,
+  computeReceiptCommitment,
+  createBountyGraphProgram,
+} from "./client.ts";
@@ -22,21 +22,73 @@ export const PDA_SEEDS = {
   graph: "graph",
   task: "task",
   escrow: "escrow",
+  deposit: "deposit",
   receipt: "receipt",
+  receiptCommitment: "receipt_commitment",
   dispute: "dispute",
+  creatorAllowlist: "creator_allowlist",
+  protocolConfig: "protocol_config",
+  graphTemplate: "graph_template",
 } as const;
 
+/**
+ * Encode a u64 as 8 little-endian bytes for use as a PDA seed.
+ */
+function u64Seed(value: bigint | number, label: string): Buffer {
+  let id: bigint;
+  if (typeof value === "number") {
+    if (!Number.isSafeInteger(value) || value < 0) {
+      throw new Error(`Invalid ${label} number: ${value}`);
+    }
+    id = BigInt(value);
+  } else {
+    if (value < 0n) throw new Error(`Invalid ${label} bigint: ${value.toString()}`);
+    id = value;
+  }
+
+  const le = Buffer.alloc(8);
+  le.writeBigUInt64LE(id);
+  return le;
+}
+
 /**
  * Derive the Graph PDA.
  *
- * Seeds: ["graph", authority]
+ * Seeds: ["graph", authority, graphIdLE]
+ * - graphIdLE is a u64 encoded little-endian.
+ *
+ * Why graphId in the seed:
+ * - lets a single authority own several independent graphs (per product line, per quarter, ...)
  *
  * Typical usage:
  * ```ts
- * const [graphPda] = findGraphPda(wallet.publicKey);
+ * const [graphPda] = findGraphPda(wallet.publicKey, 0);
  * ```
  */
 export function findGraphPda(
+  authority: PubkeyLike,
+  graphId: bigint | number,
+  programId: PublicKey = BOUNTYGRAPH_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(PDA_SEEDS.graph), authority.toBuffer(), u64Seed(graphId, "graphId")],
+    programId
+  );
+}
+
+/**
+ * Graph id assigned on-chain to graphs migrated from the v0 layout.
+ *
+ * v0 graphs were seeded as ["graph", authority] with no id; use `findLegacyGraphPda` for them.
+ */
+export const LEGACY_GRAPH_ID = 0xffff_ffff_ffff_ffffn;
+
+/**
+ * Derive a v0 Graph PDA.
+ *
+ * Seeds: ["graph", authority]
+ */
+export function findLegacyGraphPda(
   authority: PubkeyLike,
   programId: PublicKey = BOUNTYGRAPH_PROGRAM_ID
 ): [PublicKey, number] {
@@ -66,21 +118,8 @@ export function findTaskPda(
   taskId: bigint | number,
   programId: PublicKey = BOUNTYGRAPH_PROGRAM_ID
 ): [PublicKey, number] {
-  let id: bigint;
-  if (typeof taskId === "number") {
-    if (!Number.isSafeInteger(taskId) || taskId < 0) {
-      throw new Error(`Invalid taskId number: ${taskId}`);
-    }
-    id = BigInt(taskId);
-  } else {
-    if (taskId < 0n) throw new Error(`Invalid taskId bigint: ${taskId.toString()}`);
-    id = taskId;
-  }
-
-  const le = Buffer.alloc(8);
-  le.writeBigUInt64LE(id);
   return PublicKey.findProgramAddressSync(
-    [Buffer.from(PDA_SEEDS.task), graph.toBuffer(), le],
+    [Buffer.from(PDA_SEEDS.task), graph.toBuffer(), u64Seed(taskId, "taskId")],
     programId
   );
 }
@@ -120,6 +159,86 @@ export function findReceiptPda(
   );
 }
 
+/**
+ * Derive a milestone Receipt PDA.
+ *
+ * Seeds: ["receipt", task, agent, milestoneIndex (u8)]
+ *
+ * The extra index byte gives each milestone of a task its own receipt.
+ */
+export function findMilestoneReceiptPda(
+  task: PubkeyLike,
+  agent: PubkeyLike,
+  milestoneIndex: number,
+  programId: PublicKey = BOUNTYGRAPH_PROGRAM_ID
+): [PublicKey, number] {
+  if (!Number.isInteger(milestoneIndex) || milestoneIndex < 0 || milestoneIndex > 255) {
+    throw new Error(`Invalid milestone index: ${milestoneIndex}`);
+  }
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(PDA_SEEDS.receipt), task.toBuffer(), agent.toBuffer(), Buffer.from([milestoneIndex])],
+    programId
+  );
+}
+
+/**
+ * Derive the Receipt PDA for one completion of a repeatable task.
+ *
+ * Seeds: ["receipt", task, agent, completionIndex (u16 LE)]
+ *
+ * The index is the task's `completionCount` when the completion is submitted.
+ */
+export function findCompletionReceiptPda(
+  task: PubkeyLike,
+  agent: PubkeyLike,
+  completionIndex: number,
+  programId: PublicKey = BOUNTYGRAPH_PROGRAM_ID
+): [PublicKey, number] {
+  if (!Number.isInteger(completionIndex) || completionIndex < 0 || completionIndex > 0xffff) {
+    throw new Error(`Invalid completion index: ${completionIndex}`);
+  }
+  const index = Buffer.alloc(2);
+  index.writeUInt16LE(completionIndex);
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(PDA_SEEDS.receipt), task.toBuffer(), agent.toBuffer(), index],
+    programId
+  );
+}
+
+/**
+ * Derive the Deposit PDA recording what a funder other than the creator put into a task.
+ *
+ * Seeds: ["deposit", task, funder]
+ */
+export function findDepositPda(
+  task: PubkeyLike,
+  funder: PubkeyLike,
+  programId: PublicKey = BOUNTYGRAPH_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(PDA_SEEDS.deposit), task.toBuffer(), funder.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * Derive the ReceiptCommitment PDA.
+ *
+ * Seeds: ["receipt_commitment", task, agent]
+ *
+ * Closed by `reveal_receipt`, which creates the agent's ordinary Receipt PDA.
+ */
+export function findReceiptCommitmentPda(
+  task: PubkeyLike,
+  agent: PubkeyLike,
+  programId: PublicKey = BOUNTYGRAPH_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(PDA_SEEDS.receiptCommitment), task.toBuffer(), agent.toBuffer()],
+    programId
+  );
+}
+
 /**
  * Derive the Dispute PDA.
  *
@@ -139,3 +258,53 @@ export function findDisputePda(
     programId
   );
 }
+
+/**
+ * Derive the CreatorAllowlist PDA.
+ *
+ * Seeds: ["creator_allowlist", graph, creator]
+ *
+ * One entry per (graph, creator) pair; its existence admits the creator under the
+ * `Allowlist` creation policy.
+ */
+export function findCreatorAllowlistPda(
+  graph: PubkeyLike,
+  creator: PubkeyLike,
+  programId: PublicKey = BOUNTYGRAPH_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(PDA_SEEDS.creatorAllowlist), graph.toBuffer(), creator.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * Derive the ProtocolConfig PDA.
+ *
+ * Seeds: ["protocol_config"]
+ *
+ * A program-wide singleton holding the protocol fee and treasury.
+ */
+export function findProtocolConfigPda(
+  programId: PublicKey = BOUNTYGRAPH_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from(PDA_SEEDS.protocolConfig)], programId);
+}
+
+/**
+ * Derive the GraphTemplate PDA.
+ *
+ * Seeds: ["graph_template", authority, templateIdLE]
+ *
+ * Templates belong to their creator but can be instantiated into any graph.
+ */
+export function findGraphTemplatePda(
+  authority: PubkeyLike,
+  templateId: bigint | number,
+  programId: PublicKey = BOUNTYGRAPH_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(PDA_SEEDS.graphTemplate), authority.toBuffer(), u64Seed(templateId, "templateId")],
+    programId
+  );
+}
@@ -0,0 +1,169 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Ed25519Program, Keypair, PublicKey, SYSVAR_INSTRUCTIONS_PUBKEY } from '@solana/web3.js';
+import * as assert from 'assert';
+import { createHash } from 'crypto';
+import {
+  deriveGraphPda,
+  deriveTaskPda,
+  deriveReceiptPda,
+  airdrop,
+  taskParams,
+} from './helpers';
+
+describe('bountygraph oracle attestation', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Bountygraph as anchor.Program;
+  const authority = (provider.wallet as anchor.Wallet).payer;
+
+  const graphId = new anchor.BN(50);
+
+  const [graphPda] = deriveGraphPda(authority.publicKey, graphId);
+
+  before(async () => {
+    try {
+      await program.account.graph.fetch(graphPda);
+    } catch {
+      await program.methods
+        .initializeGraph({
+          graphId,
+          maxDependenciesPerTask: 10,
+          creationPolicy: { authorityOnly: {} },
+          creationDepositLamports: new anchor.BN(0),
+          guardian: null,
+        })
+        .accounts({
+          graph: graphPda,
+          authority: authority.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    }
+  });
+
+  const createTask = (taskId: number, verifiers: PublicKey[], verifierThreshold: number) =>
+    program.methods
+      .createTask(taskParams(new anchor.BN(taskId), { verifiers, verifierThreshold }))
+      .accounts({
+        graph: graphPda,
+        authority: authority.publicKey,
+        creator: authority.publicKey,
+        creatorAllowlist: null,
+        parent: null,
+        task: deriveTaskPda(graphPda, new anchor.BN(taskId))[0],
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+  const submit = (taskPda: PublicKey, agent: Keypair, workHash: number[]) =>
+    program.methods
+      .submitReceipt({ workHash, uri: 'ipfs://oracle-checked', contributors: [] })
+      .accounts({
+        graph: graphPda,
+        task: taskPda,
+        receipt: deriveReceiptPda(taskPda, agent.publicKey)[0],
+        agent: agent.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+
+  // The message a verifier signs: task || agent || work_hash.
+  const attestationMessage = (taskPda: PublicKey, agent: PublicKey, workHash: number[]) =>
+    Buffer.concat([taskPda.toBuffer(), agent.toBuffer(), Buffer.from(workHash)]);
+
+  const attest = (taskPda: PublicKey, agent: PublicKey, signatures: Array<{ oracle: Keypair; message: Buffer }>) =>
+    program.methods
+      .attestReceipt()
+      .accounts({
+        graph: graphPda,
+        task: taskPda,
+        receipt: deriveReceiptPda(taskPda, agent)[0],
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .preInstructions(
+        signatures.map(({ oracle, message }) =>
+          Ed25519Program.createInstructionWithPrivateKey({ privateKey: oracle.secretKey, message })
+        )
+      )
+      .rpc();
+
+  const expectMissingAttestation = async (attempt: Promise<string>, why: string) => {
+    try {
+      await attempt;
+      assert.fail(why);
+    } catch (err: any) {
+      assert.ok(err.message.includes('MissingAttestation'), 'Should fail with MissingAttestation');
+    }
+  };
+
+  it('completes a task only once its oracle signs the receipt', async () => {
+    const oracle = Keypair.generate();
+    const impostor = Keypair.generate();
+    const agent = Keypair.generate();
+    await airdrop(agent, 1);
+
+    await createTask(5001, [oracle.publicKey], 1);
+    const [taskPda] = deriveTaskPda(graphPda, new anchor.BN(5001));
+    const workHash = Array.from(createHash('sha256').update('report-5001').digest());
+
+    await submit(taskPda, agent, workHash);
+    let task: any = await program.account.task.fetch(taskPda);
+    assert.ok(task.status.open, 'receipt alone does not complete an attested task');
+
+    const message = attestationMessage(taskPda, agent.publicKey, workHash);
+    await expectMissingAttestation(attest(taskPda, agent.publicKey, []), 'Attestation requires a signature');
+    await expectMissingAttestation(
+      attest(taskPda, agent.publicKey, [{ oracle: impostor, message }]),
+      'Only task verifiers can attest'
+    );
+    await expectMissingAttestation(
+      attest(taskPda, agent.publicKey, [
+        { oracle, message: attestationMessage(taskPda, agent.publicKey, Array(32).fill(7)) },
+      ]),
+      'Signature must cover the receipt work hash'
+    );
+
+    await attest(taskPda, agent.publicKey, [{ oracle, message }]);
+    task = await program.account.task.fetch(taskPda);
+    assert.ok(task.status.completed);
+    assert.equal(task.completedBy.toString(), agent.publicKey.toString());
+  });
+
+  it('requires a threshold of distinct verifiers', async () => {
+    const oracles = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+    const agent = Keypair.generate();
+    await airdrop(agent, 1);
+
+    await createTask(5002, oracles.map((o) => o.publicKey), 2);
+    const [taskPda] = deriveTaskPda(graphPda, new anchor.BN(5002));
+    const workHash = Array.from(createHash('sha256').update('report-5002').digest());
+    await submit(taskPda, agent, workHash);
+
+    const message = attestationMessage(taskPda, agent.publicKey, workHash);
+    await expectMissingAttestation(
+      attest(taskPda, agent.publicKey, [
+        { oracle: oracles[0], message },
+        { oracle: oracles[0], message },
+      ]),
+      'The same verifier counts once'
+    );
+
+    await attest(taskPda, agent.publicKey, [
+      { oracle: oracles[0], message },
+      { oracle: oracles[2], message },
+    ]);
+    const task: any = await program.account.task.fetch(taskPda);
+    assert.ok(task.status.completed);
+  });
+
+  it('rejects unreachable thresholds', async () => {
+    try {
+      await createTask(5003, [Keypair.generate().publicKey], 2);
+      assert.fail('Threshold cannot exceed the number of verifiers');
+    } catch (err: any) {
+      assert.ok(err.message.includes('InvalidVerifiers'), 'Should fail with InvalidVerifiers');
+    }
+  });
+});
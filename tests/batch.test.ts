@@ -0,0 +1,130 @@
+import * as anchor from '@coral-xyz/anchor';
+import { ComputeBudgetProgram, PublicKey } from '@solana/web3.js';
+import * as assert from 'assert';
+import { deriveTaskPda, ensureGraph, taskParams } from './helpers';
+
+describe('bountygraph batch task creation', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Bountygraph as anchor.Program;
+  const authority = (provider.wallet as anchor.Wallet).payer;
+
+  let graphPda: PublicKey;
+
+  // The compute-unit limit requested for every batch, the most a transaction may ask for.
+  const CU_LIMIT = 1_400_000;
+
+  const chainParams = (taskId: number, dependencies: number[] = []) =>
+    taskParams(taskId, { dependencies: dependencies.map((id) => new anchor.BN(id)) });
+
+  const taskPda = (taskId: number) => deriveTaskPda(graphPda, new anchor.BN(taskId))[0];
+
+  // Remaining accounts: the new task PDAs in order, then each existing dependency once.
+  const batchBuilder = (params: ReturnType<typeof chainParams>[], existing: number[] = []) =>
+    program.methods
+      .createTasksBatch(params)
+      .accounts({
+        graph: graphPda,
+        authority: authority.publicKey,
+        creator: authority.publicKey,
+        creatorAllowlist: null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts([
+        ...params.map((p) => ({ pubkey: taskPda(p.taskId.toNumber()), isSigner: false, isWritable: true })),
+        ...existing.map((id) => ({ pubkey: taskPda(id), isSigner: false, isWritable: false })),
+      ])
+      .preInstructions([ComputeBudgetProgram.setComputeUnitLimit({ units: CU_LIMIT })]);
+
+  const createBatch = (params: ReturnType<typeof chainParams>[], existing: number[] = []) =>
+    batchBuilder(params, existing).rpc();
+
+  const computeUnits = async (signature: string): Promise<number> => {
+    const tx = await provider.connection.getTransaction(signature, {
+      commitment: 'confirmed',
+      maxSupportedTransactionVersion: 0,
+    });
+    return tx!.meta!.computeUnitsConsumed!;
+  };
+
+  before(async () => {
+    graphPda = await ensureGraph(44);
+  });
+
+  it('creates a dependency chain in one transaction', async () => {
+    await createBatch([chainParams(4400)]);
+
+    const graphBefore: any = await program.account.graph.fetch(graphPda);
+    // 4401 depends on an existing task; each later task depends on the one before it.
+    await createBatch(
+      [chainParams(4401, [4400]), chainParams(4402, [4401]), chainParams(4403, [4400, 4402])],
+      [4400]
+    );
+
+    const last: any = await program.account.task.fetch(taskPda(4403));
+    assert.deepEqual(
+      last.dependencies.map((d: anchor.BN) => d.toNumber()),
+      [4400, 4402]
+    );
+    assert.ok(last.status.open, 'Batch tasks should be open');
+    const graphAfter: any = await program.account.graph.fetch(graphPda);
+    assert.equal(graphAfter.taskCount.toNumber() - graphBefore.taskCount.toNumber(), 3);
+  });
+
+  it('rejects dependencies on later tasks in the batch', async () => {
+    try {
+      await createBatch([chainParams(4404, [4405]), chainParams(4405)]);
+      assert.fail('Batch should be in topological order');
+    } catch (err: any) {
+      assert.ok(err.message.includes('InvalidBatch'), 'Should fail with InvalidBatch');
+    }
+  });
+
+  it('fits a full batch in one transaction', async () => {
+    const base = 4450;
+    const params = Array.from({ length: 5 }, (_, k) => chainParams(base + k, k === 0 ? [] : [base + k - 1]));
+    const tx = await batchBuilder(params).transaction();
+    tx.feePayer = authority.publicKey;
+    tx.recentBlockhash = (await provider.connection.getLatestBlockhash()).blockhash;
+    tx.sign(authority);
+    assert.ok(tx.serialize().length <= 1232, `5-task batch is ${tx.serialize().length} bytes`);
+
+    await createBatch(params);
+    const last: any = await program.account.task.fetch(taskPda(base + 4));
+    assert.ok(last.status.open, 'Batch tasks should be open');
+  });
+
+  it('creates tasks whose address was pre-funded', async () => {
+    // Dust sent to a task PDA ahead of time must not block the batch.
+    const transfer = new anchor.web3.Transaction().add(
+      anchor.web3.SystemProgram.transfer({
+        fromPubkey: authority.publicKey,
+        toPubkey: taskPda(4461),
+        lamports: 1_000,
+      })
+    );
+    await provider.sendAndConfirm(transfer);
+
+    await createBatch([chainParams(4460), chainParams(4461, [4460])]);
+    const task: any = await program.account.task.fetch(taskPda(4461));
+    assert.ok(task.status.open, 'Pre-funded task should be created');
+  });
+
+  it('measures compute units per batch size', async () => {
+    const sizes = [1, 3, 5];
+    const costs: number[] = [];
+    for (const [i, size] of sizes.entries()) {
+      const base = 4410 + 10 * i;
+      // A chain, so every task past the first validates one in-batch dependency.
+      const params = Array.from({ length: size }, (_, k) =>
+        chainParams(base + k, k === 0 ? [] : [base + k - 1])
+      );
+      const units = await computeUnits(await createBatch(params));
+      assert.ok(units < CU_LIMIT, `A ${size}-task batch used ${units} CU, over the ${CU_LIMIT} limit`);
+      costs.push(units);
+    }
+
+    assert.ok(costs[1] > costs[0] && costs[2] > costs[1], 'Cost should grow with batch size');
+  });
+});
@@ -25,12 +25,19 @@ function toBigIntArray(xs) {
 describe("bountygraph SDK helpers", () => {
   it("derives graph PDA deterministically", () => {
     const authority = Keypair.generate().publicKey;
-    const [a1, b1] = findGraphPda(authority, PROGRAM_ID);
-    const [a2, b2] = findGraphPda(authority, PROGRAM_ID);
+    const [a1, b1] = findGraphPda(authority, 0, PROGRAM_ID);
+    const [a2, b2] = findGraphPda(authority, 0n, PROGRAM_ID);
     expect(a1.toBase58()).to.eq(a2.toBase58());
     expect(b1).to.eq(b2);
   });
 
+  it("derives distinct graph PDAs per graphId for the same authority", () => {
+    const authority = Keypair.generate().publicKey;
+    const [g0] = findGraphPda(authority, 0n, PROGRAM_ID);
+    const [g1] = findGraphPda(authority, 1n, PROGRAM_ID);
+    expect(g0.toBase58()).to.not.eq(g1.toBase58());
+  });
+
   it("derives task PDA deterministically for bigint and number", () => {
     const graph = Keypair.generate().publicKey;
     const id = 123n;
@@ -0,0 +1,372 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import * as assert from 'assert';
+import {
+  deriveTaskPda,
+  deriveEscrowPda,
+  deriveReceiptPda,
+  deriveMilestoneReceiptPda,
+  deriveCommitmentPda,
+  deriveDisputePda,
+  deriveProtocolConfigPda,
+  receiptCommitment,
+  airdrop,
+  ensureGraph,
+  ensureProtocolConfig,
+  taskParams,
+} from './helpers';
+
+describe('bountygraph task cancellation and closing', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Bountygraph as anchor.Program;
+  const authority = (provider.wallet as anchor.Wallet).payer;
+
+  let graphPda: PublicKey;
+  const creator = Keypair.generate();
+  const worker = Keypair.generate();
+
+  let treasury: PublicKey;
+  const [protocolConfigPda] = deriveProtocolConfigPda();
+
+  before(async () => {
+    graphPda = await ensureGraph(33);
+    await airdrop(creator, 2);
+    await airdrop(worker, 1);
+    treasury = await ensureProtocolConfig();
+
+    // No retention period, so settled accounts can be closed straight away.
+    await program.methods
+      .updateGraphConfig({
+        maxDependenciesPerTask: null,
+        minRewardLamports: null,
+        maxRewardLamports: null,
+        closeRetentionSlots: new anchor.BN(0),
+        graphFeeBps: null,
+        requireDependentApproval: null,
+        dependencyDisputePolicy: null,
+      })
+      .accounts({ graph: graphPda, authority: authority.publicKey })
+      .rpc();
+  });
+
+  const createFundedTask = async (taskId: number, overrides: Record<string, any> = {}) => {
+    const [taskPda] = deriveTaskPda(graphPda, taskId);
+    const reward = overrides.rewardLamports ?? new anchor.BN(100_000);
+    await program.methods
+      .createTask(taskParams(taskId, overrides))
+      .accounts({
+        graph: graphPda,
+        authority: authority.publicKey,
+        creator: creator.publicKey,
+        creatorAllowlist: null,
+        parent: null,
+        task: taskPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([authority, creator])
+      .rpc();
+    await program.methods
+      .fundTask(reward)
+      .accounts({
+        graph: graphPda,
+        task: taskPda,
+        escrow: deriveEscrowPda(taskPda)[0],
+        deposit: null,
+        funder: creator.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([creator])
+      .rpc();
+    return taskPda;
+  };
+
+  const cancel = (taskPda: PublicKey) =>
+    program.methods
+      .cancelTask()
+      .accounts({
+        task: taskPda,
+        escrow: deriveEscrowPda(taskPda)[0],
+        parent: null,
+        creator: creator.publicKey,
+      })
+      .signers([creator])
+      .rpc();
+
+  const expectPendingSubmissions = async (taskPda: PublicKey, why: string) => {
+    try {
+      await cancel(taskPda);
+      assert.fail(why);
+    } catch (err: any) {
+      assert.ok(err.message.includes('PendingSubmissions'), 'Should fail with PendingSubmissions');
+    }
+  };
+
+  const entry = {
+    workHash: Array.from(Buffer.alloc(32, 33)),
+    uri: 'ipfs://entry',
+    salt: Array.from(Buffer.alloc(32, 7)),
+  };
+
+  const submit = (taskPda: PublicKey) =>
+    program.methods
+      .submitReceipt({ workHash: entry.workHash, uri: entry.uri, contributors: [] })
+      .accounts({
+        graph: graphPda,
+        task: taskPda,
+        receipt: deriveReceiptPda(taskPda, worker.publicKey)[0],
+        agent: worker.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([worker])
+      .rpc();
+
+  const closeReceipt = (taskPda: PublicKey, receipt: PublicKey) =>
+    program.methods
+      .closeReceipt()
+      .accounts({ graph: graphPda, task: taskPda, receipt, agent: worker.publicKey })
+      .signers([worker])
+      .rpc();
+
+  const commit = (taskPda: PublicKey) =>
+    program.methods
+      .commitReceipt(receiptCommitment(entry, worker.publicKey))
+      .accounts({
+        graph: graphPda,
+        task: taskPda,
+        commitment: deriveCommitmentPda(taskPda, worker.publicKey)[0],
+        agent: worker.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([worker])
+      .rpc();
+
+  it('refunds the escrow of an untouched task to its creator', async () => {
+    const taskPda = await createFundedTask(3301);
+    const [escrowPda] = deriveEscrowPda(taskPda);
+    const escrowBalance = await provider.connection.getBalance(escrowPda, 'confirmed');
+    const creatorBefore = await provider.connection.getBalance(creator.publicKey, 'confirmed');
+
+    await cancel(taskPda);
+
+    const task: any = await program.account.task.fetch(taskPda);
+    assert.ok(task.status.cancelled);
+    assert.ok(task.settledAtSlot.toNumber() > 0);
+    const creatorAfter = await provider.connection.getBalance(creator.publicKey, 'confirmed');
+    assert.equal(creatorAfter - creatorBefore, escrowBalance);
+  });
+
+  it('refuses while a competition commitment can still be revealed', async () => {
+    const deadline = (await provider.connection.getSlot('confirmed')) + 1_000;
+    const taskPda = await createFundedTask(3302, { competitionDeadlineSlot: new anchor.BN(deadline) });
+
+    await commit(taskPda);
+    await expectPendingSubmissions(taskPda, 'A pending commitment should block cancellation');
+  });
+
+  it('refuses once a competition has entries', async () => {
+    const deadline = (await provider.connection.getSlot('confirmed')) + 1_000;
+    const taskPda = await createFundedTask(3303, { competitionDeadlineSlot: new anchor.BN(deadline) });
+
+    await commit(taskPda);
+    await program.methods
+      .revealReceipt(entry)
+      .accounts({
+        graph: graphPda,
+        task: taskPda,
+        commitment: deriveCommitmentPda(taskPda, worker.publicKey)[0],
+        receipt: deriveReceiptPda(taskPda, worker.publicKey)[0],
+        agent: worker.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([worker])
+      .rpc();
+
+    const task: any = await program.account.task.fetch(taskPda);
+    assert.equal(task.submissionCount, 1);
+    await expectPendingSubmissions(taskPda, 'A competition entry should block cancellation');
+  });
+
+  it('refuses while a receipt awaits attestation, until the creator rejects it', async () => {
+    const oracle = Keypair.generate();
+    const taskPda = await createFundedTask(3304, { verifiers: [oracle.publicKey], verifierThreshold: 1 });
+
+    await submit(taskPda);
+    await expectPendingSubmissions(taskPda, 'An unattested receipt should block cancellation');
+
+    const [receiptPda] = deriveReceiptPda(taskPda, worker.publicKey);
+    await program.methods
+      .rejectReceipt()
+      .accounts({ task: taskPda, receipt: receiptPda, creator: creator.publicKey, agent: worker.publicKey })
+      .signers([creator])
+      .rpc();
+    assert.equal(await provider.connection.getAccountInfo(receiptPda), null, 'the receipt is closed to the agent');
+    const task: any = await program.account.task.fetch(taskPda);
+    assert.equal(task.submissionCount, 0);
+
+    await cancel(taskPda);
+    const cancelled: any = await program.account.task.fetch(taskPda);
+    assert.ok(cancelled.status.cancelled);
+  });
+
+  it('requires the escrow to cancel a funded task', async () => {
+    const taskPda = await createFundedTask(3309);
+
+    // Without the escrow, the cancelled task would leave its funding locked for good.
+    try {
+      await program.methods
+        .cancelTask()
+        .accounts({ task: taskPda, escrow: null, parent: null, creator: creator.publicKey })
+        .signers([creator])
+        .rpc();
+      assert.fail('A funded task cannot be cancelled without its escrow');
+    } catch (err: any) {
+      assert.ok(err.message.includes('EscrowRequired'), 'Should fail with EscrowRequired');
+    }
+
+    await cancel(taskPda);
+    assert.equal(await provider.connection.getBalance(deriveEscrowPda(taskPda)[0], 'confirmed'), 0);
+  });
+
+  it('closes a cancelled task to a tombstone', async () => {
+    const taskPda = await createFundedTask(3305);
+    await cancel(taskPda);
+
+    const sizeBefore = (await provider.connection.getAccountInfo(taskPda))!.data.length;
+    const creatorBefore = await provider.connection.getBalance(creator.publicKey, 'confirmed');
+    await program.methods
+      .closeTask()
+      .accounts({ graph: graphPda, task: taskPda, creator: creator.publicKey })
+      .signers([creator])
+      .rpc();
+
+    const tombstone: any = await program.account.taskTombstone.fetch(taskPda);
+    assert.equal(tombstone.taskId.toNumber(), 3305);
+    assert.equal(tombstone.completed, false);
+    assert.ok((await provider.connection.getAccountInfo(taskPda))!.data.length < sizeBefore);
+    const creatorAfter = await provider.connection.getBalance(creator.publicKey, 'confirmed');
+    assert.ok(creatorAfter > creatorBefore, 'the rent difference returns to the creator');
+  });
+
+  it('closes the receipt and dispute of a resolved task', async () => {
+    const taskPda = await createFundedTask(3306);
+    const [receiptPda] = deriveReceiptPda(taskPda, worker.publicKey);
+    const [disputePda] = deriveDisputePda(taskPda, worker.publicKey);
+    await submit(taskPda);
+
+    await program.methods
+      .disputeTask({ reason: 'Creator rejected finished work' })
+      .accounts({
+        task: taskPda,
+        dispute: disputePda,
+        initiator: worker.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([worker])
+      .rpc();
+
+    try {
+      await closeReceipt(taskPda, receiptPda);
+      assert.fail('A disputed task is not settled yet');
+    } catch (err: any) {
+      assert.ok(err.message.includes('TaskNotTerminal'), 'Should fail with TaskNotTerminal');
+    }
+
+    await program.methods
+      .resolveDispute({ creatorPct: 50, workerPct: 50 })
+      .accounts({
+        graph: graphPda,
+        authority: authority.publicKey,
+        task: taskPda,
+        dispute: disputePda,
+        escrow: deriveEscrowPda(taskPda)[0],
+        protocolConfig: protocolConfigPda,
+        treasury,
+        creator: creator.publicKey,
+        worker: worker.publicKey,
+        receipt: null,
+      })
+      .rpc();
+
+    await closeReceipt(taskPda, receiptPda);
+    await program.methods
+      .closeDispute()
+      .accounts({ graph: graphPda, task: taskPda, dispute: disputePda, initiator: worker.publicKey })
+      .signers([worker])
+      .rpc();
+
+    assert.equal(await provider.connection.getAccountInfo(receiptPda), null, 'receipt is closed');
+    assert.equal(await provider.connection.getAccountInfo(disputePda), null, 'dispute is closed');
+  });
+
+  it('closes milestone receipts by their index', async () => {
+    const taskPda = await createFundedTask(3307, {
+      rewardLamports: new anchor.BN(100_000),
+      milestones: [new anchor.BN(40_000), new anchor.BN(60_000)],
+    });
+
+    for (const index of [0, 1]) {
+      await program.methods
+        .submitMilestone({ index, workHash: entry.workHash, uri: `ipfs://milestone-${index}` })
+        .accounts({
+          graph: graphPda,
+          task: taskPda,
+          receipt: deriveMilestoneReceiptPda(taskPda, worker.publicKey, index)[0],
+          agent: worker.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([worker])
+        .rpc();
+      await program.methods
+        .approveMilestone(index)
+        .accounts({
+          graph: graphPda,
+          task: taskPda,
+          escrow: deriveEscrowPda(taskPda)[0],
+          protocolConfig: protocolConfigPda,
+          treasury,
+          graphAuthority: authority.publicKey,
+          creator: creator.publicKey,
+          worker: worker.publicKey,
+        })
+        .signers([creator])
+        .rpc();
+    }
+
+    for (const index of [0, 1]) {
+      const [receiptPda] = deriveMilestoneReceiptPda(taskPda, worker.publicKey, index);
+      await closeReceipt(taskPda, receiptPda);
+      assert.equal(await provider.connection.getAccountInfo(receiptPda), null, `milestone ${index} receipt is closed`);
+    }
+  });
+
+  it('closes a commitment once it can no longer be revealed', async () => {
+    const deadline = (await provider.connection.getSlot('confirmed')) + 1_000;
+    const taskPda = await createFundedTask(3308, { competitionDeadlineSlot: new anchor.BN(deadline) });
+    const [commitmentPda] = deriveCommitmentPda(taskPda, worker.publicKey);
+    await commit(taskPda);
+
+    const closeCommitment = () =>
+      program.methods
+        .closeCommitment()
+        .accounts({ task: taskPda, commitment: commitmentPda, agent: worker.publicKey })
+        .signers([worker])
+        .rpc();
+
+    try {
+      await closeCommitment();
+      assert.fail('The commitment can still be revealed');
+    } catch (err: any) {
+      assert.ok(err.message.includes('RevealWindowOpen'), 'Should fail with RevealWindowOpen');
+    }
+
+    const { committedAtSlot } = (await program.account.receiptCommitment.fetch(commitmentPda)) as any;
+    while ((await provider.connection.getSlot('confirmed')) <= committedAtSlot.toNumber() + 150) {
+      await new Promise((resolve) => setTimeout(resolve, 400));
+    }
+
+    await closeCommitment();
+    assert.equal(await provider.connection.getAccountInfo(commitmentPda), null, 'commitment is closed');
+  });
+});
@@ -0,0 +1,158 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import * as assert from 'assert';
+import { deriveTaskPda, deriveReceiptPda, airdrop, ensureGraph, taskParams } from './helpers';
+
+describe('bountygraph task claims and assignment', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Bountygraph as anchor.Program;
+  const authority = (provider.wallet as anchor.Wallet).payer;
+
+  let graphPda: PublicKey;
+  const alice = Keypair.generate();
+  const bob = Keypair.generate();
+
+  before(async () => {
+    graphPda = await ensureGraph(32);
+    await airdrop(alice, 1);
+    await airdrop(bob, 1);
+  });
+
+  const createTask = async (taskId: number, claimDurationSlots: number) => {
+    const [taskPda] = deriveTaskPda(graphPda, taskId);
+    await program.methods
+      .createTask(taskParams(taskId, { claimDurationSlots: new anchor.BN(claimDurationSlots) }))
+      .accounts({
+        graph: graphPda,
+        authority: authority.publicKey,
+        creator: authority.publicKey,
+        creatorAllowlist: null,
+        parent: null,
+        task: taskPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    return taskPda;
+  };
+
+  const claim = (taskPda: PublicKey, agent: Keypair) =>
+    program.methods.claimTask().accounts({ task: taskPda, agent: agent.publicKey }).signers([agent]).rpc();
+
+  const unclaim = (taskPda: PublicKey, agent: Keypair) =>
+    program.methods.unclaimTask().accounts({ task: taskPda, agent: agent.publicKey }).signers([agent]).rpc();
+
+  const assign = (taskPda: PublicKey, worker: PublicKey | null, creator: Keypair = authority) =>
+    program.methods
+      .assignTask(worker)
+      .accounts({ task: taskPda, creator: creator.publicKey })
+      .signers(creator === authority ? [] : [creator])
+      .rpc();
+
+  const submit = (taskPda: PublicKey, agent: Keypair) =>
+    program.methods
+      .submitReceipt({ workHash: Array.from(Buffer.alloc(32, 32)), uri: 'ipfs://claimed-work', contributors: [] })
+      .accounts({
+        graph: graphPda,
+        task: taskPda,
+        receipt: deriveReceiptPda(taskPda, agent.publicKey)[0],
+        agent: agent.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+
+  const expectError = async (call: Promise<unknown>, error: string, why: string) => {
+    try {
+      await call;
+      assert.fail(why);
+    } catch (err: any) {
+      assert.ok(err.message.includes(error), `Should fail with ${error}`);
+    }
+  };
+
+  const waitForSlot = async (slot: number) => {
+    while ((await provider.connection.getSlot('confirmed')) <= slot) {
+      await new Promise((resolve) => setTimeout(resolve, 400));
+    }
+  };
+
+  it('reserves a claim-mode task for the claimant', async () => {
+    const taskPda = await createTask(3201, 1_000);
+
+    await claim(taskPda, alice);
+    const task: any = await program.account.task.fetch(taskPda);
+    assert.ok(task.assignee.equals(alice.publicKey));
+    assert.ok(task.claimExpiresAtSlot.toNumber() > 0);
+
+    await expectError(claim(taskPda, bob), 'TaskAlreadyClaimed', 'An active reservation is exclusive');
+    await expectError(submit(taskPda, bob), 'NotAssignee', 'Only the claimant may submit');
+
+    // The claimant can renew their own reservation.
+    await claim(taskPda, alice);
+    await submit(taskPda, alice);
+    const completed: any = await program.account.task.fetch(taskPda);
+    assert.ok(completed.status.completed);
+    assert.ok(completed.completedBy.equals(alice.publicKey));
+  });
+
+  it('lets the claimant give a task back', async () => {
+    const taskPda = await createTask(3202, 1_000);
+    await claim(taskPda, alice);
+
+    await expectError(unclaim(taskPda, bob), 'NotAssignee', 'Only the claimant can unclaim');
+
+    await unclaim(taskPda, alice);
+    const task: any = await program.account.task.fetch(taskPda);
+    assert.equal(task.assignee, null);
+    assert.equal(task.claimExpiresAtSlot.toNumber(), 0);
+
+    await claim(taskPda, bob);
+    await submit(taskPda, bob);
+  });
+
+  it('makes lapsed reservations claimable again', async () => {
+    const taskPda = await createTask(3203, 2);
+    await claim(taskPda, alice);
+
+    const task: any = await program.account.task.fetch(taskPda);
+    await waitForSlot(task.claimExpiresAtSlot.toNumber());
+
+    await claim(taskPda, bob);
+    const reclaimed: any = await program.account.task.fetch(taskPda);
+    assert.ok(reclaimed.assignee.equals(bob.publicKey));
+
+    // Claim-mode tasks only take work from the latest claimant.
+    await expectError(submit(taskPda, alice), 'NotAssignee', 'A lapsed claimant can no longer submit');
+  });
+
+  it('lets the creator assign and unassign a worker directly', async () => {
+    const taskPda = await createTask(3204, 0);
+
+    await expectError(claim(taskPda, alice), 'AssignmentDisabled', 'Only claim-mode tasks can be claimed');
+    await expectError(
+      assign(taskPda, alice.publicKey, bob),
+      'ConstraintHasOne',
+      'Only the creator can assign'
+    );
+
+    await assign(taskPda, alice.publicKey);
+    const task: any = await program.account.task.fetch(taskPda);
+    assert.ok(task.assignee.equals(alice.publicKey));
+    assert.equal(task.claimExpiresAtSlot.toNumber(), 0, 'creator assignments do not lapse');
+    await expectError(submit(taskPda, bob), 'NotAssignee', 'An assignment is exclusive');
+
+    await assign(taskPda, null);
+    await submit(taskPda, bob);
+  });
+
+  it('overrides an active reservation with a creator assignment', async () => {
+    const taskPda = await createTask(3205, 1_000);
+    await claim(taskPda, alice);
+
+    await assign(taskPda, bob.publicKey);
+    await expectError(submit(taskPda, alice), 'NotAssignee', 'The assignment replaces the claim');
+    await submit(taskPda, bob);
+  });
+});
@@ -0,0 +1,199 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import * as assert from 'assert';
+import { createHash } from 'crypto';
+import {
+  deriveGraphPda,
+  deriveTaskPda,
+  deriveReceiptPda,
+  deriveCommitmentPda,
+  receiptCommitment,
+  airdrop,
+  taskParams,
+} from './helpers';
+
+describe('bountygraph commit-reveal receipts', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Bountygraph as anchor.Program;
+  const authority = (provider.wallet as anchor.Wallet).payer;
+
+  const graphId = new anchor.BN(49);
+
+  const [graphPda] = deriveGraphPda(authority.publicKey, graphId);
+
+  before(async () => {
+    try {
+      await program.account.graph.fetch(graphPda);
+    } catch {
+      await program.methods
+        .initializeGraph({
+          graphId,
+          maxDependenciesPerTask: 10,
+          creationPolicy: { authorityOnly: {} },
+          creationDepositLamports: new anchor.BN(0),
+          guardian: null,
+        })
+        .accounts({
+          graph: graphPda,
+          authority: authority.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    }
+  });
+
+  const createTask = async (taskId: number, competitionDeadlineSlot = 0) => {
+    const [taskPda] = deriveTaskPda(graphPda, new anchor.BN(taskId));
+    await program.methods
+      .createTask(taskParams(new anchor.BN(taskId), {
+        competitionDeadlineSlot: new anchor.BN(competitionDeadlineSlot),
+      }))
+      .accounts({
+        graph: graphPda,
+        authority: authority.publicKey,
+        creator: authority.publicKey,
+        creatorAllowlist: null,
+        parent: null,
+        task: taskPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    return taskPda;
+  };
+
+  const createCompetition = async (taskId: number) =>
+    createTask(taskId, (await provider.connection.getSlot('confirmed')) + 1_000);
+
+  const work = (label: string) => ({
+    workHash: Array.from(createHash('sha256').update(label).digest()),
+    uri: `ipfs://${label}`,
+    salt: Array.from(Keypair.generate().publicKey.toBuffer()),
+  });
+
+  const commit = (taskPda: PublicKey, agent: Keypair, w: ReturnType<typeof work>) =>
+    program.methods
+      .commitReceipt(receiptCommitment(w, agent.publicKey))
+      .accounts({
+        graph: graphPda,
+        task: taskPda,
+        commitment: deriveCommitmentPda(taskPda, agent.publicKey)[0],
+        agent: agent.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+
+  const reveal = (taskPda: PublicKey, agent: Keypair, w: ReturnType<typeof work>) =>
+    program.methods
+      .revealReceipt(w)
+      .accounts({
+        graph: graphPda,
+        task: taskPda,
+        commitment: deriveCommitmentPda(taskPda, agent.publicKey)[0],
+        receipt: deriveReceiptPda(taskPda, agent.publicKey)[0],
+        agent: agent.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+
+  const submit = (taskPda: PublicKey, agent: Keypair, w: ReturnType<typeof work>) =>
+    program.methods
+      .submitReceipt({ workHash: w.workHash, uri: w.uri, contributors: [] })
+      .accounts({
+        graph: graphPda,
+        task: taskPda,
+        receipt: deriveReceiptPda(taskPda, agent.publicKey)[0],
+        agent: agent.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+
+  it('verifies the reveal and dates the entry at its commit slot', async () => {
+    const alice = Keypair.generate();
+    await airdrop(alice, 1);
+    const taskPda = await createCompetition(4901);
+
+    const w = work('alice-4901');
+    await commit(taskPda, alice, w);
+    const { committedAtSlot } = (await program.account.receiptCommitment.fetch(
+      deriveCommitmentPda(taskPda, alice.publicKey)[0]
+    )) as any;
+
+    try {
+      await reveal(taskPda, alice, { ...w, uri: 'ipfs://something-else' });
+      assert.fail('Reveal must match the commitment');
+    } catch (err: any) {
+      assert.ok(err.message.includes('CommitmentMismatch'), 'Should fail with CommitmentMismatch');
+    }
+
+    await reveal(taskPda, alice, w);
+    const task: any = await program.account.task.fetch(taskPda);
+    assert.ok(task.status.open, 'competition stays open until winners are selected');
+    assert.equal(task.submissionCount, 1);
+    const receipt: any = await program.account.receipt.fetch(deriveReceiptPda(taskPda, alice.publicKey)[0]);
+    assert.equal(receipt.uri, w.uri);
+    assert.equal(receipt.submittedAtSlot.toString(), committedAtSlot.toString(), 'receipt ranks by its commit slot');
+    assert.equal(
+      await provider.connection.getAccountInfo(deriveCommitmentPda(taskPda, alice.publicKey)[0]),
+      null,
+      'commitment is closed on reveal'
+    );
+  });
+
+  it('ranks competition entries by commit slot, not reveal order', async () => {
+    const alice = Keypair.generate();
+    const bob = Keypair.generate();
+    await airdrop(alice, 1);
+    await airdrop(bob, 1);
+    const taskPda = await createCompetition(4902);
+
+    const aliceWork = work('alice-4902');
+    const bobWork = work('bob-4902');
+    await commit(taskPda, alice, aliceWork);
+    await commit(taskPda, bob, bobWork);
+    // Bob reveals first, but Alice committed first.
+    await reveal(taskPda, bob, bobWork);
+    await reveal(taskPda, alice, aliceWork);
+
+    const aliceReceipt: any = await program.account.receipt.fetch(deriveReceiptPda(taskPda, alice.publicKey)[0]);
+    const bobReceipt: any = await program.account.receipt.fetch(deriveReceiptPda(taskPda, bob.publicKey)[0]);
+    assert.ok(aliceReceipt.submittedAtSlot.lt(bobReceipt.submittedAtSlot), 'earlier commit ranks first');
+
+    const task: any = await program.account.task.fetch(taskPda);
+    assert.equal(task.submissionCount, 2);
+  });
+
+  it('rejects plain submissions on competitions', async () => {
+    const mallory = Keypair.generate();
+    await airdrop(mallory, 1);
+    const taskPda = await createCompetition(4903);
+
+    try {
+      await submit(taskPda, mallory, work('mallory-4903'));
+      assert.fail('Competition entries must be committed first');
+    } catch (err: any) {
+      assert.ok(err.message.includes('CommitRevealRequired'), 'Should fail with CommitRevealRequired');
+    }
+  });
+
+  it('rejects commitments on regular tasks', async () => {
+    const alice = Keypair.generate();
+    await airdrop(alice, 1);
+    const taskPda = await createTask(4904);
+
+    try {
+      await commit(taskPda, alice, work('alice-4904'));
+      assert.fail('Only competitions take commitments');
+    } catch (err: any) {
+      assert.ok(err.message.includes('NotACompetition'), 'Should fail with NotACompetition');
+    }
+
+    await submit(taskPda, alice, work('alice-4904'));
+    const task: any = await program.account.task.fetch(taskPda);
+    assert.ok(task.status.completed);
+  });
+});
@@ -0,0 +1,241 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair } from '@solana/web3.js';
+import * as assert from 'assert';
+import {
+  deriveGraphPda,
+  deriveTaskPda,
+  deriveEscrowPda,
+  deriveReceiptPda,
+  deriveCommitmentPda,
+  deriveDisputePda,
+  deriveProtocolConfigPda,
+  airdrop,
+  ensureProtocolConfig,
+  receiptCommitment,
+  taskParams,
+} from './helpers';
+
+describe('bountygraph competitive bounties', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Bountygraph as anchor.Program;
+  const authority = (provider.wallet as anchor.Wallet).payer;
+
+  const graphId = new anchor.BN(0);
+
+  const [protocolConfigPda] = deriveProtocolConfigPda();
+
+  const waitForSlot = async (slot: number) => {
+    while ((await provider.connection.getSlot('confirmed')) < slot) {
+      await new Promise((resolve) => setTimeout(resolve, 200));
+    }
+  };
+
+  const [graphPda] = deriveGraphPda(authority.publicKey, graphId);
+
+  before(async () => {
+    try {
+      await program.account.graph.fetch(graphPda);
+    } catch {
+      await program.methods
+        .initializeGraph({
+          graphId,
+          maxDependenciesPerTask: 10,
+          creationPolicy: { authorityOnly: {} },
+          creationDepositLamports: new anchor.BN(0),
+          guardian: null,
+        })
+        .accounts({
+          graph: graphPda,
+          authority: authority.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    }
+  });
+
+  it('collects several receipts and splits the pool between selected winners', async () => {
+    const creator = Keypair.generate();
+    const workers = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+    await airdrop(creator, 2);
+    for (const w of workers) await airdrop(w, 1);
+
+    const taskId = new anchor.BN(3301);
+    const [taskPda] = deriveTaskPda(graphPda, taskId);
+    const [escrowPda] = deriveEscrowPda(taskPda);
+    const deadline = (await provider.connection.getSlot('confirmed')) + 40;
+
+    await program.methods
+      .createTask(taskParams(taskId, {
+        rewardLamports: new anchor.BN(1_000_000),
+        competitionDeadlineSlot: new anchor.BN(deadline),
+      }))
+      .accounts({
+        graph: graphPda,
+        authority: authority.publicKey,
+        creator: creator.publicKey,
+        creatorAllowlist: null,
+        parent: null,
+        task: taskPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([authority, creator])
+      .rpc();
+
+    await program.methods
+      .fundTask(new anchor.BN(1_000_000))
+      .accounts({
+        graph: graphPda,
+        task: taskPda,
+        escrow: escrowPda,
+        deposit: null,
+        funder: creator.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([creator])
+      .rpc();
+
+    // Entries are committed before the deadline and revealed afterwards.
+    const entries = workers.map((worker, i) => ({
+      workHash: Array.from(Buffer.alloc(32, 70 + i)),
+      uri: `ipfs://entry-${i}`,
+      salt: Array.from(Keypair.generate().publicKey.toBuffer()),
+    }));
+    for (const [i, worker] of workers.entries()) {
+      await program.methods
+        .commitReceipt(receiptCommitment(entries[i], worker.publicKey))
+        .accounts({
+          graph: graphPda,
+          task: taskPda,
+          commitment: deriveCommitmentPda(taskPda, worker.publicKey)[0],
+          agent: worker.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([worker])
+        .rpc();
+    }
+    for (const [i, worker] of workers.entries()) {
+      await program.methods
+        .revealReceipt(entries[i])
+        .accounts({
+          graph: graphPda,
+          task: taskPda,
+          commitment: deriveCommitmentPda(taskPda, worker.publicKey)[0],
+          receipt: deriveReceiptPda(taskPda, worker.publicKey)[0],
+          agent: worker.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([worker])
+        .rpc();
+    }
+
+    let taskAccount: any = await program.account.task.fetch(taskPda);
+    assert.ok(taskAccount.status.open, 'competition stays open while collecting entries');
+    assert.equal(taskAccount.submissionCount, 3);
+
+    const winners = [workers[0], workers[2]];
+    const selectWinners = () =>
+      program.methods
+        .selectWinners({ weightsBps: [6_000, 4_000] })
+        .accounts({
+          graph: graphPda,
+          task: taskPda,
+          escrow: escrowPda,
+          selector: creator.publicKey,
+        })
+        .remainingAccounts(
+          winners.map((w) => ({
+            pubkey: deriveReceiptPda(taskPda, w.publicKey)[0],
+            isSigner: false,
+            isWritable: true,
+          }))
+        )
+        .signers([creator])
+        .rpc();
+
+    try {
+      await selectWinners();
+      assert.fail('Should not select winners before the deadline');
+    } catch (err: any) {
+      assert.ok(err.message.includes('SubmissionWindowOpen'), 'Should fail with SubmissionWindowOpen');
+    }
+
+    // Past the deadline, selection still waits out the last commitment's reveal window.
+    await waitForSlot(deadline);
+    try {
+      await selectWinners();
+      assert.fail('Should not select winners while commitments can still be revealed');
+    } catch (err: any) {
+      assert.ok(err.message.includes('RevealWindowOpen'), 'Should fail with RevealWindowOpen');
+    }
+
+    await waitForSlot(taskAccount.lastCommitSlot.toNumber() + 151);
+    await selectWinners();
+
+    taskAccount = await program.account.task.fetch(taskPda);
+    assert.ok(taskAccount.status.completed);
+    assert.equal(taskAccount.unclaimedPayoutBps, 10_000);
+    const pool = taskAccount.payoutPoolLamports.toNumber();
+
+    // A split between the creator and the first winner would shortchange the second, so neither
+    // side can dispute the result.
+    for (const initiator of [creator, workers[0]]) {
+      try {
+        await program.methods
+          .disputeTask({ reason: 'Winner selection is unfair' })
+          .accounts({
+            task: taskPda,
+            dispute: deriveDisputePda(taskPda, initiator.publicKey)[0],
+            initiator: initiator.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([initiator])
+          .rpc();
+        assert.fail('Competition results should not be disputable');
+      } catch (err: any) {
+        assert.ok(
+          err.message.includes('CompetitionNotDisputable'),
+          'Should fail with CompetitionNotDisputable'
+        );
+      }
+    }
+
+    const treasury = await ensureProtocolConfig();
+    const claim = (worker: Keypair) =>
+      program.methods
+        .claimReward()
+        .accounts({
+          graph: graphPda,
+          task: taskPda,
+          escrow: escrowPda,
+          protocolConfig: protocolConfigPda,
+          treasury,
+          graphAuthority: authority.publicKey,
+          receipt: deriveReceiptPda(taskPda, worker.publicKey)[0],
+          agent: worker.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([worker])
+        .rpc();
+
+    try {
+      await claim(workers[1]);
+      assert.fail('Non-winning receipt should not be paid');
+    } catch (err: any) {
+      assert.ok(err.message.includes('NotAWinner'), 'Should fail with NotAWinner');
+    }
+
+    const escrowBefore = await provider.connection.getBalance(escrowPda, 'confirmed');
+    await claim(workers[0]);
+    const escrowAfterFirst = await provider.connection.getBalance(escrowPda, 'confirmed');
+    assert.equal(escrowBefore - escrowAfterFirst, Math.floor((pool * 6_000) / 10_000));
+
+    await claim(workers[2]);
+    assert.equal(await provider.connection.getBalance(escrowPda, 'confirmed'), 0);
+
+    taskAccount = await program.account.task.fetch(taskPda);
+    assert.equal(taskAccount.unclaimedPayoutBps, 0);
+    assert.ok(taskAccount.settledAtSlot.toNumber() > 0);
+  });
+});
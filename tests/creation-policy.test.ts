@@ -0,0 +1,138 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import * as assert from 'assert';
+import { deriveTaskPda, deriveCreatorAllowlistPda, airdrop, ensureGraph, taskParams } from './helpers';
+
+describe('bountygraph task creation policies', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Bountygraph as anchor.Program;
+  const authority = (provider.wallet as anchor.Wallet).payer;
+
+  let graphPda: PublicKey;
+  const creator = Keypair.generate();
+
+  before(async () => {
+    graphPda = await ensureGraph(27);
+    await airdrop(creator, 2);
+
+    // No retention period, so the cancelled task can be closed straight away.
+    await program.methods
+      .updateGraphConfig({
+        maxDependenciesPerTask: null,
+        minRewardLamports: null,
+        maxRewardLamports: null,
+        closeRetentionSlots: new anchor.BN(0),
+        graphFeeBps: null,
+        requireDependentApproval: null,
+        dependencyDisputePolicy: null,
+      })
+      .accounts({ graph: graphPda, authority: authority.publicKey })
+      .rpc();
+  });
+
+  const setPolicy = (creationPolicy: Record<string, {}>, depositLamports = 0) =>
+    program.methods
+      .setCreationPolicy({ creationPolicy, creationDepositLamports: new anchor.BN(depositLamports) })
+      .accounts({ graph: graphPda, authority: authority.publicKey })
+      .rpc();
+
+  const allowlistPda = () => deriveCreatorAllowlistPda(graphPda, creator.publicKey)[0];
+
+  const createTask = (taskId: number, opts: { authority?: boolean; allowlist?: boolean } = {}) =>
+    program.methods
+      .createTask(taskParams(taskId))
+      .accounts({
+        graph: graphPda,
+        authority: opts.authority ? authority.publicKey : null,
+        creator: creator.publicKey,
+        creatorAllowlist: opts.allowlist ? allowlistPda() : null,
+        parent: null,
+        task: deriveTaskPda(graphPda, taskId)[0],
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers(opts.authority ? [authority, creator] : [creator])
+      .rpc();
+
+  const expectUnauthorized = async (taskId: number, why: string, opts = {}) => {
+    try {
+      await createTask(taskId, opts);
+      assert.fail(why);
+    } catch (err: any) {
+      assert.ok(err.message.includes('UnauthorizedCreator'), 'Should fail with UnauthorizedCreator');
+    }
+  };
+
+  it('requires the graph authority under AuthorityOnly', async () => {
+    await setPolicy({ authorityOnly: {} });
+
+    await expectUnauthorized(2701, 'A creator alone cannot create tasks under AuthorityOnly');
+
+    await createTask(2701, { authority: true });
+    const task: any = await program.account.task.fetch(deriveTaskPda(graphPda, 2701)[0]);
+    assert.ok(task.creator.equals(creator.publicKey));
+    assert.equal(task.creationDepositLamports.toNumber(), 0);
+  });
+
+  it('admits allowlisted creators until they are removed', async () => {
+    await setPolicy({ allowlist: {} });
+
+    await expectUnauthorized(2702, 'A creator must be allowlisted');
+
+    await program.methods
+      .addAllowedCreator()
+      .accounts({
+        graph: graphPda,
+        authority: authority.publicKey,
+        creator: creator.publicKey,
+        creatorAllowlist: allowlistPda(),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    const entry: any = await program.account.creatorAllowlist.fetch(allowlistPda());
+    assert.ok(entry.graph.equals(graphPda));
+    assert.ok(entry.creator.equals(creator.publicKey));
+
+    await createTask(2702, { allowlist: true });
+    const task: any = await program.account.task.fetch(deriveTaskPda(graphPda, 2702)[0]);
+    assert.equal(task.creationDepositLamports.toNumber(), 0);
+
+    await program.methods
+      .removeAllowedCreator()
+      .accounts({ graph: graphPda, authority: authority.publicKey, creatorAllowlist: allowlistPda() })
+      .rpc();
+    assert.equal(await provider.connection.getAccountInfo(allowlistPda()), null);
+
+    await expectUnauthorized(2703, 'A removed creator can no longer create tasks');
+  });
+
+  it('charges the creation deposit under Open and refunds it when the task is closed', async () => {
+    const deposit = 50_000;
+    await setPolicy({ open: {} }, deposit);
+
+    const [taskPda] = deriveTaskPda(graphPda, 2704);
+    await createTask(2704);
+
+    const task: any = await program.account.task.fetch(taskPda);
+    assert.equal(task.creationDepositLamports.toNumber(), deposit);
+    const info = (await provider.connection.getAccountInfo(taskPda))!;
+    const rent = await provider.connection.getMinimumBalanceForRentExemption(info.data.length);
+    assert.equal(info.lamports, rent + deposit, 'the deposit sits in the task account on top of rent');
+
+    await program.methods
+      .cancelTask()
+      .accounts({ task: taskPda, escrow: null, parent: null, creator: creator.publicKey })
+      .signers([creator])
+      .rpc();
+
+    const before = await provider.connection.getBalance(creator.publicKey, 'confirmed');
+    await program.methods
+      .closeTask()
+      .accounts({ graph: graphPda, task: taskPda, creator: creator.publicKey })
+      .signers([creator])
+      .rpc();
+    const after = await provider.connection.getBalance(creator.publicKey, 'confirmed');
+    assert.ok(after - before >= deposit, 'closing returns the deposit with the freed rent');
+  });
+});
@@ -0,0 +1,146 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import * as assert from 'assert';
+import {
+  deriveGraphPda,
+  deriveTaskPda,
+  deriveReceiptPda,
+  airdrop,
+  taskParams,
+} from './helpers';
+
+describe('bountygraph cross-graph dependencies', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Bountygraph as anchor.Program;
+  const authority = (provider.wallet as anchor.Wallet).payer;
+
+  const productGraphId = new anchor.BN(0);
+  const platformGraphId = new anchor.BN(41);
+
+  const [productGraph] = deriveGraphPda(authority.publicKey, productGraphId);
+  const [platformGraph] = deriveGraphPda(authority.publicKey, platformGraphId);
+
+  const ensureGraph = async (graphPda: PublicKey, graphId: anchor.BN) => {
+    try {
+      await program.account.graph.fetch(graphPda);
+    } catch {
+      await program.methods
+        .initializeGraph({
+          graphId,
+          maxDependenciesPerTask: 10,
+          creationPolicy: { authorityOnly: {} },
+          creationDepositLamports: new anchor.BN(0),
+          guardian: null,
+        })
+        .accounts({
+          graph: graphPda,
+          authority: authority.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    }
+  };
+
+  before(async () => {
+    await ensureGraph(productGraph, productGraphId);
+    await ensureGraph(platformGraph, platformGraphId);
+
+    // The platform graph vets which product tasks may build on its work.
+    await program.methods
+      .updateGraphConfig({
+        maxDependenciesPerTask: null,
+        minRewardLamports: null,
+        maxRewardLamports: null,
+        closeRetentionSlots: null,
+        graphFeeBps: null,
+        requireDependentApproval: true,
+        dependencyDisputePolicy: null,
+      })
+      .accounts({
+        graph: platformGraph,
+        authority: authority.publicKey,
+      })
+      .rpc();
+  });
+
+  const createTask = (
+    graphPda: PublicKey,
+    taskId: anchor.BN,
+    externalDependencies: Array<{ graph: PublicKey; taskId: anchor.BN }>
+  ) =>
+    program.methods
+      .createTask(taskParams(taskId, {
+        rewardLamports: new anchor.BN(1_000_000),
+        externalDependencies,
+      }))
+      .accounts({
+        graph: graphPda,
+        authority: authority.publicKey,
+        creator: authority.publicKey,
+        creatorAllowlist: null,
+        parent: null,
+        task: deriveTaskPda(graphPda, taskId)[0],
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts(
+        externalDependencies.flatMap((edge) => [
+          { pubkey: edge.graph, isSigner: false, isWritable: false },
+          { pubkey: deriveTaskPda(edge.graph, edge.taskId)[0], isSigner: false, isWritable: false },
+        ])
+      )
+      .rpc();
+
+  const submit = (worker: Keypair, graphPda: PublicKey, taskId: anchor.BN, dependencyTasks: PublicKey[]) => {
+    const [taskPda] = deriveTaskPda(graphPda, taskId);
+    return program.methods
+      .submitReceipt({ workHash: Array.from(Buffer.alloc(32, 41)), uri: 'ipfs://cross-graph', contributors: [] })
+      .accounts({
+        graph: graphPda,
+        task: taskPda,
+        receipt: deriveReceiptPda(taskPda, worker.publicKey)[0],
+        agent: worker.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts(dependencyTasks.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false })))
+      .signers([worker])
+      .rpc();
+  };
+
+  it('unlocks a product task once an approved platform task is completed', async () => {
+    const worker = Keypair.generate();
+    await airdrop(worker, 1);
+
+    const platformTaskId = new anchor.BN(4101);
+    const productTaskId = new anchor.BN(4102);
+    const [platformTask] = deriveTaskPda(platformGraph, platformTaskId);
+    const [productTask] = deriveTaskPda(productGraph, productTaskId);
+
+    await createTask(platformGraph, platformTaskId, []);
+    await createTask(productGraph, productTaskId, [{ graph: platformGraph, taskId: platformTaskId }]);
+
+    let taskAccount: any = await program.account.task.fetch(productTask);
+    assert.equal(taskAccount.externalDependencies[0].approved, false);
+
+    await submit(worker, platformGraph, platformTaskId, []);
+    try {
+      await submit(worker, productGraph, productTaskId, [platformTask]);
+      assert.fail('Unapproved cross-graph edge should not unlock the task');
+    } catch (err: any) {
+      assert.ok(
+        err.message.includes('ExternalDependencyNotApproved'),
+        'Should fail with ExternalDependencyNotApproved'
+      );
+    }
+
+    await program.methods
+      .approveExternalDependency(0)
+      .accounts({ graph: platformGraph, authority: authority.publicKey, task: productTask })
+      .rpc();
+
+    await submit(worker, productGraph, productTaskId, [platformTask]);
+    taskAccount = await program.account.task.fetch(productTask);
+    assert.ok(taskAccount.status.completed);
+  });
+});
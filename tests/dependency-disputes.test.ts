@@ -0,0 +1,205 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair } from '@solana/web3.js';
+import * as assert from 'assert';
+import {
+  deriveGraphPda,
+  deriveTaskPda,
+  deriveEscrowPda,
+  deriveReceiptPda,
+  deriveDisputePda,
+  deriveProtocolConfigPda,
+  airdrop,
+  ensureProtocolConfig,
+  taskParams,
+} from './helpers';
+
+describe('bountygraph dependency dispute policy', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Bountygraph as anchor.Program;
+  const authority = (provider.wallet as anchor.Wallet).payer;
+
+  const graphId = new anchor.BN(42);
+
+  const [graphPda] = deriveGraphPda(authority.publicKey, graphId);
+
+  before(async () => {
+    try {
+      await program.account.graph.fetch(graphPda);
+    } catch {
+      await program.methods
+        .initializeGraph({
+          graphId,
+          maxDependenciesPerTask: 10,
+          creationPolicy: { authorityOnly: {} },
+          creationDepositLamports: new anchor.BN(0),
+          guardian: null,
+        })
+        .accounts({
+          graph: graphPda,
+          authority: authority.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    }
+  });
+
+  const setPolicy = (blockWhileRaised: boolean, minWorkerPct: number) =>
+    program.methods
+      .updateGraphConfig({
+        maxDependenciesPerTask: null,
+        minRewardLamports: null,
+        maxRewardLamports: null,
+        closeRetentionSlots: null,
+        graphFeeBps: null,
+        requireDependentApproval: null,
+        dependencyDisputePolicy: { blockWhileRaised, minWorkerPct },
+      })
+      .accounts({
+        graph: graphPda,
+        authority: authority.publicKey,
+      })
+      .rpc();
+
+  const createTask = (taskId: number, dependencies: number[]) =>
+    program.methods
+      .createTask(taskParams(new anchor.BN(taskId), {
+        rewardLamports: new anchor.BN(1_000_000),
+        dependencies: dependencies.map((id) => new anchor.BN(id)),
+      }))
+      .accounts({
+        graph: graphPda,
+        authority: authority.publicKey,
+        creator: authority.publicKey,
+        creatorAllowlist: null,
+        parent: null,
+        task: deriveTaskPda(graphPda, new anchor.BN(taskId))[0],
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts(
+        dependencies.map((id) => ({
+          pubkey: deriveTaskPda(graphPda, new anchor.BN(id))[0],
+          isSigner: false,
+          isWritable: false,
+        }))
+      )
+      .rpc();
+
+  const submit = (worker: Keypair, taskId: number, dependencies: number[]) => {
+    const [taskPda] = deriveTaskPda(graphPda, new anchor.BN(taskId));
+    return program.methods
+      .submitReceipt({ workHash: Array.from(Buffer.alloc(32, 42)), uri: `ipfs://task-${taskId}`, contributors: [] })
+      .accounts({
+        graph: graphPda,
+        task: taskPda,
+        receipt: deriveReceiptPda(taskPda, worker.publicKey)[0],
+        agent: worker.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts(
+        dependencies.map((id) => ({
+          pubkey: deriveTaskPda(graphPda, new anchor.BN(id))[0],
+          isSigner: false,
+          isWritable: false,
+        }))
+      )
+      .signers([worker])
+      .rpc();
+  };
+
+  it('blocks dependents while a prerequisite is under dispute, per graph policy', async () => {
+    const worker = Keypair.generate();
+    await airdrop(worker, 1);
+
+    await createTask(4201, []);
+    await createTask(4202, [4201]);
+    await submit(worker, 4201, []);
+
+    const [prerequisite] = deriveTaskPda(graphPda, new anchor.BN(4201));
+    await program.methods
+      .disputeTask({ reason: 'Prerequisite output is incomplete' })
+      .accounts({
+        task: prerequisite,
+        dispute: deriveDisputePda(prerequisite, authority.publicKey)[0],
+        initiator: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    try {
+      await submit(worker, 4202, [4201]);
+      assert.fail('A disputed prerequisite should not unlock its dependents');
+    } catch (err: any) {
+      assert.ok(err.message.includes('DependencyDisputed'), 'Should fail with DependencyDisputed');
+    }
+
+    try {
+      await setPolicy(false, 101);
+      assert.fail('Worker percentage above 100 should be rejected');
+    } catch (err: any) {
+      assert.ok(err.message.includes('InvalidConfig'), 'Should fail with InvalidConfig');
+    }
+
+    // A graph that tolerates open disputes lets the dependent proceed.
+    await setPolicy(false, 1);
+    await submit(worker, 4202, [4201]);
+
+    const taskAccount: any = await program.account.task.fetch(deriveTaskPda(graphPda, new anchor.BN(4202))[0]);
+    assert.ok(taskAccount.status.completed);
+  });
+
+  it('resolves disputes on unfunded prerequisites without a payout', async () => {
+    const worker = Keypair.generate();
+    await airdrop(worker, 1);
+
+    await createTask(4203, []);
+    await createTask(4204, [4203]);
+    await submit(worker, 4203, []);
+
+    const [prerequisite] = deriveTaskPda(graphPda, new anchor.BN(4203));
+    const [disputePda] = deriveDisputePda(prerequisite, authority.publicKey);
+    await program.methods
+      .disputeTask({ reason: 'Prerequisite output is wrong' })
+      .accounts({
+        task: prerequisite,
+        dispute: disputePda,
+        initiator: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    // The task was never funded, so its escrow PDA does not exist.
+    await program.methods
+      .resolveDispute({ creatorPct: 100, workerPct: 0 })
+      .accounts({
+        graph: graphPda,
+        authority: authority.publicKey,
+        task: prerequisite,
+        dispute: disputePda,
+        escrow: deriveEscrowPda(prerequisite)[0],
+        creator: authority.publicKey,
+        worker: worker.publicKey,
+        protocolConfig: deriveProtocolConfigPda()[0],
+        treasury: await ensureProtocolConfig(),
+        receipt: null,
+      })
+      .rpc();
+
+    const taskAccount: any = await program.account.task.fetch(prerequisite);
+    assert.ok(taskAccount.disputeStatus.resolved);
+    assert.equal(taskAccount.disputeWorkerPct, 0);
+    assert.equal(taskAccount.workerAwardLamports.toNumber(), 0);
+    const dispute: any = await program.account.dispute.fetch(disputePda);
+    assert.ok(dispute.status.resolved);
+    assert.equal(dispute.creatorPct, 100);
+
+    // The recorded outcome still counts: resolved wholly for the creator, below minWorkerPct.
+    try {
+      await submit(worker, 4204, [4203]);
+      assert.fail('A prerequisite resolved for the creator should not unlock its dependents');
+    } catch (err: any) {
+      assert.ok(err.message.includes('DependencyDisputed'), 'Should fail with DependencyDisputed');
+    }
+  });
+});
@@ -0,0 +1,117 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import * as assert from 'assert';
+import {
+  deriveGraphPda,
+  deriveTaskPda,
+  deriveReceiptPda,
+  airdrop,
+  taskParams,
+} from './helpers';
+
+describe('bountygraph dependency groups', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Bountygraph as anchor.Program;
+  const authority = (provider.wallet as anchor.Wallet).payer;
+
+  const graphId = new anchor.BN(0);
+
+  const [graphPda] = deriveGraphPda(authority.publicKey, graphId);
+
+  before(async () => {
+    try {
+      await program.account.graph.fetch(graphPda);
+    } catch {
+      await program.methods
+        .initializeGraph({
+          graphId,
+          maxDependenciesPerTask: 10,
+          creationPolicy: { authorityOnly: {} },
+          creationDepositLamports: new anchor.BN(0),
+          guardian: null,
+        })
+        .accounts({
+          graph: graphPda,
+          authority: authority.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    }
+  });
+
+  const asRemaining = (pdas: PublicKey[]) =>
+    pdas.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false }));
+
+  const createTask = (
+    taskId: number,
+    dependencies: number[],
+    dependencyGroups: Array<{ threshold: number; taskIds: anchor.BN[] }>
+  ) =>
+    program.methods
+      .createTask(taskParams(new anchor.BN(taskId), {
+        rewardLamports: new anchor.BN(1_000_000),
+        dependencies: dependencies.map((id) => new anchor.BN(id)),
+        dependencyGroups,
+      }))
+      .accounts({
+        graph: graphPda,
+        authority: authority.publicKey,
+        creator: authority.publicKey,
+        creatorAllowlist: null,
+        parent: null,
+        task: deriveTaskPda(graphPda, new anchor.BN(taskId))[0],
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts(asRemaining(dependencies.map((id) => deriveTaskPda(graphPda, new anchor.BN(id))[0])))
+      .rpc();
+
+  const submit = (worker: Keypair, taskId: number, dependencies: number[]) => {
+    const [taskPda] = deriveTaskPda(graphPda, new anchor.BN(taskId));
+    return program.methods
+      .submitReceipt({ workHash: Array.from(Buffer.alloc(32, 40)), uri: `ipfs://task-${taskId}`, contributors: [] })
+      .accounts({
+        graph: graphPda,
+        task: taskPda,
+        receipt: deriveReceiptPda(taskPda, worker.publicKey)[0],
+        agent: worker.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts(asRemaining(dependencies.map((id) => deriveTaskPda(graphPda, new anchor.BN(id))[0])))
+      .signers([worker])
+      .rpc();
+  };
+
+  it('unlocks a task once 2 of its 3 grouped dependencies are completed', async () => {
+    const worker = Keypair.generate();
+    await airdrop(worker, 1);
+
+    const audits = [4001, 4002, 4003];
+    for (const id of audits) await createTask(id, [], []);
+
+    try {
+      await createTask(4004, audits, [{ threshold: 4, taskIds: audits.map((id) => new anchor.BN(id)) }]);
+      assert.fail('Threshold above the group size should be rejected');
+    } catch (err: any) {
+      assert.ok(err.message.includes('InvalidDependencyGroups'), 'Should fail with InvalidDependencyGroups');
+    }
+
+    await createTask(4004, audits, [{ threshold: 2, taskIds: audits.map((id) => new anchor.BN(id)) }]);
+
+    await submit(worker, 4001, []);
+    try {
+      await submit(worker, 4004, audits);
+      assert.fail('One completed audit should not satisfy a 2-of-3 group');
+    } catch (err: any) {
+      assert.ok(err.message.includes('DependencyNotCompleted'), 'Should fail with DependencyNotCompleted');
+    }
+
+    await submit(worker, 4003, []);
+    await submit(worker, 4004, audits);
+
+    const taskAccount: any = await program.account.task.fetch(deriveTaskPda(graphPda, new anchor.BN(4004))[0]);
+    assert.ok(taskAccount.status.completed);
+    assert.equal(taskAccount.dependencyGroups[0].threshold, 2);
+  });
+});
@@ -1,6 +1,17 @@
 import * as anchor from '@coral-xyz/anchor';
-import { Keypair, LAMPORTS_PER_SOL, PublicKey } from '@solana/web3.js';
+import { Keypair } from '@solana/web3.js';
 import * as assert from 'assert';
+import {
+  deriveGraphPda,
+  deriveTaskPda,
+  deriveEscrowPda,
+  deriveReceiptPda,
+  deriveDisputePda,
+  deriveProtocolConfigPda,
+  airdrop,
+  ensureProtocolConfig,
+  taskParams,
+} from './helpers';
 
 describe('bountygraph detailed dispute mechanism with reasons', () => {
   const provider = anchor.AnchorProvider.env();
@@ -9,56 +20,24 @@ describe('bountygraph detailed dispute mechanism with reasons', () => {
   const program = anchor.workspace.Bountygraph as anchor.Program;
   const authority = (provider.wallet as anchor.Wallet).payer;
 
-  const deriveGraphPda = (authorityPk: PublicKey) =>
-    PublicKey.findProgramAddressSync(
-      [Buffer.from('graph'), authorityPk.toBuffer()],
-      program.programId
-    );
-
-  const deriveTaskPda = (graphPk: PublicKey, taskId: anchor.BN) =>
-    PublicKey.findProgramAddressSync(
-      [
-        Buffer.from('task'),
-        graphPk.toBuffer(),
-        taskId.toArrayLike(Buffer, 'le', 8),
-      ],
-      program.programId
-    );
-
-  const deriveEscrowPda = (taskPk: PublicKey) =>
-    PublicKey.findProgramAddressSync(
-      [Buffer.from('escrow'), taskPk.toBuffer()],
-      program.programId
-    );
+  const graphId = new anchor.BN(0);
 
-  const deriveReceiptPda = (taskPk: PublicKey, agentPk: PublicKey) =>
-    PublicKey.findProgramAddressSync(
-      [Buffer.from('receipt'), taskPk.toBuffer(), agentPk.toBuffer()],
-      program.programId
-    );
-
-  const deriveDisputePda = (taskPk: PublicKey, initiatorPk: PublicKey) =>
-    PublicKey.findProgramAddressSync(
-      [Buffer.from('dispute'), taskPk.toBuffer(), initiatorPk.toBuffer()],
-      program.programId
-    );
-
-  const airdrop = async (kp: Keypair, sol: number) => {
-    const sig = await provider.connection.requestAirdrop(
-      kp.publicKey,
-      sol * LAMPORTS_PER_SOL
-    );
-    await provider.connection.confirmTransaction(sig, 'confirmed');
-  };
+  const [protocolConfigPda] = deriveProtocolConfigPda();
 
   const ensureGraph = async () => {
-    const [graphPda] = deriveGraphPda(authority.publicKey);
+    const [graphPda] = deriveGraphPda(authority.publicKey, graphId);
     try {
       await program.account.graph.fetch(graphPda);
       return graphPda;
     } catch {
       await program.methods
-        .initializeGraph({ maxDependenciesPerTask: 10 })
+        .initializeGraph({
+          graphId,
+          maxDependenciesPerTask: 10,
+          creationPolicy: { authorityOnly: {} },
+          creationDepositLamports: new anchor.BN(0),
+          guardian: null,
+        })
         .accounts({
           graph: graphPda,
           authority: authority.publicKey,
@@ -86,11 +65,13 @@ describe('bountygraph detailed dispute mechanism with reasons', () => {
 
     // Create and fund task
     await program.methods
-      .createTask({ taskId, rewardLamports: new anchor.BN(1_000_000), dependencies: [] })
+      .createTask(taskParams(taskId, { rewardLamports: new anchor.BN(1_000_000) }))
       .accounts({
         graph: graphPda,
         authority: authority.publicKey,
         creator: creator.publicKey,
+        creatorAllowlist: null,
+        parent: null,
         task: taskPda,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
@@ -101,8 +82,10 @@ describe('bountygraph detailed dispute mechanism with reasons', () => {
     await program.methods
       .fundTask(new anchor.BN(600_000))
       .accounts({
+        graph: graphPda,
         task: taskPda,
         escrow: escrowPda,
+        deposit: null,
         funder: creator.publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
@@ -114,8 +97,9 @@ describe('bountygraph detailed dispute mechanism with reasons', () => {
     const workHash = Array.from(Buffer.alloc(32, 42));
 
     await program.methods
-      .submitReceipt({ workHash, uri: 'ipfs://detailed-test-1' })
+      .submitReceipt({ workHash, uri: 'ipfs://detailed-test-1', contributors: [] })
       .accounts({
+        graph: graphPda,
         task: taskPda,
         receipt: receiptPda,
         agent: worker.publicKey,
@@ -159,11 +143,13 @@ describe('bountygraph detailed dispute mechanism with reasons', () => {
 
     // Setup task and dispute
     await program.methods
-      .createTask({ taskId, rewardLamports: new anchor.BN(1_000_000), dependencies: [] })
+      .createTask(taskParams(taskId, { rewardLamports: new anchor.BN(1_000_000) }))
       .accounts({
         graph: graphPda,
         authority: authority.publicKey,
         creator: creator.publicKey,
+        creatorAllowlist: null,
+        parent: null,
         task: taskPda,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
@@ -174,8 +160,10 @@ describe('bountygraph detailed dispute mechanism with reasons', () => {
     await program.methods
       .fundTask(new anchor.BN(1_000_000))
       .accounts({
+        graph: graphPda,
         task: taskPda,
         escrow: escrowPda,
+        deposit: null,
         funder: creator.publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
@@ -186,8 +174,9 @@ describe('bountygraph detailed dispute mechanism with reasons', () => {
     const workHash = Array.from(Buffer.alloc(32, 51));
 
     await program.methods
-      .submitReceipt({ workHash, uri: 'ipfs://detailed-test-2' })
+      .submitReceipt({ workHash, uri: 'ipfs://detailed-test-2', contributors: [] })
       .accounts({
+        graph: graphPda,
         task: taskPda,
         receipt: receiptPda,
         agent: worker.publicKey,
@@ -223,9 +212,11 @@ describe('bountygraph detailed dispute mechanism with reasons', () => {
         task: taskPda,
         dispute: disputePda,
         escrow: escrowPda,
+        protocolConfig: protocolConfigPda,
+        treasury: await ensureProtocolConfig(),
         creator: creator.publicKey,
         worker: worker.publicKey,
-        systemProgram: anchor.web3.SystemProgram.programId,
+        receipt: null,
       })
       .signers([authority])
       .rpc();
@@ -270,11 +261,13 @@ describe('bountygraph detailed dispute mechanism with reasons', () => {
 
     // Setup task and dispute
     await program.methods
-      .createTask({ taskId, rewardLamports: new anchor.BN(1_000_000), dependencies: [] })
+      .createTask(taskParams(taskId, { rewardLamports: new anchor.BN(1_000_000) }))
       .accounts({
         graph: graphPda,
         authority: authority.publicKey,
         creator: creator.publicKey,
+        creatorAllowlist: null,
+        parent: null,
         task: taskPda,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
@@ -285,8 +278,10 @@ describe('bountygraph detailed dispute mechanism with reasons', () => {
     await program.methods
       .fundTask(new anchor.BN(500_000))
       .accounts({
+        graph: graphPda,
         task: taskPda,
         escrow: escrowPda,
+        deposit: null,
         funder: creator.publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
@@ -297,8 +292,9 @@ describe('bountygraph detailed dispute mechanism with reasons', () => {
     const workHash = Array.from(Buffer.alloc(32, 60));
 
     await program.methods
-      .submitReceipt({ workHash, uri: 'ipfs://detailed-test-3' })
+      .submitReceipt({ workHash, uri: 'ipfs://detailed-test-3', contributors: [] })
       .accounts({
+        graph: graphPda,
         task: taskPda,
         receipt: receiptPda,
         agent: worker.publicKey,
@@ -329,9 +325,11 @@ describe('bountygraph detailed dispute mechanism with reasons', () => {
           task: taskPda,
           dispute: disputePda,
           escrow: escrowPda,
+          protocolConfig: protocolConfigPda,
+          treasury: await ensureProtocolConfig(),
           creator: creator.publicKey,
           worker: worker.publicKey,
-          systemProgram: anchor.web3.SystemProgram.programId,
+          receipt: null,
         })
         .signers([authority])
         .rpc();
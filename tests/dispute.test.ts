@@ -1,6 +1,16 @@
 import * as anchor from '@coral-xyz/anchor';
-import { Keypair, LAMPORTS_PER_SOL, PublicKey } from '@solana/web3.js';
+import { Keypair } from '@solana/web3.js';
 import * as assert from 'assert';
+import {
+  deriveGraphPda,
+  deriveTaskPda,
+  deriveEscrowPda,
+  deriveReceiptPda,
+  deriveProtocolConfigPda,
+  airdrop,
+  ensureProtocolConfig,
+  taskParams,
+} from './helpers';
 
 describe('bountygraph dispute resolution', () => {
   const provider = anchor.AnchorProvider.env();
@@ -10,50 +20,24 @@ describe('bountygraph dispute resolution', () => {
 
   const authority = (provider.wallet as anchor.Wallet).payer;
 
-  const deriveGraphPda = (authorityPk: PublicKey) =>
-    PublicKey.findProgramAddressSync(
-      [Buffer.from('graph'), authorityPk.toBuffer()],
-      program.programId
-    );
-
-  const deriveTaskPda = (graphPk: PublicKey, taskId: anchor.BN) =>
-    PublicKey.findProgramAddressSync(
-      [
-        Buffer.from('task'),
-        graphPk.toBuffer(),
-        taskId.toArrayLike(Buffer, 'le', 8),
-      ],
-      program.programId
-    );
-
-  const deriveEscrowPda = (taskPk: PublicKey) =>
-    PublicKey.findProgramAddressSync(
-      [Buffer.from('escrow'), taskPk.toBuffer()],
-      program.programId
-    );
-
-  const deriveReceiptPda = (taskPk: PublicKey, agentPk: PublicKey) =>
-    PublicKey.findProgramAddressSync(
-      [Buffer.from('receipt'), taskPk.toBuffer(), agentPk.toBuffer()],
-      program.programId
-    );
-
-  const airdrop = async (kp: Keypair, sol: number) => {
-    const sig = await provider.connection.requestAirdrop(
-      kp.publicKey,
-      sol * LAMPORTS_PER_SOL
-    );
-    await provider.connection.confirmTransaction(sig, 'confirmed');
-  };
+  const graphId = new anchor.BN(0);
+
+  const [protocolConfigPda] = deriveProtocolConfigPda();
 
   const ensureGraph = async () => {
-    const [graphPda] = deriveGraphPda(authority.publicKey);
+    const [graphPda] = deriveGraphPda(authority.publicKey, graphId);
     try {
       await program.account.graph.fetch(graphPda);
       return graphPda;
     } catch {
       await program.methods
-        .initializeGraph({ maxDependenciesPerTask: 10 })
+        .initializeGraph({
+          graphId,
+          maxDependenciesPerTask: 10,
+          creationPolicy: { authorityOnly: {} },
+          creationDepositLamports: new anchor.BN(0),
+          guardian: null,
+        })
         .accounts({
           graph: graphPda,
           authority: authority.publicKey,
@@ -81,11 +65,13 @@ describe('bountygraph dispute resolution', () => {
     const [taskPda] = deriveTaskPda(graphPda, taskId);
 
     await program.methods
-      .createTask({ taskId, rewardLamports: new anchor.BN(1_000_000), dependencies: [] })
+      .createTask(taskParams(taskId, { rewardLamports: new anchor.BN(1_000_000) }))
       .accounts({
         graph: graphPda,
         authority: authority.publicKey,
         creator: creator.publicKey,
+        creatorAllowlist: null,
+        parent: null,
         task: taskPda,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
@@ -97,8 +83,10 @@ describe('bountygraph dispute resolution', () => {
     await program.methods
       .fundTask(new anchor.BN(500_000))
       .accounts({
+        graph: graphPda,
         task: taskPda,
         escrow: escrowPda,
+        deposit: null,
         funder: creator.publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
@@ -109,8 +97,9 @@ describe('bountygraph dispute resolution', () => {
     const workHash = Array.from(Buffer.alloc(32, 7));
 
     await program.methods
-      .submitReceipt({ workHash, uri: 'ipfs://receipt' })
+      .submitReceipt({ workHash, uri: 'ipfs://receipt', contributors: [] })
       .accounts({
+        graph: graphPda,
         task: taskPda,
         receipt: receiptPda,
         agent: worker.publicKey,
@@ -140,9 +129,11 @@ describe('bountygraph dispute resolution', () => {
         authority: authority.publicKey,
         task: taskPda,
         escrow: escrowPda,
+        protocolConfig: protocolConfigPda,
+        treasury: await ensureProtocolConfig(),
         creator: creator.publicKey,
         worker: worker.publicKey,
-        systemProgram: anchor.web3.SystemProgram.programId,
+        receipt: null,
       })
       .signers([authority])
       .rpc();
@@ -171,11 +162,13 @@ describe('bountygraph dispute resolution', () => {
     const [taskPda] = deriveTaskPda(graphPda, taskId);
 
     await program.methods
-      .createTask({ taskId, rewardLamports: new anchor.BN(1_000_000), dependencies: [] })
+      .createTask(taskParams(taskId, { rewardLamports: new anchor.BN(1_000_000) }))
       .accounts({
         graph: graphPda,
         authority: authority.publicKey,
         creator: creator.publicKey,
+        creatorAllowlist: null,
+        parent: null,
         task: taskPda,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
@@ -187,8 +180,10 @@ describe('bountygraph dispute resolution', () => {
     await program.methods
       .fundTask(new anchor.BN(700_000))
       .accounts({
+        graph: graphPda,
         task: taskPda,
         escrow: escrowPda,
+        deposit: null,
         funder: creator.publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
@@ -199,8 +194,9 @@ describe('bountygraph dispute resolution', () => {
     const workHash = Array.from(Buffer.alloc(32, 9));
 
     await program.methods
-      .submitReceipt({ workHash, uri: 'ipfs://receipt-2' })
+      .submitReceipt({ workHash, uri: 'ipfs://receipt-2', contributors: [] })
       .accounts({
+        graph: graphPda,
         task: taskPda,
         receipt: receiptPda,
         agent: worker.publicKey,
@@ -226,9 +222,11 @@ describe('bountygraph dispute resolution', () => {
         authority: authority.publicKey,
         task: taskPda,
         escrow: escrowPda,
+        protocolConfig: protocolConfigPda,
+        treasury: await ensureProtocolConfig(),
         creator: creator.publicKey,
         worker: worker.publicKey,
-        systemProgram: anchor.web3.SystemProgram.programId,
+        receipt: null,
       })
       .signers([authority])
       .rpc();
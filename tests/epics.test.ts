@@ -0,0 +1,301 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import * as assert from 'assert';
+import {
+  deriveGraphPda,
+  deriveTaskPda,
+  deriveEscrowPda,
+  deriveReceiptPda,
+  deriveProtocolConfigPda,
+  airdrop,
+  ensureProtocolConfig,
+  taskParams,
+} from './helpers';
+
+describe('bountygraph epics', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Bountygraph as anchor.Program;
+  const authority = (provider.wallet as anchor.Wallet).payer;
+
+  const graphId = new anchor.BN(43);
+
+  const [protocolConfigPda] = deriveProtocolConfigPda();
+
+  const [graphPda] = deriveGraphPda(authority.publicKey, graphId);
+  let treasury: PublicKey;
+
+  const createTask = (
+    creator: Keypair,
+    taskId: anchor.BN,
+    reward: number,
+    opts: { epic?: boolean; parentTaskId?: anchor.BN; coSigned?: boolean } = {}
+  ) =>
+    program.methods
+      .createTask(taskParams(taskId, {
+        rewardLamports: new anchor.BN(reward),
+        epic: opts.epic ?? false,
+        parentTaskId: opts.parentTaskId ?? null,
+      }))
+      .accounts({
+        graph: graphPda,
+        authority: opts.coSigned === false ? null : authority.publicKey,
+        creator: creator.publicKey,
+        creatorAllowlist: null,
+        parent: opts.parentTaskId ? deriveTaskPda(graphPda, opts.parentTaskId)[0] : null,
+        task: deriveTaskPda(graphPda, taskId)[0],
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers(opts.coSigned === false ? [creator] : [authority, creator])
+      .rpc();
+
+  const submit = (taskPda: PublicKey, worker: Keypair) =>
+    program.methods
+      .submitReceipt({ workHash: Array.from(Buffer.alloc(32, 43)), uri: 'ipfs://epic', contributors: [] })
+      .accounts({
+        graph: graphPda,
+        task: taskPda,
+        receipt: deriveReceiptPda(taskPda, worker.publicKey)[0],
+        agent: worker.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([worker])
+      .rpc();
+
+  const rollUp = (epicPda: PublicKey, children: PublicKey[], recipients: PublicKey[]) =>
+    program.methods
+      .rollUp()
+      .accounts({
+        graph: graphPda,
+        task: epicPda,
+        escrow: recipients.length > 0 ? deriveEscrowPda(epicPda)[0] : null,
+        protocolConfig: protocolConfigPda,
+        treasury,
+        graphAuthority: authority.publicKey,
+      })
+      .remainingAccounts([
+        ...children.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false })),
+        ...recipients.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true })),
+      ])
+      .rpc();
+
+  before(async () => {
+    try {
+      await program.account.graph.fetch(graphPda);
+    } catch {
+      await program.methods
+        .initializeGraph({
+          graphId,
+          maxDependenciesPerTask: 10,
+          creationPolicy: { authorityOnly: {} },
+          creationDepositLamports: new anchor.BN(0),
+          guardian: null,
+        })
+        .accounts({
+          graph: graphPda,
+          authority: authority.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    }
+
+    // Open creation, so the epic creator check is exercised without the authority co-signing.
+    await program.methods
+      .setCreationPolicy({ creationPolicy: { open: {} }, creationDepositLamports: new anchor.BN(0) })
+      .accounts({ graph: graphPda, authority: authority.publicKey })
+      .rpc();
+
+    treasury = await ensureProtocolConfig();
+  });
+
+  it('rolls up an epic once all its children complete and splits the bonus', async () => {
+    const creator = Keypair.generate();
+    const workerA = Keypair.generate();
+    const workerB = Keypair.generate();
+    await airdrop(creator, 2);
+    await airdrop(workerA, 1);
+    await airdrop(workerB, 1);
+
+    const epicId = new anchor.BN(4301);
+    const childA = new anchor.BN(4302);
+    const childB = new anchor.BN(4303);
+    const [epicPda] = deriveTaskPda(graphPda, epicId);
+    const [childAPda] = deriveTaskPda(graphPda, childA);
+    const [childBPda] = deriveTaskPda(graphPda, childB);
+    const [escrowPda] = deriveEscrowPda(epicPda);
+    const bonus = 1_000_000;
+
+    await createTask(creator, epicId, bonus, { epic: true });
+    await createTask(creator, childA, 100_000, { parentTaskId: epicId });
+    await createTask(creator, childB, 100_000, { parentTaskId: epicId });
+
+    await program.methods
+      .fundTask(new anchor.BN(bonus))
+      .accounts({
+        graph: graphPda,
+        task: epicPda,
+        escrow: escrowPda,
+        deposit: null,
+        funder: creator.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([creator])
+      .rpc();
+
+    const epic: any = await program.account.task.fetch(epicPda);
+    assert.equal(epic.childCount, 2);
+
+    try {
+      await submit(epicPda, workerA);
+      assert.fail('Epics should not accept receipts');
+    } catch (err: any) {
+      assert.ok(err.message.includes('EpicTask'), 'Should fail with EpicTask');
+    }
+
+    await submit(childAPda, workerA);
+    try {
+      await rollUp(epicPda, [childAPda, childBPda], [workerA.publicKey, workerB.publicKey]);
+      assert.fail('Roll-up should wait for every child');
+    } catch (err: any) {
+      assert.ok(err.message.includes('ChildNotCompleted'), 'Should fail with ChildNotCompleted');
+    }
+
+    await submit(childBPda, workerB);
+    try {
+      await rollUp(epicPda, [childAPda], [workerA.publicKey]);
+      assert.fail('Roll-up should require every child');
+    } catch (err: any) {
+      assert.ok(err.message.includes('InvalidEpicChildren'), 'Should fail with InvalidEpicChildren');
+    }
+
+    const escrowBefore = await provider.connection.getBalance(escrowPda, 'confirmed');
+    const workerABefore = await provider.connection.getBalance(workerA.publicKey, 'confirmed');
+    await rollUp(epicPda, [childAPda, childBPda], [workerA.publicKey, workerB.publicKey]);
+
+    const rolled: any = await program.account.task.fetch(epicPda);
+    assert.ok(rolled.status.completed, 'Epic should be completed');
+    assert.equal(await provider.connection.getBalance(escrowPda, 'confirmed'), 0);
+    const workerAAfter = await provider.connection.getBalance(workerA.publicKey, 'confirmed');
+    assert.equal(workerAAfter - workerABefore, Math.floor(escrowBefore / 2));
+  });
+
+  it('rejects epics with dependencies of their own', async () => {
+    const creator = Keypair.generate();
+    await airdrop(creator, 1);
+
+    const prerequisiteId = new anchor.BN(4313);
+    await createTask(creator, prerequisiteId, 100_000);
+
+    // roll_up only checks the children, so the prerequisite belongs on them instead.
+    try {
+      await program.methods
+        .createTask(taskParams(new anchor.BN(4314), {
+          rewardLamports: new anchor.BN(0),
+          epic: true,
+          dependencies: [prerequisiteId],
+        }))
+        .accounts({
+          graph: graphPda,
+          authority: authority.publicKey,
+          creator: creator.publicKey,
+          creatorAllowlist: null,
+          parent: null,
+          task: deriveTaskPda(graphPda, new anchor.BN(4314))[0],
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .remainingAccounts([
+          { pubkey: deriveTaskPda(graphPda, prerequisiteId)[0], isSigner: false, isWritable: false },
+        ])
+        .signers([authority, creator])
+        .rpc();
+      assert.fail('Epics cannot have dependencies');
+    } catch (err: any) {
+      assert.ok(err.message.includes('InvalidConfig'), 'Should fail with InvalidConfig');
+    }
+  });
+
+  it('rejects children of a task that is not an epic', async () => {
+    const creator = Keypair.generate();
+    await airdrop(creator, 1);
+
+    const plainId = new anchor.BN(4304);
+    await createTask(creator, plainId, 100_000);
+
+    try {
+      await createTask(creator, new anchor.BN(4305), 100_000, { parentTaskId: plainId });
+      assert.fail('Only epics can have children');
+    } catch (err: any) {
+      assert.ok(err.message.includes('InvalidEpic'), 'Should fail with InvalidEpic');
+    }
+  });
+
+  it('only lets the epic creator or the graph authority attach children', async () => {
+    const creator = Keypair.generate();
+    const stranger = Keypair.generate();
+    await airdrop(creator, 1);
+    await airdrop(stranger, 1);
+
+    const epicId = new anchor.BN(4306);
+    const [epicPda] = deriveTaskPda(graphPda, epicId);
+    await createTask(creator, epicId, 100_000, { epic: true, coSigned: false });
+
+    try {
+      await createTask(stranger, new anchor.BN(4307), 100_000, { parentTaskId: epicId, coSigned: false });
+      assert.fail('A stranger should not be able to attach children to the epic');
+    } catch (err: any) {
+      assert.ok(err.message.includes('InvalidEpic'), 'Should fail with InvalidEpic');
+    }
+
+    await createTask(creator, new anchor.BN(4308), 100_000, { parentTaskId: epicId, coSigned: false });
+    await createTask(stranger, new anchor.BN(4309), 100_000, { parentTaskId: epicId });
+
+    const epic: any = await program.account.task.fetch(epicPda);
+    assert.equal(epic.childCount, 2);
+  });
+
+  it('detaches a cancelled child so the epic can still roll up', async () => {
+    const creator = Keypair.generate();
+    const worker = Keypair.generate();
+    await airdrop(creator, 1);
+    await airdrop(worker, 1);
+
+    const epicId = new anchor.BN(4310);
+    const keptId = new anchor.BN(4311);
+    const cancelledId = new anchor.BN(4312);
+    const [epicPda] = deriveTaskPda(graphPda, epicId);
+    const [keptPda] = deriveTaskPda(graphPda, keptId);
+    const [cancelledPda] = deriveTaskPda(graphPda, cancelledId);
+
+    await createTask(creator, epicId, 100_000, { epic: true });
+    await createTask(creator, keptId, 100_000, { parentTaskId: epicId });
+    await createTask(creator, cancelledId, 100_000, { parentTaskId: epicId });
+
+    try {
+      await program.methods
+        .cancelTask()
+        .accounts({ task: cancelledPda, escrow: null, parent: null, creator: creator.publicKey })
+        .signers([creator])
+        .rpc();
+      assert.fail('Cancelling a child should require its epic');
+    } catch (err: any) {
+      assert.ok(err.message.includes('InvalidEpic'), 'Should fail with InvalidEpic');
+    }
+
+    await program.methods
+      .cancelTask()
+      .accounts({ task: cancelledPda, escrow: null, parent: epicPda, creator: creator.publicKey })
+      .signers([creator])
+      .rpc();
+
+    const epic: any = await program.account.task.fetch(epicPda);
+    assert.equal(epic.childCount, 1);
+    const cancelled: any = await program.account.task.fetch(cancelledPda);
+    assert.equal(cancelled.parentTaskId, null);
+
+    await submit(keptPda, worker);
+    await rollUp(epicPda, [keptPda], []);
+    const rolled: any = await program.account.task.fetch(epicPda);
+    assert.ok(rolled.status.completed, 'Epic should be completed');
+  });
+});
@@ -0,0 +1,166 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import * as assert from 'assert';
+import {
+  deriveGraphPda,
+  deriveTaskPda,
+  deriveEscrowPda,
+  deriveReceiptPda,
+  deriveProtocolConfigPda,
+  airdrop,
+  ensureProtocolConfig,
+  taskParams,
+} from './helpers';
+
+describe('bountygraph protocol and graph fees', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Bountygraph as anchor.Program;
+  const authority = (provider.wallet as anchor.Wallet).payer;
+
+  const graphId = new anchor.BN(37);
+
+  const [protocolConfigPda] = deriveProtocolConfigPda();
+
+  const [graphPda] = deriveGraphPda(authority.publicKey, graphId);
+  const treasury = Keypair.generate();
+  let originalTreasury: PublicKey;
+
+  const updateProtocolConfig = (params: { admin: PublicKey | null; treasury: PublicKey | null; feeBps: number | null }) =>
+    program.methods
+      .updateProtocolConfig(params)
+      .accounts({ protocolConfig: protocolConfigPda, admin: authority.publicKey })
+      .rpc();
+
+  const updateGraphFee = (graphFeeBps: number) =>
+    program.methods
+      .updateGraphConfig({
+        maxDependenciesPerTask: null,
+        minRewardLamports: null,
+        maxRewardLamports: null,
+        closeRetentionSlots: null,
+        graphFeeBps,
+        requireDependentApproval: null,
+        dependencyDisputePolicy: null,
+      })
+      .accounts({
+        graph: graphPda,
+        authority: authority.publicKey,
+      })
+      .rpc();
+
+  before(async () => {
+    try {
+      await program.account.graph.fetch(graphPda);
+    } catch {
+      await program.methods
+        .initializeGraph({
+          graphId,
+          maxDependenciesPerTask: 10,
+          creationPolicy: { authorityOnly: {} },
+          creationDepositLamports: new anchor.BN(0),
+          guardian: null,
+        })
+        .accounts({
+          graph: graphPda,
+          authority: authority.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    }
+
+    originalTreasury = await ensureProtocolConfig();
+    // Fees are credited directly, so the treasury must already be rent-exempt.
+    await airdrop(treasury, 1);
+    await updateProtocolConfig({ admin: null, treasury: treasury.publicKey, feeBps: 500 });
+    await updateGraphFee(200);
+  });
+
+  after(async () => {
+    await updateProtocolConfig({ admin: null, treasury: originalTreasury, feeBps: 0 });
+  });
+
+  it('rejects fees above the protocol cap', async () => {
+    try {
+      await updateProtocolConfig({ admin: null, treasury: null, feeBps: 1_001 });
+      assert.fail('Fee above the cap should be rejected');
+    } catch (err: any) {
+      assert.ok(err.message.includes('FeeTooHigh'), 'Should fail with FeeTooHigh');
+    }
+  });
+
+  it('routes protocol and graph fees out of a claimed reward', async () => {
+    const creator = Keypair.generate();
+    const worker = Keypair.generate();
+    await airdrop(creator, 2);
+    await airdrop(worker, 1);
+
+    const taskId = new anchor.BN(3701);
+    const [taskPda] = deriveTaskPda(graphPda, taskId);
+    const [escrowPda] = deriveEscrowPda(taskPda);
+    const reward = 1_000_000;
+
+    await program.methods
+      .createTask(taskParams(taskId, { rewardLamports: new anchor.BN(reward) }))
+      .accounts({
+        graph: graphPda,
+        authority: authority.publicKey,
+        creator: creator.publicKey,
+        creatorAllowlist: null,
+        parent: null,
+        task: taskPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([authority, creator])
+      .rpc();
+
+    await program.methods
+      .fundTask(new anchor.BN(reward))
+      .accounts({
+        graph: graphPda,
+        task: taskPda,
+        escrow: escrowPda,
+        deposit: null,
+        funder: creator.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([creator])
+      .rpc();
+
+    await program.methods
+      .submitReceipt({ workHash: Array.from(Buffer.alloc(32, 37)), uri: 'ipfs://fees', contributors: [] })
+      .accounts({
+        graph: graphPda,
+        task: taskPda,
+        receipt: deriveReceiptPda(taskPda, worker.publicKey)[0],
+        agent: worker.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([worker])
+      .rpc();
+
+    const escrowBefore = await provider.connection.getBalance(escrowPda, 'confirmed');
+    const treasuryBefore = await provider.connection.getBalance(treasury.publicKey, 'confirmed');
+
+    await program.methods
+      .claimReward()
+      .accounts({
+        graph: graphPda,
+        task: taskPda,
+        escrow: escrowPda,
+        protocolConfig: protocolConfigPda,
+        treasury: treasury.publicKey,
+        graphAuthority: authority.publicKey,
+        receipt: null,
+        agent: worker.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([worker])
+      .rpc();
+
+    const treasuryAfter = await provider.connection.getBalance(treasury.publicKey, 'confirmed');
+    assert.equal(treasuryAfter - treasuryBefore, Math.floor((escrowBefore * 500) / 10_000));
+    assert.equal(await provider.connection.getBalance(escrowPda, 'confirmed'), 0);
+  });
+});
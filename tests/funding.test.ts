@@ -0,0 +1,177 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair } from '@solana/web3.js';
+import * as assert from 'assert';
+import {
+  deriveGraphPda,
+  deriveTaskPda,
+  deriveEscrowPda,
+  deriveReceiptPda,
+  airdrop,
+  taskParams,
+} from './helpers';
+
+describe('bountygraph funding status', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Bountygraph as anchor.Program;
+  const authority = (provider.wallet as anchor.Wallet).payer;
+
+  const graphId = new anchor.BN(0);
+
+  const [graphPda] = deriveGraphPda(authority.publicKey, graphId);
+
+  before(async () => {
+    try {
+      await program.account.graph.fetch(graphPda);
+    } catch {
+      await program.methods
+        .initializeGraph({
+          graphId,
+          maxDependenciesPerTask: 10,
+          creationPolicy: { authorityOnly: {} },
+          creationDepositLamports: new anchor.BN(0),
+          guardian: null,
+        })
+        .accounts({
+          graph: graphPda,
+          authority: authority.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    }
+  });
+
+  it('keeps a task in Draft until its reward is fully funded', async () => {
+    const creator = Keypair.generate();
+    const worker = Keypair.generate();
+    await airdrop(creator, 2);
+    await airdrop(worker, 1);
+
+    const taskId = new anchor.BN(3901);
+    const [taskPda] = deriveTaskPda(graphPda, taskId);
+    const [escrowPda] = deriveEscrowPda(taskPda);
+
+    await program.methods
+      .createTask(taskParams(taskId, {
+        rewardLamports: new anchor.BN(1_000_000),
+        requireFunding: true,
+      }))
+      .accounts({
+        graph: graphPda,
+        authority: authority.publicKey,
+        creator: creator.publicKey,
+        creatorAllowlist: null,
+        parent: null,
+        task: taskPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([authority, creator])
+      .rpc();
+
+    const fund = (lamports: number) =>
+      program.methods
+        .fundTask(new anchor.BN(lamports))
+        .accounts({
+          graph: graphPda,
+          task: taskPda,
+          escrow: escrowPda,
+          deposit: null,
+          funder: creator.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([creator])
+        .rpc();
+
+    const submit = () =>
+      program.methods
+        .submitReceipt({ workHash: Array.from(Buffer.alloc(32, 39)), uri: 'ipfs://funded', contributors: [] })
+        .accounts({
+          graph: graphPda,
+          task: taskPda,
+          receipt: deriveReceiptPda(taskPda, worker.publicKey)[0],
+          agent: worker.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([worker])
+        .rpc();
+
+    await fund(400_000);
+
+    let funding: any = await program.methods.fundingStatus().accounts({ task: taskPda }).view();
+    assert.ok(funding.status.draft, 'partially funded task stays a draft');
+    assert.equal(funding.fundedLamports.toNumber(), 400_000);
+    assert.equal(funding.fullyFunded, false);
+
+    try {
+      await submit();
+      assert.fail('Draft tasks should not accept receipts');
+    } catch (err: any) {
+      assert.ok(err.message.includes('TaskNotFunded'), 'Should fail with TaskNotFunded');
+    }
+
+    try {
+      await fund(700_000);
+      assert.fail('Funding beyond the reward should be rejected');
+    } catch (err: any) {
+      assert.ok(err.message.includes('InvalidReward'), 'Should fail with InvalidReward');
+    }
+
+    await fund(600_000);
+
+    funding = await program.methods.fundingStatus().accounts({ task: taskPda }).view();
+    assert.ok(funding.status.open, 'fully funded task opens');
+    assert.equal(funding.fullyFunded, true);
+
+    await submit();
+    const taskAccount: any = await program.account.task.fetch(taskPda);
+    assert.ok(taskAccount.status.completed);
+  });
+
+  it('opens tasks without requireFunding at once, even unfunded', async () => {
+    const worker = Keypair.generate();
+    await airdrop(worker, 1);
+
+    const taskId = new anchor.BN(3902);
+    const [taskPda] = deriveTaskPda(graphPda, taskId);
+
+    await program.methods
+      .createTask(taskParams(taskId, { rewardLamports: new anchor.BN(1_000_000) }))
+      .accounts({
+        graph: graphPda,
+        authority: authority.publicKey,
+        creator: authority.publicKey,
+        creatorAllowlist: null,
+        parent: null,
+        task: taskPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    // Funding is opt-in: agents have to read the status themselves.
+    const funding: any = await program.methods.fundingStatus().accounts({ task: taskPda }).view();
+    assert.ok(funding.status.open, 'tasks without requireFunding open at creation');
+    assert.equal(funding.fundedLamports.toNumber(), 0);
+    assert.equal(funding.fullyFunded, false);
+
+    await program.methods
+      .submitReceipt({ workHash: Array.from(Buffer.alloc(32, 39)), uri: 'ipfs://unfunded', contributors: [] })
+      .accounts({
+        graph: graphPda,
+        task: taskPda,
+        receipt: deriveReceiptPda(taskPda, worker.publicKey)[0],
+        agent: worker.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([worker])
+      .rpc();
+
+    const taskAccount: any = await program.account.task.fetch(taskPda);
+    assert.ok(taskAccount.status.completed, 'unfunded Open tasks still accept receipts');
+    assert.equal(
+      await provider.connection.getAccountInfo(deriveEscrowPda(taskPda)[0]),
+      null,
+      'there is no escrow to pay the worker from'
+    );
+  });
+});
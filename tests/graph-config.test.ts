@@ -0,0 +1,97 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair } from '@solana/web3.js';
+import * as assert from 'assert';
+import { airdrop, ensureGraph } from './helpers';
+
+describe('bountygraph graph config updates', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Bountygraph as anchor.Program;
+  const authority = (provider.wallet as anchor.Wallet).payer;
+
+  const graphId = new anchor.BN(29);
+  let graphPda: anchor.web3.PublicKey;
+
+  const unchanged = {
+    maxDependenciesPerTask: null,
+    minRewardLamports: null,
+    maxRewardLamports: null,
+    closeRetentionSlots: null,
+    graphFeeBps: null,
+    requireDependentApproval: null,
+    dependencyDisputePolicy: null,
+  };
+
+  const update = (params: Record<string, any>, signer: Keypair = authority) =>
+    program.methods
+      .updateGraphConfig({ ...unchanged, ...params })
+      .accounts({ graph: graphPda, authority: signer.publicKey })
+      .signers(signer === authority ? [] : [signer])
+      .rpc();
+
+  before(async () => {
+    graphPda = await ensureGraph(graphId);
+  });
+
+  it('updates the given fields, keeps the rest and bumps config_version', async () => {
+    const before: any = await program.account.graph.fetch(graphPda);
+
+    await update({
+      maxDependenciesPerTask: 6,
+      minRewardLamports: new anchor.BN(5_000),
+      maxRewardLamports: new anchor.BN(50_000_000),
+      graphFeeBps: 250,
+    });
+
+    const after: any = await program.account.graph.fetch(graphPda);
+    assert.equal(after.maxDependenciesPerTask, 6);
+    assert.equal(after.minRewardLamports.toNumber(), 5_000);
+    assert.equal(after.maxRewardLamports.toNumber(), 50_000_000);
+    assert.equal(after.graphFeeBps, 250);
+    assert.equal(after.closeRetentionSlots.toString(), before.closeRetentionSlots.toString());
+    assert.equal(after.requireDependentApproval, before.requireDependentApproval);
+    assert.equal(after.configVersion, before.configVersion + 1);
+  });
+
+  it('only lets the graph authority update the config', async () => {
+    const stranger = Keypair.generate();
+    await airdrop(stranger, 1);
+
+    try {
+      await update({ graphFeeBps: 0 }, stranger);
+      assert.fail('A stranger must not update the graph config');
+    } catch (err: any) {
+      assert.ok(
+        err.message.includes('ConstraintHasOne') || err.message.includes('ConstraintSeeds'),
+        'Should fail the authority constraint'
+      );
+    }
+  });
+
+  it('validates the resulting bounds', async () => {
+    const rejects = async (params: Record<string, any>, error: string) => {
+      try {
+        await update(params);
+        assert.fail(`${JSON.stringify(params)} should be rejected`);
+      } catch (err: any) {
+        assert.ok(err.message.includes(error), `Should fail with ${error}`);
+      }
+    };
+
+    await rejects({ maxDependenciesPerTask: 0 }, 'InvalidConfig');
+    await rejects({ minRewardLamports: new anchor.BN(0) }, 'InvalidConfig');
+    // Checked against the stored minimum of 5_000 when only the maximum changes.
+    await rejects({ maxRewardLamports: new anchor.BN(4_000) }, 'InvalidConfig');
+    await rejects({ graphFeeBps: 1_001 }, 'FeeTooHigh');
+    await rejects(
+      { dependencyDisputePolicy: { blockWhileRaised: true, minWorkerPct: 101 } },
+      'InvalidConfig'
+    );
+
+    // Zero leaves rewards uncapped.
+    await update({ maxRewardLamports: new anchor.BN(0) });
+    const graph: any = await program.account.graph.fetch(graphPda);
+    assert.equal(graph.maxRewardLamports.toNumber(), 0);
+  });
+});
@@ -0,0 +1,145 @@
+import * as anchor from '@coral-xyz/anchor';
+import { createHash } from 'crypto';
+import { Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram } from '@solana/web3.js';
+
+// Fixtures shared by the program suites. Seeds mirror the `SEED_PREFIX` constants in
+// programs/bountygraph/src/state.rs; the program and wallet are resolved lazily from the provider
+// each suite sets up.
+
+const program = () => anchor.workspace.Bountygraph as anchor.Program;
+const wallet = () => ((anchor.getProvider() as anchor.AnchorProvider).wallet as anchor.Wallet).payer;
+
+const pda = (seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, program().programId);
+const u64 = (value: anchor.BN | number) => new anchor.BN(value).toArrayLike(Buffer, 'le', 8);
+const u16 = (value: number) => {
+  const bytes = Buffer.alloc(2);
+  bytes.writeUInt16LE(value);
+  return bytes;
+};
+
+export const deriveGraphPda = (authority: PublicKey, graphId: anchor.BN | number) =>
+  pda([Buffer.from('graph'), authority.toBuffer(), u64(graphId)]);
+
+export const deriveTaskPda = (graph: PublicKey, taskId: anchor.BN | number) =>
+  pda([Buffer.from('task'), graph.toBuffer(), u64(taskId)]);
+
+export const deriveEscrowPda = (task: PublicKey) => pda([Buffer.from('escrow'), task.toBuffer()]);
+
+export const deriveDepositPda = (task: PublicKey, funder: PublicKey) =>
+  pda([Buffer.from('deposit'), task.toBuffer(), funder.toBuffer()]);
+
+export const deriveReceiptPda = (task: PublicKey, agent: PublicKey) =>
+  pda([Buffer.from('receipt'), task.toBuffer(), agent.toBuffer()]);
+
+export const deriveMilestoneReceiptPda = (task: PublicKey, agent: PublicKey, index: number) =>
+  pda([Buffer.from('receipt'), task.toBuffer(), agent.toBuffer(), Buffer.from([index])]);
+
+export const deriveCompletionReceiptPda = (task: PublicKey, agent: PublicKey, index: number) =>
+  pda([Buffer.from('receipt'), task.toBuffer(), agent.toBuffer(), u16(index)]);
+
+export const deriveCommitmentPda = (task: PublicKey, agent: PublicKey) =>
+  pda([Buffer.from('receipt_commitment'), task.toBuffer(), agent.toBuffer()]);
+
+export const deriveDisputePda = (task: PublicKey, initiator: PublicKey) =>
+  pda([Buffer.from('dispute'), task.toBuffer(), initiator.toBuffer()]);
+
+export const deriveCreatorAllowlistPda = (graph: PublicKey, creator: PublicKey) =>
+  pda([Buffer.from('creator_allowlist'), graph.toBuffer(), creator.toBuffer()]);
+
+export const deriveTemplatePda = (authority: PublicKey, templateId: anchor.BN | number) =>
+  pda([Buffer.from('graph_template'), authority.toBuffer(), u64(templateId)]);
+
+export const deriveProtocolConfigPda = () => pda([Buffer.from('protocol_config')]);
+
+/** What `commit_receipt` stores: sha256(work_hash || uri || salt || agent). */
+export const receiptCommitment = (
+  w: { workHash: number[]; uri: string; salt: number[] },
+  agent: PublicKey
+) =>
+  Array.from(
+    createHash('sha256')
+      .update(Buffer.from(w.workHash))
+      .update(Buffer.from(w.uri, 'utf8'))
+      .update(Buffer.from(w.salt))
+      .update(agent.toBuffer())
+      .digest()
+  );
+
+export const airdrop = async (kp: Keypair, sol: number) => {
+  const connection = anchor.getProvider().connection;
+  const sig = await connection.requestAirdrop(kp.publicKey, sol * LAMPORTS_PER_SOL);
+  await connection.confirmTransaction(sig, 'confirmed');
+};
+
+// Payout instructions need the protocol config; the first suite to run creates it fee-free.
+export const ensureProtocolConfig = async (): Promise<PublicKey> => {
+  const [protocolConfig] = deriveProtocolConfigPda();
+  try {
+    const config: any = await program().account.protocolConfig.fetch(protocolConfig);
+    return config.treasury;
+  } catch {
+    const [programData] = PublicKey.findProgramAddressSync(
+      [program().programId.toBuffer()],
+      new PublicKey('BPFLoaderUpgradeab1e11111111111111111111111')
+    );
+    await program()
+      .methods.initializeProtocolConfig({ treasury: wallet().publicKey, feeBps: 0 })
+      .accounts({
+        protocolConfig,
+        program: program().programId,
+        programData,
+        admin: wallet().publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    return wallet().publicKey;
+  }
+};
+
+/** Create graph `graphId` for the provider wallet unless an earlier run already has. */
+export const ensureGraph = async (graphId: anchor.BN | number, params: Record<string, any> = {}) => {
+  const [graph] = deriveGraphPda(wallet().publicKey, graphId);
+  try {
+    await program().account.graph.fetch(graph);
+  } catch {
+    await program()
+      .methods.initializeGraph({
+        graphId: new anchor.BN(graphId),
+        maxDependenciesPerTask: 10,
+        creationPolicy: { authorityOnly: {} },
+        creationDepositLamports: new anchor.BN(0),
+        guardian: null,
+        ...params,
+      })
+      .accounts({ graph, authority: wallet().publicKey, systemProgram: SystemProgram.programId })
+      .rpc();
+  }
+  return graph;
+};
+
+/** `CreateTaskParams` with every optional feature off; `overrides` set what a test exercises. */
+export const taskParams = (taskId: anchor.BN | number, overrides: Record<string, any> = {}): Record<string, any> => ({
+  taskId: new anchor.BN(taskId),
+  rewardLamports: new anchor.BN(100_000),
+  dependencies: [],
+  dependencyGroups: [],
+  externalDependencies: [],
+  claimDurationSlots: new anchor.BN(0),
+  competitionDeadlineSlot: new anchor.BN(0),
+  milestones: [],
+  vesting: null,
+  crowdfunded: false,
+  requireFunding: false,
+  epic: false,
+  parentTaskId: null,
+  specUri: '',
+  specHash: Array(32).fill(0),
+  category: 0,
+  tags: [],
+  maxCompletions: 0,
+  recurrenceIntervalSlots: new anchor.BN(0),
+  recurrenceEndSlot: new anchor.BN(0),
+  verifiers: [],
+  verifierThreshold: 0,
+  ...overrides,
+});
@@ -0,0 +1,121 @@
+import * as anchor from '@coral-xyz/anchor';
+import { PublicKey } from '@solana/web3.js';
+import * as assert from 'assert';
+import { deriveEscrowPda } from './helpers';
+
+// v0 accounts preloaded by Anchor.toml from tests/fixtures (see scripts/gen-v0-fixtures.py).
+const V0 = {
+  graph: new PublicKey('AENV6YzNikQHJrDX4okHbfAhhMepYM6mtqYZHbavu8Tt'),
+  task: new PublicKey('ARdztvBuw1nZZejY22JTcqxFj3GHQqg5XSjksVcjAGFR'),
+  // The task's escrow PDA, holding its 1_000_000 lamport reward above rent.
+  escrow: new PublicKey('6X2964w9gjmnbuxKtangN5VMc3Jg1CK8q37sfWBTDKFL'),
+  receipt: new PublicKey('J9oQ8LTgQJ15HWofZiDx4vLeBrjQsmiZ7m5wKc6AYNi4'),
+  dispute: new PublicKey('CBLujHHagU3Qxi8u2hvJsFWkrS7SwRAwBxdZJsaStpeW'),
+};
+const AUTHORITY = new PublicKey('435MVbMPowjw3DRPtvw5CCSePxZdZtU9XQp41xNNqm5m');
+const CREATOR = new PublicKey('9FKykaVCotbNXhgxyzWfQ99XwGnApiBcHo4TgUZq7zNq');
+const WORKER = new PublicKey('5YrUFwiPcqFJpReVzqPaSfA7gyEMYN6PsG4F7rNDYNXW');
+
+describe('bountygraph v0 account migrations', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Bountygraph as anchor.Program;
+  const payer = (provider.wallet as anchor.Wallet).payer;
+
+  // migrate_task also reads the task's escrow PDA for its funding.
+  const migrate = (method: string, account: PublicKey) =>
+    program.methods[method]()
+      .accounts({
+        account,
+        ...(method === 'migrateTask' ? { escrow: deriveEscrowPda(account)[0] } : {}),
+        payer: payer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+  const migrateOnce = async (method: string, account: PublicKey) => {
+    await migrate(method, account);
+    try {
+      await migrate(method, account);
+      assert.fail('A migrated account cannot be migrated again');
+    } catch (err: any) {
+      assert.ok(err.message.includes('AlreadyMigrated'), 'Should fail with AlreadyMigrated');
+    }
+  };
+
+  it('upgrades a v0 graph to the legacy graph id', async () => {
+    await assert.rejects(program.account.graph.fetch(V0.graph), 'v0 graphs do not decode as current');
+    await migrateOnce('migrateGraph', V0.graph);
+
+    const graph: any = await program.account.graph.fetch(V0.graph);
+    assert.ok(graph.authority.equals(AUTHORITY));
+    assert.equal(graph.graphId.toString(), '18446744073709551615');
+    assert.equal(graph.maxDependenciesPerTask, 8);
+    assert.equal(graph.taskCount.toNumber(), 1);
+    assert.ok(graph.creationPolicy.authorityOnly);
+    assert.equal(graph.bump, 254);
+    assert.equal(graph.version, 1);
+  });
+
+  it('upgrades a v0 task, keeping its outcome and dependencies', async () => {
+    await assert.rejects(program.account.task.fetch(V0.task), 'handlers need the task migrated first');
+    await migrateOnce('migrateTask', V0.task);
+
+    const task: any = await program.account.task.fetch(V0.task);
+    assert.ok(task.graph.equals(V0.graph));
+    assert.equal(task.taskId.toNumber(), 7);
+    assert.ok(task.creator.equals(CREATOR));
+    assert.equal(task.rewardLamports.toNumber(), 1_000_000);
+    assert.ok(task.status.completed);
+    assert.ok(task.disputeStatus.raised);
+    assert.deepEqual(task.dependencies.map((d: anchor.BN) => d.toNumber()), [3, 5]);
+    assert.ok(task.completedBy.equals(WORKER));
+    assert.ok(task.disputedBy.equals(CREATOR));
+    assert.equal(task.configVersion, 0);
+    assert.equal(task.fundedLamports.toNumber(), 1_000_000, 'funding is read from the escrow');
+    assert.equal(task.depositorCount, 0);
+    assert.equal(task.bump, 253);
+    assert.equal(task.version, 1);
+
+    const info = await provider.connection.getAccountInfo(V0.task);
+    assert.ok(
+      info!.lamports >= (await provider.connection.getMinimumBalanceForRentExemption(info!.data.length)),
+      'the payer tops the task up to rent exemption at its new size'
+    );
+  });
+
+  it('upgrades v0 escrows, receipts and disputes', async () => {
+    await migrateOnce('migrateEscrow', V0.escrow);
+    const escrow: any = await program.account.escrow.fetch(V0.escrow);
+    assert.ok(escrow.task.equals(V0.task));
+    assert.equal(escrow.bump, 254);
+    assert.equal(escrow.version, 1);
+
+    await migrateOnce('migrateReceipt', V0.receipt);
+    const receipt: any = await program.account.receipt.fetch(V0.receipt);
+    assert.ok(receipt.agent.equals(WORKER));
+    assert.equal(receipt.uri, 'ipfs://v0-receipt');
+    assert.equal(receipt.submittedAtSlot.toNumber(), 110);
+    assert.equal(receipt.payoutBps, 0);
+    assert.equal(receipt.milestoneIndex, null);
+    assert.equal(receipt.version, 1);
+
+    await migrateOnce('migrateDispute', V0.dispute);
+    const dispute: any = await program.account.dispute.fetch(V0.dispute);
+    assert.ok(dispute.raisedBy.equals(CREATOR));
+    assert.equal(dispute.reason, 'Work does not match the spec');
+    assert.ok(dispute.status.raised);
+    assert.equal(dispute.specRevision, 0);
+    assert.equal(dispute.version, 1);
+  });
+
+  it('rejects accounts of the wrong type', async () => {
+    try {
+      await migrate('migrateTask', V0.escrow);
+      assert.fail('An escrow is not a task');
+    } catch (err: any) {
+      assert.ok(err.message.includes('InvalidAccountLayout'), 'Should fail with InvalidAccountLayout');
+    }
+  });
+});
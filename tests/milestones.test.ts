@@ -0,0 +1,221 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair } from '@solana/web3.js';
+import * as assert from 'assert';
+import {
+  deriveGraphPda,
+  deriveTaskPda,
+  deriveEscrowPda,
+  deriveMilestoneReceiptPda,
+  deriveProtocolConfigPda,
+  airdrop,
+  ensureProtocolConfig,
+  taskParams,
+} from './helpers';
+
+describe('bountygraph milestone payouts', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Bountygraph as anchor.Program;
+  const authority = (provider.wallet as anchor.Wallet).payer;
+
+  const graphId = new anchor.BN(0);
+
+  const [protocolConfigPda] = deriveProtocolConfigPda();
+
+  const [graphPda] = deriveGraphPda(authority.publicKey, graphId);
+
+  before(async () => {
+    try {
+      await program.account.graph.fetch(graphPda);
+    } catch {
+      await program.methods
+        .initializeGraph({
+          graphId,
+          maxDependenciesPerTask: 10,
+          creationPolicy: { authorityOnly: {} },
+          creationDepositLamports: new anchor.BN(0),
+          guardian: null,
+        })
+        .accounts({
+          graph: graphPda,
+          authority: authority.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    }
+  });
+
+  it('pays each milestone on approval and completes the task on the last one', async () => {
+    const creator = Keypair.generate();
+    const worker = Keypair.generate();
+    await airdrop(creator, 2);
+    await airdrop(worker, 1);
+
+    const taskId = new anchor.BN(3501);
+    const [taskPda] = deriveTaskPda(graphPda, taskId);
+    const [escrowPda] = deriveEscrowPda(taskPda);
+
+    await program.methods
+      .createTask(taskParams(taskId, {
+        rewardLamports: new anchor.BN(1_000_000),
+        milestones: [new anchor.BN(400_000), new anchor.BN(600_000)],
+      }))
+      .accounts({
+        graph: graphPda,
+        authority: authority.publicKey,
+        creator: creator.publicKey,
+        creatorAllowlist: null,
+        parent: null,
+        task: taskPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([authority, creator])
+      .rpc();
+
+    await program.methods
+      .fundTask(new anchor.BN(1_000_000))
+      .accounts({
+        graph: graphPda,
+        task: taskPda,
+        escrow: escrowPda,
+        deposit: null,
+        funder: creator.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([creator])
+      .rpc();
+
+    const submit = (index: number) =>
+      program.methods
+        .submitMilestone({ index, workHash: Array.from(Buffer.alloc(32, 35 + index)), uri: `ipfs://milestone-${index}` })
+        .accounts({
+          graph: graphPda,
+          task: taskPda,
+          receipt: deriveMilestoneReceiptPda(taskPda, worker.publicKey, index)[0],
+          agent: worker.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([worker])
+        .rpc();
+
+    const treasury = await ensureProtocolConfig();
+    const approve = (index: number) =>
+      program.methods
+        .approveMilestone(index)
+        .accounts({
+          graph: graphPda,
+          task: taskPda,
+          escrow: escrowPda,
+          protocolConfig: protocolConfigPda,
+          treasury,
+          graphAuthority: authority.publicKey,
+          creator: creator.publicKey,
+          worker: worker.publicKey,
+        })
+        .signers([creator])
+        .rpc();
+
+    try {
+      await submit(1);
+      assert.fail('Milestones must be delivered in order');
+    } catch (err: any) {
+      assert.ok(err.message.includes('MilestoneNotReady'), 'Should fail with MilestoneNotReady');
+    }
+
+    await submit(0);
+    const workerBefore = await provider.connection.getBalance(worker.publicKey, 'confirmed');
+    await approve(0);
+    const workerAfter = await provider.connection.getBalance(worker.publicKey, 'confirmed');
+    assert.equal(workerAfter - workerBefore, 400_000);
+
+    let taskAccount: any = await program.account.task.fetch(taskPda);
+    assert.ok(taskAccount.status.open, 'task stays open until the final milestone is approved');
+    assert.ok(taskAccount.milestones[0].status.paid);
+
+    await submit(1);
+    await approve(1);
+
+    taskAccount = await program.account.task.fetch(taskPda);
+    assert.ok(taskAccount.status.completed);
+    assert.equal(taskAccount.completedBy.toString(), worker.publicKey.toString());
+    assert.equal(await provider.connection.getBalance(escrowPda, 'confirmed'), 0);
+  });
+
+  it('returns a rejected milestone to pending and blocks cancel while one awaits approval', async () => {
+    const creator = Keypair.generate();
+    const worker = Keypair.generate();
+    await airdrop(creator, 2);
+    await airdrop(worker, 1);
+
+    const taskId = new anchor.BN(3502);
+    const [taskPda] = deriveTaskPda(graphPda, taskId);
+    const [receiptPda] = deriveMilestoneReceiptPda(taskPda, worker.publicKey, 0);
+
+    await program.methods
+      .createTask(taskParams(taskId, {
+        rewardLamports: new anchor.BN(1_000_000),
+        milestones: [new anchor.BN(400_000), new anchor.BN(600_000)],
+      }))
+      .accounts({
+        graph: graphPda,
+        authority: authority.publicKey,
+        creator: creator.publicKey,
+        creatorAllowlist: null,
+        parent: null,
+        task: taskPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([authority, creator])
+      .rpc();
+
+    const submit = () =>
+      program.methods
+        .submitMilestone({ index: 0, workHash: Array.from(Buffer.alloc(32, 35)), uri: 'ipfs://milestone-0' })
+        .accounts({
+          graph: graphPda,
+          task: taskPda,
+          receipt: receiptPda,
+          agent: worker.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([worker])
+        .rpc();
+    const cancel = () =>
+      program.methods
+        .cancelTask()
+        .accounts({ task: taskPda, escrow: null, parent: null, creator: creator.publicKey })
+        .signers([creator])
+        .rpc();
+
+    await submit();
+    try {
+      await cancel();
+      assert.fail('Cancel must wait for the submitted milestone to be reviewed');
+    } catch (err: any) {
+      assert.ok(err.message.includes('PendingSubmissions'), 'Should fail with PendingSubmissions');
+    }
+
+    await program.methods
+      .rejectMilestone(0)
+      .accounts({ task: taskPda, receipt: receiptPda, creator: creator.publicKey, worker: worker.publicKey })
+      .signers([creator])
+      .rpc();
+
+    let taskAccount: any = await program.account.task.fetch(taskPda);
+    assert.ok(taskAccount.milestones[0].status.pending, 'rejected milestone is pending again');
+    assert.equal(taskAccount.milestones[0].worker, null);
+    assert.equal(await provider.connection.getAccountInfo(receiptPda), null, 'receipt is closed on rejection');
+
+    // The worker can resubmit, and once rejected again the creator can cancel.
+    await submit();
+    await program.methods
+      .rejectMilestone(0)
+      .accounts({ task: taskPda, receipt: receiptPda, creator: creator.publicKey, worker: worker.publicKey })
+      .signers([creator])
+      .rpc();
+    await cancel();
+    taskAccount = await program.account.task.fetch(taskPda);
+    assert.ok(taskAccount.status.cancelled);
+  });
+});
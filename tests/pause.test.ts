@@ -0,0 +1,233 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import * as assert from 'assert';
+import {
+  deriveTaskPda,
+  deriveEscrowPda,
+  deriveDepositPda,
+  deriveReceiptPda,
+  deriveDisputePda,
+  deriveProtocolConfigPda,
+  airdrop,
+  ensureGraph,
+  ensureProtocolConfig,
+  taskParams,
+} from './helpers';
+
+describe('bountygraph emergency pause', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Bountygraph as anchor.Program;
+  const authority = (provider.wallet as anchor.Wallet).payer;
+
+  // Dedicated graph so pausing never interferes with the other suites.
+  let graphPda: PublicKey;
+  let treasury: PublicKey;
+  const [protocolConfigPda] = deriveProtocolConfigPda();
+
+  const guardian = Keypair.generate();
+  const creator = Keypair.generate();
+  const worker = Keypair.generate();
+  const backer = Keypair.generate();
+
+  const taskPda = (taskId: number) => deriveTaskPda(graphPda, taskId)[0];
+  const escrowPda = (taskId: number) => deriveEscrowPda(taskPda(taskId))[0];
+
+  const createTask = (creator: Keypair, taskId: anchor.BN | number, overrides = {}) =>
+    program.methods
+      .createTask(taskParams(taskId, { rewardLamports: new anchor.BN(1_000_000), ...overrides }))
+      .accounts({
+        graph: graphPda,
+        authority: authority.publicKey,
+        creator: creator.publicKey,
+        creatorAllowlist: null,
+        parent: null,
+        task: deriveTaskPda(graphPda, taskId)[0],
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([authority, creator])
+      .rpc();
+
+  const fund = (taskId: number, funder: Keypair, lamports: number) =>
+    program.methods
+      .fundTask(new anchor.BN(lamports))
+      .accounts({
+        graph: graphPda,
+        task: taskPda(taskId),
+        escrow: escrowPda(taskId),
+        deposit: funder === creator ? null : deriveDepositPda(taskPda(taskId), funder.publicKey)[0],
+        funder: funder.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([funder])
+      .rpc();
+
+  const submit = (taskId: number) =>
+    program.methods
+      .submitReceipt({ workHash: Array.from(Buffer.alloc(32, 28)), uri: 'ipfs://paused', contributors: [] })
+      .accounts({
+        graph: graphPda,
+        task: taskPda(taskId),
+        receipt: deriveReceiptPda(taskPda(taskId), worker.publicKey)[0],
+        agent: worker.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([worker])
+      .rpc();
+
+  const expectPaused = async (call: Promise<unknown>, why: string) => {
+    try {
+      await call;
+      assert.fail(why);
+    } catch (err: any) {
+      assert.ok(err.message.includes('GraphPaused'), 'Should fail with GraphPaused');
+    }
+  };
+
+  before(async () => {
+    await airdrop(guardian, 1);
+    await airdrop(creator, 5);
+    await airdrop(worker, 1);
+    await airdrop(backer, 1);
+    graphPda = await ensureGraph(28, { guardian: guardian.publicKey });
+    treasury = await ensureProtocolConfig();
+
+    // Set up before the pause: 2803 completed and unclaimed, 2804 completed and disputed,
+    // 2805 open with a backer deposit, 2806 open and unfunded.
+    for (const taskId of [2803, 2804]) {
+      await createTask(creator, taskId);
+      await fund(taskId, creator, 1_000_000);
+      await submit(taskId);
+    }
+    await program.methods
+      .disputeTask({ reason: 'Work does not match the spec' })
+      .accounts({
+        task: taskPda(2804),
+        dispute: deriveDisputePda(taskPda(2804), creator.publicKey)[0],
+        initiator: creator.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([creator])
+      .rpc();
+
+    await createTask(creator, 2805, { crowdfunded: true });
+    await fund(2805, creator, 600_000);
+    await fund(2805, backer, 400_000);
+
+    await createTask(creator, 2806);
+  });
+
+  it('guardian can pause; paused graph rejects task creation', async () => {
+    const creator = Keypair.generate();
+    await airdrop(creator, 1);
+
+    await program.methods
+      .pauseGraph(7)
+      .accounts({ graph: graphPda, signer: guardian.publicKey })
+      .signers([guardian])
+      .rpc();
+
+    const graphAccount: any = await program.account.graph.fetch(graphPda);
+    assert.equal(graphAccount.paused, true);
+    assert.equal(graphAccount.pauseReason, 7);
+
+    await expectPaused(
+      createTask(creator, new anchor.BN(2801)),
+      'Should have rejected create_task while paused'
+    );
+  });
+
+  it('paused graph rejects funding, submissions and reward claims', async () => {
+    await expectPaused(fund(2806, creator, 1_000_000), 'Should have rejected fund_task while paused');
+    await expectPaused(submit(2806), 'Should have rejected submit_receipt while paused');
+    await expectPaused(
+      program.methods
+        .claimReward()
+        .accounts({
+          graph: graphPda,
+          task: taskPda(2803),
+          escrow: escrowPda(2803),
+          protocolConfig: protocolConfigPda,
+          treasury,
+          graphAuthority: authority.publicKey,
+          receipt: null,
+          agent: worker.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([worker])
+        .rpc(),
+      'Should have rejected claim_reward while paused'
+    );
+  });
+
+  it('paused graph still resolves disputes, cancels tasks and refunds deposits', async () => {
+    // Resolution only moves escrowed funds to their owners, so it stays open during a pause.
+    await program.methods
+      .resolveDispute({ creatorPct: 50, workerPct: 50 })
+      .accounts({
+        graph: graphPda,
+        authority: authority.publicKey,
+        task: taskPda(2804),
+        dispute: deriveDisputePda(taskPda(2804), creator.publicKey)[0],
+        escrow: escrowPda(2804),
+        protocolConfig: protocolConfigPda,
+        treasury,
+        creator: creator.publicKey,
+        worker: worker.publicKey,
+        receipt: null,
+      })
+      .rpc();
+    const resolved: any = await program.account.task.fetch(taskPda(2804));
+    assert.ok(resolved.disputeStatus.resolved, 'Dispute should be resolved while paused');
+
+    await program.methods
+      .cancelTask()
+      .accounts({ task: taskPda(2805), escrow: escrowPda(2805), parent: null, creator: creator.publicKey })
+      .signers([creator])
+      .rpc();
+    const cancelled: any = await program.account.task.fetch(taskPda(2805));
+    assert.ok(cancelled.status.cancelled, 'Task should be cancelled while paused');
+
+    const [depositPda] = deriveDepositPda(taskPda(2805), backer.publicKey);
+    const backerBefore = await provider.connection.getBalance(backer.publicKey, 'confirmed');
+    await program.methods
+      .refundDeposit()
+      .accounts({
+        task: taskPda(2805),
+        escrow: escrowPda(2805),
+        deposit: depositPda,
+        funder: backer.publicKey,
+        creator: creator.publicKey,
+      })
+      .rpc();
+    const backerAfter = await provider.connection.getBalance(backer.publicKey, 'confirmed');
+    assert.ok(backerAfter - backerBefore >= 400_000, 'Backer should be refunded while paused');
+    assert.equal(await provider.connection.getAccountInfo(depositPda), null, 'deposit is closed');
+  });
+
+  it('guardian cannot unpause; authority can', async () => {
+    try {
+      await program.methods
+        .unpauseGraph()
+        .accounts({ graph: graphPda, authority: guardian.publicKey })
+        .signers([guardian])
+        .rpc();
+      assert.fail('Guardian should not be able to unpause');
+    } catch (err: any) {
+      assert.ok(/ConstraintHasOne|ConstraintSeeds/.test(err.message), 'Guardian unpause must be rejected');
+    }
+
+    await program.methods
+      .unpauseGraph()
+      .accounts({ graph: graphPda, authority: authority.publicKey })
+      .rpc();
+
+    const creator = Keypair.generate();
+    await airdrop(creator, 1);
+    await createTask(creator, new anchor.BN(2802));
+
+    const graphAccount: any = await program.account.graph.fetch(graphPda);
+    assert.equal(graphAccount.paused, false);
+  });
+});
@@ -0,0 +1,268 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import * as assert from 'assert';
+import {
+  deriveGraphPda,
+  deriveTaskPda,
+  deriveEscrowPda,
+  deriveCompletionReceiptPda,
+  deriveProtocolConfigPda,
+  airdrop,
+  ensureProtocolConfig,
+  taskParams,
+} from './helpers';
+
+describe('bountygraph recurring tasks', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Bountygraph as anchor.Program;
+  const authority = (provider.wallet as anchor.Wallet).payer;
+
+  const graphId = new anchor.BN(48);
+
+  const [protocolConfigPda] = deriveProtocolConfigPda();
+
+  const [graphPda] = deriveGraphPda(authority.publicKey, graphId);
+
+  before(async () => {
+    try {
+      await program.account.graph.fetch(graphPda);
+    } catch {
+      await program.methods
+        .initializeGraph({
+          graphId,
+          maxDependenciesPerTask: 10,
+          creationPolicy: { authorityOnly: {} },
+          creationDepositLamports: new anchor.BN(0),
+          guardian: null,
+        })
+        .accounts({
+          graph: graphPda,
+          authority: authority.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    }
+  });
+
+  const createRepeatableTask = async (
+    creator: Keypair,
+    taskId: number,
+    rewardLamports: number,
+    schedule: { maxCompletions: number; recurrenceIntervalSlots?: number; recurrenceEndSlot?: number }
+  ) => {
+    const [taskPda] = deriveTaskPda(graphPda, new anchor.BN(taskId));
+    await program.methods
+      .createTask(taskParams(new anchor.BN(taskId), {
+        rewardLamports: new anchor.BN(rewardLamports),
+        maxCompletions: schedule.maxCompletions,
+        recurrenceIntervalSlots: new anchor.BN(schedule.recurrenceIntervalSlots ?? 0),
+        recurrenceEndSlot: new anchor.BN(schedule.recurrenceEndSlot ?? 0),
+      }))
+      .accounts({
+        graph: graphPda,
+        authority: authority.publicKey,
+        creator: creator.publicKey,
+        creatorAllowlist: null,
+        parent: null,
+        task: taskPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([authority, creator])
+      .rpc();
+
+    await program.methods
+      .fundTask(new anchor.BN(rewardLamports))
+      .accounts({
+        graph: graphPda,
+        task: taskPda,
+        escrow: deriveEscrowPda(taskPda)[0],
+        deposit: null,
+        funder: creator.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([creator])
+      .rpc();
+    return taskPda;
+  };
+
+  const submitCompletion = async (taskPda: PublicKey, agent: Keypair) => {
+    const task: any = await program.account.task.fetch(taskPda);
+    const [receipt] = deriveCompletionReceiptPda(taskPda, agent.publicKey, task.completionCount);
+    await program.methods
+      .submitCompletion({ workHash: Array.from(Buffer.alloc(32, 48)), uri: `ipfs://run-${task.completionCount}` })
+      .accounts({
+        graph: graphPda,
+        task: taskPda,
+        receipt,
+        agent: agent.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+    return receipt;
+  };
+
+  const approveCompletion = async (taskPda: PublicKey, creator: Keypair, worker: PublicKey) => {
+    const treasury = await ensureProtocolConfig();
+    const task: any = await program.account.task.fetch(taskPda);
+    await program.methods
+      .approveCompletion()
+      .accounts({
+        graph: graphPda,
+        task: taskPda,
+        receipt: deriveCompletionReceiptPda(taskPda, worker, task.completionCount)[0],
+        escrow: deriveEscrowPda(taskPda)[0],
+        protocolConfig: protocolConfigPda,
+        treasury,
+        graphAuthority: authority.publicKey,
+        creator: creator.publicKey,
+        worker,
+      })
+      .signers([creator])
+      .rpc();
+  };
+
+  const rejectCompletion = async (taskPda: PublicKey, creator: Keypair, worker: PublicKey) => {
+    const task: any = await program.account.task.fetch(taskPda);
+    await program.methods
+      .rejectCompletion()
+      .accounts({
+        task: taskPda,
+        receipt: deriveCompletionReceiptPda(taskPda, worker, task.completionCount)[0],
+        creator: creator.publicKey,
+        worker,
+      })
+      .signers([creator])
+      .rpc();
+  };
+
+  it('pays a slice per approved completion and completes the task with the last one', async () => {
+    const creator = Keypair.generate();
+    const alice = Keypair.generate();
+    const bob = Keypair.generate();
+    await airdrop(creator, 2);
+    await airdrop(alice, 1);
+    await airdrop(bob, 1);
+
+    const taskPda = await createRepeatableTask(creator, 4801, 900_000, { maxCompletions: 3 });
+    const [escrowPda] = deriveEscrowPda(taskPda);
+
+    const escrowBefore = await provider.connection.getBalance(escrowPda, 'confirmed');
+    const first = await submitCompletion(taskPda, alice);
+    assert.equal(await provider.connection.getBalance(escrowPda, 'confirmed'), escrowBefore);
+
+    let receipt: any = await program.account.receipt.fetch(first);
+    assert.equal(receipt.completionIndex, 0);
+    assert.ok(!receipt.claimed, 'completions are not paid until approved');
+
+    try {
+      await submitCompletion(taskPda, bob);
+      assert.fail('Only one completion may await approval');
+    } catch (err: any) {
+      assert.ok(err.message.includes('CompletionPending'), 'Should fail with CompletionPending');
+    }
+
+    await approveCompletion(taskPda, creator, alice.publicKey);
+    assert.equal(escrowBefore - (await provider.connection.getBalance(escrowPda, 'confirmed')), 300_000);
+    receipt = await program.account.receipt.fetch(first);
+    assert.ok(receipt.claimed);
+
+    // A rejected completion is closed back to its agent and can be resubmitted.
+    const rejected = await submitCompletion(taskPda, bob);
+    await rejectCompletion(taskPda, creator, bob.publicKey);
+    assert.equal(await provider.connection.getAccountInfo(rejected), null);
+    let task: any = await program.account.task.fetch(taskPda);
+    assert.equal(task.completionCount, 1);
+    assert.equal(task.pendingCompletionAgent, null);
+
+    await submitCompletion(taskPda, bob);
+    await approveCompletion(taskPda, creator, bob.publicKey);
+    task = await program.account.task.fetch(taskPda);
+    assert.ok(task.status.open, 'task stays open until the last completion');
+    assert.equal(task.completionCount, 2);
+
+    // The same agent may complete the task again; each completion has its own receipt.
+    await submitCompletion(taskPda, alice);
+    await approveCompletion(taskPda, creator, alice.publicKey);
+    task = await program.account.task.fetch(taskPda);
+    assert.ok(task.status.completed);
+    assert.equal(task.completionCount, 3);
+    assert.equal(task.completedBy.toString(), alice.publicKey.toString());
+    assert.equal(await provider.connection.getBalance(escrowPda, 'confirmed'), 0);
+
+    try {
+      await submitCompletion(taskPda, bob);
+      assert.fail('Exhausted task should not accept completions');
+    } catch (err: any) {
+      assert.ok(err.message.includes('TaskNotOpen'), 'Should fail with TaskNotOpen');
+    }
+  });
+
+  it('only pays completions the creator approves', async () => {
+    const creator = Keypair.generate();
+    const agent = Keypair.generate();
+    const stranger = Keypair.generate();
+    await airdrop(creator, 2);
+    await airdrop(agent, 1);
+    await airdrop(stranger, 1);
+
+    const taskPda = await createRepeatableTask(creator, 4804, 200_000, { maxCompletions: 2 });
+    const [escrowPda] = deriveEscrowPda(taskPda);
+    await submitCompletion(taskPda, agent);
+    const agentBefore = await provider.connection.getBalance(agent.publicKey, 'confirmed');
+
+    try {
+      await approveCompletion(taskPda, stranger, agent.publicKey);
+      assert.fail('Only the creator can approve a completion');
+    } catch (err: any) {
+      assert.ok(err.message.includes('ConstraintHasOne'), 'Should fail with ConstraintHasOne');
+    }
+
+    try {
+      await program.methods
+        .cancelTask()
+        .accounts({ task: taskPda, escrow: escrowPda, parent: null, creator: creator.publicKey })
+        .signers([creator])
+        .rpc();
+      assert.fail('A task with a pending completion cannot be cancelled');
+    } catch (err: any) {
+      assert.ok(err.message.includes('CompletionPending'), 'Should fail with CompletionPending');
+    }
+
+    assert.equal(await provider.connection.getBalance(agent.publicKey, 'confirmed'), agentBefore);
+  });
+
+  it('enforces the recurrence interval between completions', async () => {
+    const creator = Keypair.generate();
+    const agent = Keypair.generate();
+    await airdrop(creator, 2);
+    await airdrop(agent, 1);
+
+    const taskPda = await createRepeatableTask(creator, 4802, 400_000, {
+      maxCompletions: 4,
+      recurrenceIntervalSlots: 10_000,
+    });
+
+    await submitCompletion(taskPda, agent);
+    await approveCompletion(taskPda, creator, agent.publicKey);
+    try {
+      await submitCompletion(taskPda, agent);
+      assert.fail('Second completion should wait for the interval');
+    } catch (err: any) {
+      assert.ok(err.message.includes('CompletionNotDue'), 'Should fail with CompletionNotDue');
+    }
+  });
+
+  it('rejects schedules without a completion count', async () => {
+    const creator = Keypair.generate();
+    await airdrop(creator, 1);
+    try {
+      await createRepeatableTask(creator, 4803, 100_000, { maxCompletions: 0, recurrenceIntervalSlots: 100 });
+      assert.fail('A recurrence interval needs max_completions');
+    } catch (err: any) {
+      assert.ok(err.message.includes('InvalidConfig'), 'Should fail with InvalidConfig');
+    }
+  });
+});
@@ -0,0 +1,204 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import * as assert from 'assert';
+import {
+  deriveGraphPda,
+  deriveTaskPda,
+  deriveEscrowPda,
+  deriveDepositPda,
+  airdrop,
+  taskParams,
+} from './helpers';
+
+describe('bountygraph reward increases', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Bountygraph as anchor.Program;
+  const authority = (provider.wallet as anchor.Wallet).payer;
+
+  const graphId = new anchor.BN(0);
+
+  const [graphPda] = deriveGraphPda(authority.publicKey, graphId);
+
+  before(async () => {
+    try {
+      await program.account.graph.fetch(graphPda);
+    } catch {
+      await program.methods
+        .initializeGraph({
+          graphId,
+          maxDependenciesPerTask: 10,
+          creationPolicy: { authorityOnly: {} },
+          creationDepositLamports: new anchor.BN(0),
+          guardian: null,
+        })
+        .accounts({
+          graph: graphPda,
+          authority: authority.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    }
+  });
+
+  const createFundedTask = async (taskId: anchor.BN, creator: Keypair, crowdfunded: boolean) => {
+    const [taskPda] = deriveTaskPda(graphPda, taskId);
+    const [escrowPda] = deriveEscrowPda(taskPda);
+
+    await program.methods
+      .createTask(taskParams(taskId, { rewardLamports: new anchor.BN(1_000_000), crowdfunded }))
+      .accounts({
+        graph: graphPda,
+        authority: authority.publicKey,
+        creator: creator.publicKey,
+        creatorAllowlist: null,
+        parent: null,
+        task: taskPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([authority, creator])
+      .rpc();
+
+    await program.methods
+      .fundTask(new anchor.BN(1_000_000))
+      .accounts({
+        graph: graphPda,
+        task: taskPda,
+        escrow: escrowPda,
+        deposit: null,
+        funder: creator.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([creator])
+      .rpc();
+
+    return { taskPda, escrowPda };
+  };
+
+  const increaseReward = async (taskPda: PublicKey, escrowPda: PublicKey, funder: Keypair, lamports: number) => {
+    const { creator }: any = await program.account.task.fetch(taskPda);
+    return program.methods
+      .increaseReward(new anchor.BN(lamports))
+      .accounts({
+        graph: graphPda,
+        task: taskPda,
+        escrow: escrowPda,
+        deposit: funder.publicKey.equals(creator) ? null : deriveDepositPda(taskPda, funder.publicKey)[0],
+        funder: funder.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([funder])
+      .rpc();
+  };
+
+  it('lets the creator raise the reward of an open task', async () => {
+    const creator = Keypair.generate();
+    const stranger = Keypair.generate();
+    await airdrop(creator, 2);
+    await airdrop(stranger, 1);
+
+    const { taskPda, escrowPda } = await createFundedTask(new anchor.BN(3801), creator, false);
+
+    try {
+      await increaseReward(taskPda, escrowPda, stranger, 500_000);
+      assert.fail('Only the creator may top up a task that is not crowdfunded');
+    } catch (err: any) {
+      assert.ok(err.message.includes('UnauthorizedFunder'), 'Should fail with UnauthorizedFunder');
+    }
+
+    const escrowBefore = await provider.connection.getBalance(escrowPda, 'confirmed');
+    await increaseReward(taskPda, escrowPda, creator, 500_000);
+
+    const taskAccount: any = await program.account.task.fetch(taskPda);
+    assert.equal(taskAccount.rewardLamports.toNumber(), 1_500_000);
+    assert.equal(await provider.connection.getBalance(escrowPda, 'confirmed'), escrowBefore + 500_000);
+  });
+
+  it('accepts top-ups from any funder on a crowdfunded task', async () => {
+    const creator = Keypair.generate();
+    const backer = Keypair.generate();
+    await airdrop(creator, 2);
+    await airdrop(backer, 1);
+
+    const { taskPda, escrowPda } = await createFundedTask(new anchor.BN(3802), creator, true);
+
+    let event: any = null;
+    const listener = program.addEventListener('RewardIncreased', (e: any) => {
+      event = e;
+    });
+    await increaseReward(taskPda, escrowPda, backer, 250_000);
+    await new Promise((resolve) => setTimeout(resolve, 1_000));
+    await program.removeEventListener(listener);
+
+    const taskAccount: any = await program.account.task.fetch(taskPda);
+    assert.equal(taskAccount.rewardLamports.toNumber(), 1_250_000);
+    assert.ok(event, 'RewardIncreased should be emitted');
+    assert.ok(event.funder.equals(backer.publicKey));
+    assert.equal(event.newRewardLamports.toNumber(), 1_250_000);
+  });
+
+  it('refunds other funders their share when a crowdfunded task is cancelled', async () => {
+    const creator = Keypair.generate();
+    const backer = Keypair.generate();
+    await airdrop(creator, 2);
+    await airdrop(backer, 1);
+
+    const { taskPda, escrowPda } = await createFundedTask(new anchor.BN(3803), creator, true);
+    const [depositPda] = deriveDepositPda(taskPda, backer.publicKey);
+
+    try {
+      await program.methods
+        .increaseReward(new anchor.BN(500_000))
+        .accounts({
+          graph: graphPda,
+          task: taskPda,
+          escrow: escrowPda,
+          deposit: null,
+          funder: backer.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([backer])
+        .rpc();
+      assert.fail('Other funders must record a deposit');
+    } catch (err: any) {
+      assert.ok(err.message.includes('InvalidDeposit'), 'Should fail with InvalidDeposit');
+    }
+
+    await increaseReward(taskPda, escrowPda, backer, 500_000);
+    const deposit: any = await program.account.deposit.fetch(depositPda);
+    assert.equal(deposit.lamports.toNumber(), 500_000);
+
+    const creatorBefore = await provider.connection.getBalance(creator.publicKey, 'confirmed');
+    await program.methods
+      .cancelTask()
+      .accounts({ task: taskPda, escrow: escrowPda, parent: null, creator: creator.publicKey })
+      .signers([creator])
+      .rpc();
+    const creatorAfter = await provider.connection.getBalance(creator.publicKey, 'confirmed');
+    // The creator gets back only their own 1_000_000 (less the fee of the cancel they paid for).
+    assert.ok(creatorAfter - creatorBefore <= 1_000_000, 'creator must not keep the backer deposit');
+    let taskAccount: any = await program.account.task.fetch(taskPda);
+    assert.equal(taskAccount.refundPoolLamports.toNumber(), 500_000);
+
+    const backerBefore = await provider.connection.getBalance(backer.publicKey, 'confirmed');
+    const depositRent = await provider.connection.getBalance(depositPda, 'confirmed');
+    await program.methods
+      .refundDeposit()
+      .accounts({
+        task: taskPda,
+        escrow: escrowPda,
+        deposit: depositPda,
+        funder: backer.publicKey,
+        creator: creator.publicKey,
+      })
+      .rpc();
+
+    const backerAfter = await provider.connection.getBalance(backer.publicKey, 'confirmed');
+    assert.equal(backerAfter - backerBefore, 500_000 + depositRent);
+    assert.equal(await provider.connection.getAccountInfo(depositPda), null, 'deposit is closed');
+    assert.equal(await provider.connection.getBalance(escrowPda, 'confirmed'), 0, 'last refund empties the escrow');
+    taskAccount = await program.account.task.fetch(taskPda);
+    assert.equal(taskAccount.depositorCount, 0);
+  });
+});
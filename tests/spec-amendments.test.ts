@@ -0,0 +1,211 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import { createHash } from 'crypto';
+import * as assert from 'assert';
+import {
+  deriveGraphPda,
+  deriveTaskPda,
+  deriveReceiptPda,
+  deriveDisputePda,
+  deriveCommitmentPda,
+  receiptCommitment,
+  airdrop,
+  taskParams,
+} from './helpers';
+
+describe('bountygraph spec amendments', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Bountygraph as anchor.Program;
+  const authority = (provider.wallet as anchor.Wallet).payer;
+
+  const graphId = new anchor.BN(47);
+
+  const [graphPda] = deriveGraphPda(authority.publicKey, graphId);
+  const sha256 = (text: string) => Array.from(createHash('sha256').update(text).digest());
+
+  const amend = (taskPda: PublicKey, text: string, worker: Keypair | null) =>
+    program.methods
+      .amendTaskSpec({ specUri: `ipfs://${text}`, specHash: sha256(text) })
+      .accounts({
+        graph: graphPda,
+        task: taskPda,
+        creator: authority.publicKey,
+        worker: worker ? worker.publicKey : null,
+      })
+      .signers(worker ? [worker] : [])
+      .rpc();
+
+  const createTask = async (taskId: anchor.BN, overrides: Record<string, any> = {}) => {
+    const [taskPda] = deriveTaskPda(graphPda, taskId);
+    await program.methods
+      .createTask(taskParams(taskId, { specUri: 'ipfs://v0', specHash: sha256('v0'), ...overrides }))
+      .accounts({
+        graph: graphPda,
+        authority: authority.publicKey,
+        creator: authority.publicKey,
+        creatorAllowlist: null,
+        parent: null,
+        task: taskPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    return taskPda;
+  };
+
+  before(async () => {
+    try {
+      await program.account.graph.fetch(graphPda);
+    } catch {
+      await program.methods
+        .initializeGraph({
+          graphId,
+          maxDependenciesPerTask: 10,
+          creationPolicy: { authorityOnly: {} },
+          creationDepositLamports: new anchor.BN(0),
+          guardian: null,
+        })
+        .accounts({
+          graph: graphPda,
+          authority: authority.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    }
+  });
+
+  it('requires the claimant to countersign and records the revision on disputes', async () => {
+    const worker = Keypair.generate();
+    await airdrop(worker, 1);
+
+    const taskId = new anchor.BN(4701);
+    const [taskPda] = deriveTaskPda(graphPda, taskId);
+    await program.methods
+      .createTask(taskParams(taskId, {
+        claimDurationSlots: new anchor.BN(10_000),
+        specUri: 'ipfs://v0',
+        specHash: sha256('v0'),
+      }))
+      .accounts({
+        graph: graphPda,
+        authority: authority.publicKey,
+        creator: authority.publicKey,
+        creatorAllowlist: null,
+        parent: null,
+        task: taskPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    // Nobody is engaged yet, so the creator amends alone.
+    let event: any = null;
+    const listener = program.addEventListener('TaskSpecAmended', (e: any) => {
+      event = e;
+    });
+    await amend(taskPda, 'v1', null);
+    await new Promise((resolve) => setTimeout(resolve, 1_000));
+    await program.removeEventListener(listener);
+    assert.ok(event, 'TaskSpecAmended should be emitted');
+    assert.equal(event.revision, 1);
+    assert.deepEqual(Array.from(event.previousSpecHash), sha256('v0'));
+    assert.equal(event.countersignedBy, null);
+
+    await program.methods
+      .claimTask()
+      .accounts({ task: taskPda, agent: worker.publicKey })
+      .signers([worker])
+      .rpc();
+
+    try {
+      await amend(taskPda, 'v2', null);
+      assert.fail('Amending a claimed task needs the claimant');
+    } catch (err: any) {
+      assert.ok(err.message.includes('WorkerConsentRequired'), 'Should fail with WorkerConsentRequired');
+    }
+    await amend(taskPda, 'v2', worker);
+
+    const task: any = await program.account.task.fetch(taskPda);
+    assert.equal(task.specRevision, 2);
+    assert.equal(task.specUri, 'ipfs://v2');
+
+    await program.methods
+      .submitReceipt({ workHash: Array.from(Buffer.alloc(32, 47)), uri: 'ipfs://work', contributors: [] })
+      .accounts({
+        graph: graphPda,
+        task: taskPda,
+        receipt: deriveReceiptPda(taskPda, worker.publicKey)[0],
+        agent: worker.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([worker])
+      .rpc();
+
+    const [disputePda] = deriveDisputePda(taskPda, authority.publicKey);
+    await program.methods
+      .disputeTask({ reason: 'Does not meet revision 2' })
+      .accounts({
+        task: taskPda,
+        dispute: disputePda,
+        initiator: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    const dispute: any = await program.account.dispute.fetch(disputePda);
+    assert.equal(dispute.specRevision, 2);
+  });
+
+  it('requires a worker assigned by the creator to countersign', async () => {
+    const worker = Keypair.generate();
+    await airdrop(worker, 1);
+    const taskPda = await createTask(new anchor.BN(4702));
+
+    // assign_task reservations never lapse (claim_expires_at_slot = 0).
+    await program.methods
+      .assignTask(worker.publicKey)
+      .accounts({ task: taskPda, creator: authority.publicKey })
+      .rpc();
+
+    try {
+      await amend(taskPda, 'v1', null);
+      assert.fail('Amending an assigned task needs the assignee');
+    } catch (err: any) {
+      assert.ok(err.message.includes('WorkerConsentRequired'), 'Should fail with WorkerConsentRequired');
+    }
+    await amend(taskPda, 'v1', worker);
+
+    const task: any = await program.account.task.fetch(taskPda);
+    assert.equal(task.specRevision, 1);
+  });
+
+  it('refuses amendments while committed competition entries are pending', async () => {
+    const entrant = Keypair.generate();
+    await airdrop(entrant, 1);
+    const deadline = (await provider.connection.getSlot('confirmed')) + 1_000;
+    const taskPda = await createTask(new anchor.BN(4703), { competitionDeadlineSlot: new anchor.BN(deadline) });
+
+    const work = {
+      workHash: Array.from(Buffer.alloc(32, 47)),
+      uri: 'ipfs://entry',
+      salt: Array.from(Keypair.generate().publicKey.toBuffer()),
+    };
+    await program.methods
+      .commitReceipt(receiptCommitment(work, entrant.publicKey))
+      .accounts({
+        graph: graphPda,
+        task: taskPda,
+        commitment: deriveCommitmentPda(taskPda, entrant.publicKey)[0],
+        agent: entrant.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([entrant])
+      .rpc();
+
+    try {
+      await amend(taskPda, 'v1', entrant);
+      assert.fail('Committed entries were made against the current spec');
+    } catch (err: any) {
+      assert.ok(err.message.includes('WorkerConsentRequired'), 'Should fail with WorkerConsentRequired');
+    }
+  });
+});
@@ -0,0 +1,90 @@
+import * as anchor from '@coral-xyz/anchor';
+import { createHash } from 'crypto';
+import * as assert from 'assert';
+import { deriveGraphPda, deriveTaskPda, taskParams } from './helpers';
+
+describe('bountygraph task metadata', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Bountygraph as anchor.Program;
+  const authority = (provider.wallet as anchor.Wallet).payer;
+
+  const graphId = new anchor.BN(46);
+
+  const [graphPda] = deriveGraphPda(authority.publicKey, graphId);
+
+  const createTask = (taskId: number, spec: { specUri: string; specHash: number[]; category: number; tags: number[] }) =>
+    program.methods
+      .createTask(taskParams(taskId, spec))
+      .accounts({
+        graph: graphPda,
+        authority: authority.publicKey,
+        creator: authority.publicKey,
+        creatorAllowlist: null,
+        parent: null,
+        task: deriveTaskPda(graphPda, new anchor.BN(taskId))[0],
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+  before(async () => {
+    try {
+      await program.account.graph.fetch(graphPda);
+    } catch {
+      await program.methods
+        .initializeGraph({
+          graphId,
+          maxDependenciesPerTask: 10,
+          creationPolicy: { authorityOnly: {} },
+          creationDepositLamports: new anchor.BN(0),
+          guardian: null,
+        })
+        .accounts({
+          graph: graphPda,
+          authority: authority.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    }
+  });
+
+  it('stores the spec commitment and announces category and tags', async () => {
+    const spec = '# Audit the escrow module\n...';
+    const specHash = Array.from(createHash('sha256').update(spec).digest());
+
+    let event: any = null;
+    const listener = program.addEventListener('TaskCreated', (e: any) => {
+      event = e;
+    });
+    await createTask(4601, { specUri: 'ipfs://spec-4601', specHash, category: 3, tags: [7, 12] });
+    await new Promise((resolve) => setTimeout(resolve, 1_000));
+    await program.removeEventListener(listener);
+
+    const task: any = await program.account.task.fetch(deriveTaskPda(graphPda, new anchor.BN(4601))[0]);
+    assert.equal(task.specUri, 'ipfs://spec-4601');
+    assert.deepEqual(Array.from(task.specHash), specHash);
+    assert.equal(task.category, 3);
+    assert.deepEqual(Array.from(task.tags), [7, 12, 0, 0]);
+
+    assert.ok(event, 'TaskCreated should be emitted');
+    assert.equal(event.taskId.toNumber(), 4601);
+    assert.equal(event.category, 3);
+  });
+
+  it('rejects oversized URIs and invalid tags', async () => {
+    const specHash = Array(32).fill(0);
+    for (const [taskId, spec] of [
+      [4602, { specUri: 'x'.repeat(201), specHash, category: 0, tags: [] }],
+      [4603, { specUri: '', specHash, category: 0, tags: [1, 2, 3, 4, 5] }],
+      [4604, { specUri: '', specHash, category: 0, tags: [0] }],
+    ] as const) {
+      try {
+        await createTask(taskId, { ...spec, tags: [...spec.tags] });
+        assert.fail('Spec should be rejected');
+      } catch (err: any) {
+        assert.ok(err.message.includes('InvalidSpec'), 'Should fail with InvalidSpec');
+      }
+    }
+  });
+});
@@ -0,0 +1,151 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair } from '@solana/web3.js';
+import * as assert from 'assert';
+import {
+  deriveGraphPda,
+  deriveTaskPda,
+  deriveEscrowPda,
+  deriveReceiptPda,
+  deriveProtocolConfigPda,
+  airdrop,
+  ensureProtocolConfig,
+  taskParams,
+} from './helpers';
+
+describe('bountygraph team payouts', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Bountygraph as anchor.Program;
+  const authority = (provider.wallet as anchor.Wallet).payer;
+
+  const graphId = new anchor.BN(0);
+
+  const [protocolConfigPda] = deriveProtocolConfigPda();
+
+  const [graphPda] = deriveGraphPda(authority.publicKey, graphId);
+
+  before(async () => {
+    try {
+      await program.account.graph.fetch(graphPda);
+    } catch {
+      await program.methods
+        .initializeGraph({
+          graphId,
+          maxDependenciesPerTask: 10,
+          creationPolicy: { authorityOnly: {} },
+          creationDepositLamports: new anchor.BN(0),
+          guardian: null,
+        })
+        .accounts({
+          graph: graphPda,
+          authority: authority.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    }
+  });
+
+  it('splits the reward across a team once every contributor has accepted', async () => {
+    const creator = Keypair.generate();
+    const lead = Keypair.generate();
+    const teammate = Keypair.generate();
+    await airdrop(creator, 2);
+    await airdrop(lead, 1);
+    await airdrop(teammate, 1);
+
+    const taskId = new anchor.BN(3401);
+    const [taskPda] = deriveTaskPda(graphPda, taskId);
+    const [escrowPda] = deriveEscrowPda(taskPda);
+    const [receiptPda] = deriveReceiptPda(taskPda, lead.publicKey);
+
+    await program.methods
+      .createTask(taskParams(taskId, { rewardLamports: new anchor.BN(1_000_000) }))
+      .accounts({
+        graph: graphPda,
+        authority: authority.publicKey,
+        creator: creator.publicKey,
+        creatorAllowlist: null,
+        parent: null,
+        task: taskPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([authority, creator])
+      .rpc();
+
+    await program.methods
+      .fundTask(new anchor.BN(1_000_000))
+      .accounts({
+        graph: graphPda,
+        task: taskPda,
+        escrow: escrowPda,
+        deposit: null,
+        funder: creator.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([creator])
+      .rpc();
+
+    await program.methods
+      .submitReceipt({
+        workHash: Array.from(Buffer.alloc(32, 34)),
+        uri: 'ipfs://team-receipt',
+        contributors: [
+          { worker: lead.publicKey, shareBps: 7_000 },
+          { worker: teammate.publicKey, shareBps: 3_000 },
+        ],
+      })
+      .accounts({
+        graph: graphPda,
+        task: taskPda,
+        receipt: receiptPda,
+        agent: lead.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([lead])
+      .rpc();
+
+    const treasury = await ensureProtocolConfig();
+    const claim = () =>
+      program.methods
+        .claimReward()
+        .accounts({
+          graph: graphPda,
+          task: taskPda,
+          escrow: escrowPda,
+          protocolConfig: protocolConfigPda,
+          treasury,
+          graphAuthority: authority.publicKey,
+          receipt: receiptPda,
+          agent: lead.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .remainingAccounts(
+          [lead, teammate].map((kp) => ({ pubkey: kp.publicKey, isSigner: false, isWritable: true }))
+        )
+        .signers([lead])
+        .rpc();
+
+    try {
+      await claim();
+      assert.fail('Should not pay out before the teammate accepts');
+    } catch (err: any) {
+      assert.ok(err.message.includes('ContributionsPending'), 'Should fail with ContributionsPending');
+    }
+
+    await program.methods
+      .acceptContribution()
+      .accounts({ receipt: receiptPda, contributor: teammate.publicKey })
+      .signers([teammate])
+      .rpc();
+
+    const escrowBefore = await provider.connection.getBalance(escrowPda, 'confirmed');
+    const teammateBefore = await provider.connection.getBalance(teammate.publicKey, 'confirmed');
+    await claim();
+    const teammateAfter = await provider.connection.getBalance(teammate.publicKey, 'confirmed');
+
+    // The last contributor absorbs rounding dust.
+    assert.equal(teammateAfter - teammateBefore, escrowBefore - Math.floor((escrowBefore * 7_000) / 10_000));
+    assert.equal(await provider.connection.getBalance(escrowPda, 'confirmed'), 0);
+  });
+});
@@ -0,0 +1,161 @@
+import * as anchor from '@coral-xyz/anchor';
+import * as assert from 'assert';
+import { deriveGraphPda, deriveTaskPda, deriveTemplatePda } from './helpers';
+
+describe('bountygraph graph templates', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Bountygraph as anchor.Program;
+  const authority = (provider.wallet as anchor.Wallet).payer;
+
+  const graphId = new anchor.BN(45);
+
+  const [graphPda] = deriveGraphPda(authority.publicKey, graphId);
+  const templateId = new anchor.BN(45);
+  const [templatePda] = deriveTemplatePda(authority.publicKey, templateId);
+
+  const spec = (rewardLamports: number, dependencies: number[], metadata: Record<string, any> = {}) => ({
+    rewardLamports: new anchor.BN(rewardLamports),
+    dependencies: Buffer.from(dependencies),
+    claimDurationSlots: new anchor.BN(0),
+    requireFunding: false,
+    specUri: '',
+    specHash: Array(32).fill(0),
+    category: 0,
+    tags: [],
+    ...metadata,
+  });
+
+  const instantiate = (taskIdOffset: number, rewardMultiplierBps: number | null, size: number) =>
+    program.methods
+      .instantiateTemplate({ taskIdOffset: new anchor.BN(taskIdOffset), rewardMultiplierBps })
+      .accounts({
+        graph: graphPda,
+        template: templatePda,
+        authority: authority.publicKey,
+        creator: authority.publicKey,
+        creatorAllowlist: null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts(
+        Array.from({ length: size }, (_, i) => ({
+          pubkey: deriveTaskPda(graphPda, new anchor.BN(taskIdOffset + i))[0],
+          isSigner: false,
+          isWritable: true,
+        }))
+      )
+      .rpc();
+
+  before(async () => {
+    try {
+      await program.account.graph.fetch(graphPda);
+    } catch {
+      await program.methods
+        .initializeGraph({
+          graphId,
+          maxDependenciesPerTask: 10,
+          creationPolicy: { authorityOnly: {} },
+          creationDepositLamports: new anchor.BN(0),
+          guardian: null,
+        })
+        .accounts({
+          graph: graphPda,
+          authority: authority.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    }
+  });
+
+  it('rejects templates with forward edges', async () => {
+    try {
+      await program.methods
+        .createTemplate(new anchor.BN(4599), [spec(100_000, [1]), spec(100_000, [])])
+        .accounts({
+          template: deriveTemplatePda(authority.publicKey, new anchor.BN(4599))[0],
+          authority: authority.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      assert.fail('Template edges must point to earlier tasks');
+    } catch (err: any) {
+      assert.ok(err.message.includes('InvalidTemplate'), 'Should fail with InvalidTemplate');
+    }
+  });
+
+  it('rejects templates with invalid spec metadata', async () => {
+    try {
+      await program.methods
+        .createTemplate(new anchor.BN(4598), [spec(100_000, [], { tags: [1, 0] })])
+        .accounts({
+          template: deriveTemplatePda(authority.publicKey, new anchor.BN(4598))[0],
+          authority: authority.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      assert.fail('Template tags must be non-zero');
+    } catch (err: any) {
+      assert.ok(err.message.includes('InvalidSpec'), 'Should fail with InvalidSpec');
+    }
+  });
+
+  it('instantiates audit -> fix -> re-audit -> deploy with offsets and scaling', async () => {
+    await program.methods
+      .createTemplate(templateId, [
+        spec(400_000, [], {
+          specUri: 'ipfs://audit-spec',
+          specHash: Array(32).fill(45),
+          category: 3,
+          tags: [7, 12],
+        }),
+        spec(1_000_000, [0]),
+        spec(200_000, [1]),
+        spec(100_000, [1, 2]),
+      ])
+      .accounts({
+        template: templatePda,
+        authority: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    await instantiate(4500, null, 4);
+    await instantiate(4510, 20_000, 4);
+
+    const deploy: any = await program.account.task.fetch(
+      deriveTaskPda(graphPda, new anchor.BN(4503))[0]
+    );
+    assert.deepEqual(
+      deploy.dependencies.map((d: anchor.BN) => d.toNumber()),
+      [4501, 4502]
+    );
+    assert.equal(deploy.rewardLamports.toNumber(), 100_000);
+
+    const scaled: any = await program.account.task.fetch(
+      deriveTaskPda(graphPda, new anchor.BN(4513))[0]
+    );
+    assert.deepEqual(
+      scaled.dependencies.map((d: anchor.BN) => d.toNumber()),
+      [4511, 4512]
+    );
+    assert.equal(scaled.rewardLamports.toNumber(), 200_000);
+
+    // Spec metadata is copied to every instantiation.
+    for (const taskId of [4500, 4510]) {
+      const audit: any = await program.account.task.fetch(
+        deriveTaskPda(graphPda, new anchor.BN(taskId))[0]
+      );
+      assert.equal(audit.specUri, 'ipfs://audit-spec');
+      assert.deepEqual(audit.specHash, Array(32).fill(45));
+      assert.equal(audit.category, 3);
+      assert.deepEqual(audit.tags, [7, 12, 0, 0]);
+    }
+
+    await program.methods
+      .closeTemplate()
+      .accounts({ template: templatePda, authority: authority.publicKey })
+      .rpc();
+    assert.equal(await provider.connection.getAccountInfo(templatePda), null);
+  });
+});
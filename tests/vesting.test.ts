@@ -0,0 +1,143 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair } from '@solana/web3.js';
+import * as assert from 'assert';
+import {
+  deriveGraphPda,
+  deriveTaskPda,
+  deriveEscrowPda,
+  deriveReceiptPda,
+  deriveProtocolConfigPda,
+  airdrop,
+  ensureProtocolConfig,
+  taskParams,
+} from './helpers';
+
+describe('bountygraph vesting payouts', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Bountygraph as anchor.Program;
+  const authority = (provider.wallet as anchor.Wallet).payer;
+
+  const graphId = new anchor.BN(0);
+
+  const [protocolConfigPda] = deriveProtocolConfigPda();
+
+  const waitForSlot = async (slot: number) => {
+    while ((await provider.connection.getSlot('confirmed')) < slot) {
+      await new Promise((resolve) => setTimeout(resolve, 200));
+    }
+  };
+
+  const [graphPda] = deriveGraphPda(authority.publicKey, graphId);
+
+  before(async () => {
+    try {
+      await program.account.graph.fetch(graphPda);
+    } catch {
+      await program.methods
+        .initializeGraph({
+          graphId,
+          maxDependenciesPerTask: 10,
+          creationPolicy: { authorityOnly: {} },
+          creationDepositLamports: new anchor.BN(0),
+          guardian: null,
+        })
+        .accounts({
+          graph: graphPda,
+          authority: authority.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    }
+  });
+
+  it('releases the reward linearly after the cliff through withdraw_vested', async () => {
+    const creator = Keypair.generate();
+    const worker = Keypair.generate();
+    await airdrop(creator, 2);
+    await airdrop(worker, 1);
+
+    const taskId = new anchor.BN(3601);
+    const [taskPda] = deriveTaskPda(graphPda, taskId);
+    const [escrowPda] = deriveEscrowPda(taskPda);
+
+    await program.methods
+      .createTask(taskParams(taskId, {
+        rewardLamports: new anchor.BN(1_000_000),
+        vesting: { cliffSlots: new anchor.BN(10), durationSlots: new anchor.BN(40) },
+      }))
+      .accounts({
+        graph: graphPda,
+        authority: authority.publicKey,
+        creator: creator.publicKey,
+        creatorAllowlist: null,
+        parent: null,
+        task: taskPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([authority, creator])
+      .rpc();
+
+    await program.methods
+      .fundTask(new anchor.BN(1_000_000))
+      .accounts({
+        graph: graphPda,
+        task: taskPda,
+        escrow: escrowPda,
+        deposit: null,
+        funder: creator.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([creator])
+      .rpc();
+
+    await program.methods
+      .submitReceipt({ workHash: Array.from(Buffer.alloc(32, 36)), uri: 'ipfs://vesting', contributors: [] })
+      .accounts({
+        graph: graphPda,
+        task: taskPda,
+        receipt: deriveReceiptPda(taskPda, worker.publicKey)[0],
+        agent: worker.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([worker])
+      .rpc();
+
+    const treasury = await ensureProtocolConfig();
+    const withdraw = () =>
+      program.methods
+        .withdrawVested()
+        .accounts({
+          graph: graphPda,
+          task: taskPda,
+          escrow: escrowPda,
+          protocolConfig: protocolConfigPda,
+          treasury,
+          graphAuthority: authority.publicKey,
+          agent: worker.publicKey,
+        })
+        .signers([worker])
+        .rpc();
+
+    try {
+      await withdraw();
+      assert.fail('Nothing should be withdrawable before the cliff');
+    } catch (err: any) {
+      assert.ok(err.message.includes('NothingVested'), 'Should fail with NothingVested');
+    }
+
+    const taskAccount: any = await program.account.task.fetch(taskPda);
+    const start = taskAccount.vestingStartSlot.toNumber();
+
+    await waitForSlot(start + 20);
+    const escrowBefore = await provider.connection.getBalance(escrowPda, 'confirmed');
+    await withdraw();
+    const escrowMid = await provider.connection.getBalance(escrowPda, 'confirmed');
+    assert.ok(escrowMid > 0 && escrowMid < escrowBefore, 'partial withdrawal leaves the unvested remainder');
+
+    await waitForSlot(start + 40);
+    await withdraw();
+    assert.equal(await provider.connection.getBalance(escrowPda, 'confirmed'), 0);
+  });
+});
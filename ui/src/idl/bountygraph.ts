@@ -2,6 +2,39 @@ export const BOUNTYGRAPH_IDL = {
   version: "0.1.0",
   name: "bountygraph",
   instructions: [
+    {
+      name: "initializeProtocolConfig",
+      accounts: [
+        { name: "protocolConfig", isMut: true, isSigner: false },
+        { name: "program", isMut: false, isSigner: false },
+        { name: "programData", isMut: false, isSigner: false },
+        { name: "admin", isMut: true, isSigner: true },
+        { name: "systemProgram", isMut: false, isSigner: false },
+      ],
+      args: [
+        {
+          name: "params",
+          type: {
+            defined: "InitializeProtocolConfigParams",
+          },
+        },
+      ],
+    },
+    {
+      name: "updateProtocolConfig",
+      accounts: [
+        { name: "protocolConfig", isMut: true, isSigner: false },
+        { name: "admin", isMut: false, isSigner: true },
+      ],
+      args: [
+        {
+          name: "params",
+          type: {
+            defined: "UpdateProtocolConfigParams",
+          },
+        },
+      ],
+    },
     {
       name: "initializeGraph",
       accounts: [
@@ -19,11 +52,87 @@ export const BOUNTYGRAPH_IDL = {
       ],
     },
     {
-      name: "createTask",
+      name: "updateGraphConfig",
+      accounts: [
+        { name: "graph", isMut: true, isSigner: false },
+        { name: "authority", isMut: false, isSigner: true },
+      ],
+      args: [
+        {
+          name: "params",
+          type: {
+            defined: "UpdateGraphConfigParams",
+          },
+        },
+      ],
+    },
+    {
+      name: "setGuardian",
+      accounts: [
+        { name: "graph", isMut: true, isSigner: false },
+        { name: "authority", isMut: false, isSigner: true },
+      ],
+      args: [{ name: "guardian", type: { option: "publicKey" } }],
+    },
+    {
+      name: "pauseGraph",
+      accounts: [
+        { name: "graph", isMut: true, isSigner: false },
+        { name: "signer", isMut: false, isSigner: true },
+      ],
+      args: [{ name: "reasonCode", type: "u16" }],
+    },
+    {
+      name: "unpauseGraph",
+      accounts: [
+        { name: "graph", isMut: true, isSigner: false },
+        { name: "authority", isMut: false, isSigner: true },
+      ],
+      args: [],
+    },
+    {
+      name: "setCreationPolicy",
       accounts: [
         { name: "graph", isMut: true, isSigner: false },
         { name: "authority", isMut: false, isSigner: true },
+      ],
+      args: [
+        {
+          name: "params",
+          type: {
+            defined: "SetCreationPolicyParams",
+          },
+        },
+      ],
+    },
+    {
+      name: "addAllowedCreator",
+      accounts: [
+        { name: "graph", isMut: false, isSigner: false },
+        { name: "authority", isMut: true, isSigner: true },
+        { name: "creator", isMut: false, isSigner: false },
+        { name: "creatorAllowlist", isMut: true, isSigner: false },
+        { name: "systemProgram", isMut: false, isSigner: false },
+      ],
+      args: [],
+    },
+    {
+      name: "removeAllowedCreator",
+      accounts: [
+        { name: "graph", isMut: false, isSigner: false },
+        { name: "authority", isMut: true, isSigner: true },
+        { name: "creatorAllowlist", isMut: true, isSigner: false },
+      ],
+      args: [],
+    },
+    {
+      name: "createTask",
+      accounts: [
+        { name: "graph", isMut: true, isSigner: false },
+        { name: "authority", isMut: false, isSigner: true, isOptional: true },
         { name: "creator", isMut: true, isSigner: true },
+        { name: "creatorAllowlist", isMut: false, isSigner: false, isOptional: true },
+        { name: "parent", isMut: true, isSigner: false, isOptional: true },
         { name: "task", isMut: true, isSigner: false },
         { name: "systemProgram", isMut: false, isSigner: false },
       ],
@@ -36,19 +145,108 @@ export const BOUNTYGRAPH_IDL = {
         },
       ],
     },
+    {
+      name: "createTasksBatch",
+      accounts: [
+        { name: "graph", isMut: true, isSigner: false },
+        { name: "authority", isMut: false, isSigner: true, isOptional: true },
+        { name: "creator", isMut: true, isSigner: true },
+        { name: "creatorAllowlist", isMut: false, isSigner: false, isOptional: true },
+        { name: "systemProgram", isMut: false, isSigner: false },
+      ],
+      args: [
+        {
+          name: "params",
+          type: {
+            vec: {
+              defined: "CreateTaskParams",
+            },
+          },
+        },
+      ],
+    },
+    {
+      name: "createTemplate",
+      accounts: [
+        { name: "template", isMut: true, isSigner: false },
+        { name: "authority", isMut: true, isSigner: true },
+        { name: "systemProgram", isMut: false, isSigner: false },
+      ],
+      args: [
+        { name: "templateId", type: "u64" },
+        { name: "tasks", type: { vec: { defined: "TemplateTask" } } },
+      ],
+    },
+    {
+      name: "closeTemplate",
+      accounts: [
+        { name: "template", isMut: true, isSigner: false },
+        { name: "authority", isMut: true, isSigner: true },
+      ],
+      args: [],
+    },
+    {
+      name: "instantiateTemplate",
+      accounts: [
+        { name: "graph", isMut: true, isSigner: false },
+        { name: "template", isMut: false, isSigner: false },
+        { name: "authority", isMut: false, isSigner: true, isOptional: true },
+        { name: "creator", isMut: true, isSigner: true },
+        { name: "creatorAllowlist", isMut: false, isSigner: false, isOptional: true },
+        { name: "systemProgram", isMut: false, isSigner: false },
+      ],
+      args: [
+        {
+          name: "params",
+          type: {
+            defined: "InstantiateTemplateParams",
+          },
+        },
+      ],
+    },
+    {
+      name: "approveExternalDependency",
+      accounts: [
+        { name: "graph", isMut: false, isSigner: false },
+        { name: "authority", isMut: false, isSigner: true },
+        { name: "task", isMut: true, isSigner: false },
+      ],
+      args: [{ name: "index", type: "u8" }],
+    },
     {
       name: "fundTask",
       accounts: [
+        { name: "graph", isMut: false, isSigner: false },
         { name: "task", isMut: true, isSigner: false },
         { name: "escrow", isMut: true, isSigner: false },
+        { name: "deposit", isMut: true, isSigner: false, isOptional: true },
         { name: "funder", isMut: true, isSigner: true },
         { name: "systemProgram", isMut: false, isSigner: false },
       ],
       args: [{ name: "lamports", type: "u64" }],
     },
+    {
+      name: "fundingStatus",
+      accounts: [{ name: "task", isMut: false, isSigner: false }],
+      args: [],
+      returns: { defined: "TaskFunding" },
+    },
+    {
+      name: "increaseReward",
+      accounts: [
+        { name: "graph", isMut: false, isSigner: false },
+        { name: "task", isMut: true, isSigner: false },
+        { name: "escrow", isMut: true, isSigner: false },
+        { name: "deposit", isMut: true, isSigner: false, isOptional: true },
+        { name: "funder", isMut: true, isSigner: true },
+        { name: "systemProgram", isMut: false, isSigner: false },
+      ],
+      args: [{ name: "additionalLamports", type: "u64" }],
+    },
     {
       name: "submitReceipt",
       accounts: [
+        { name: "graph", isMut: false, isSigner: false },
         { name: "task", isMut: true, isSigner: false },
         { name: "receipt", isMut: true, isSigner: false },
         { name: "agent", isMut: true, isSigner: true },
@@ -63,16 +261,206 @@ export const BOUNTYGRAPH_IDL = {
         },
       ],
     },
+    {
+      name: "rollUp",
+      accounts: [
+        { name: "graph", isMut: false, isSigner: false },
+        { name: "task", isMut: true, isSigner: false },
+        { name: "escrow", isMut: true, isSigner: false, isOptional: true },
+        { name: "protocolConfig", isMut: false, isSigner: false },
+        { name: "treasury", isMut: true, isSigner: false },
+        { name: "graphAuthority", isMut: true, isSigner: false },
+      ],
+      args: [],
+    },
     {
       name: "claimReward",
       accounts: [
+        { name: "graph", isMut: false, isSigner: false },
         { name: "task", isMut: true, isSigner: false },
         { name: "escrow", isMut: true, isSigner: false },
+        { name: "protocolConfig", isMut: false, isSigner: false },
+        { name: "treasury", isMut: true, isSigner: false },
+        { name: "graphAuthority", isMut: true, isSigner: false },
+        { name: "receipt", isMut: true, isSigner: false, isOptional: true },
         { name: "agent", isMut: true, isSigner: true },
         { name: "systemProgram", isMut: false, isSigner: false },
       ],
       args: [],
     },
+    {
+      name: "withdrawVested",
+      accounts: [
+        { name: "graph", isMut: false, isSigner: false },
+        { name: "task", isMut: true, isSigner: false },
+        { name: "escrow", isMut: true, isSigner: false },
+        { name: "protocolConfig", isMut: false, isSigner: false },
+        { name: "treasury", isMut: true, isSigner: false },
+        { name: "graphAuthority", isMut: true, isSigner: false },
+        { name: "agent", isMut: true, isSigner: true },
+      ],
+      args: [],
+    },
+    {
+      name: "submitMilestone",
+      accounts: [
+        { name: "graph", isMut: false, isSigner: false },
+        { name: "task", isMut: true, isSigner: false },
+        { name: "receipt", isMut: true, isSigner: false },
+        { name: "agent", isMut: true, isSigner: true },
+        { name: "systemProgram", isMut: false, isSigner: false },
+      ],
+      args: [
+        {
+          name: "params",
+          type: {
+            defined: "SubmitMilestoneParams",
+          },
+        },
+      ],
+    },
+    {
+      name: "approveMilestone",
+      accounts: [
+        { name: "graph", isMut: false, isSigner: false },
+        { name: "task", isMut: true, isSigner: false },
+        { name: "escrow", isMut: true, isSigner: false },
+        { name: "protocolConfig", isMut: false, isSigner: false },
+        { name: "treasury", isMut: true, isSigner: false },
+        { name: "graphAuthority", isMut: true, isSigner: false },
+        { name: "creator", isMut: false, isSigner: true },
+        { name: "worker", isMut: true, isSigner: false },
+      ],
+      args: [{ name: "index", type: "u8" }],
+    },
+    {
+      name: "rejectMilestone",
+      accounts: [
+        { name: "task", isMut: true, isSigner: false },
+        { name: "receipt", isMut: true, isSigner: false },
+        { name: "creator", isMut: false, isSigner: true },
+        { name: "worker", isMut: true, isSigner: false },
+      ],
+      args: [{ name: "index", type: "u8" }],
+    },
+    {
+      name: "rejectReceipt",
+      accounts: [
+        { name: "task", isMut: true, isSigner: false },
+        { name: "receipt", isMut: true, isSigner: false },
+        { name: "creator", isMut: false, isSigner: true },
+        { name: "agent", isMut: true, isSigner: false },
+      ],
+      args: [],
+    },
+    {
+      name: "attestReceipt",
+      accounts: [
+        { name: "graph", isMut: false, isSigner: false },
+        { name: "task", isMut: true, isSigner: false },
+        { name: "receipt", isMut: false, isSigner: false },
+        { name: "instructions", isMut: false, isSigner: false },
+      ],
+      args: [],
+    },
+    {
+      name: "commitReceipt",
+      accounts: [
+        { name: "graph", isMut: false, isSigner: false },
+        { name: "task", isMut: true, isSigner: false },
+        { name: "commitment", isMut: true, isSigner: false },
+        { name: "agent", isMut: true, isSigner: true },
+        { name: "systemProgram", isMut: false, isSigner: false },
+      ],
+      args: [{ name: "commitment", type: { array: ["u8", 32] } }],
+    },
+    {
+      name: "revealReceipt",
+      accounts: [
+        { name: "graph", isMut: false, isSigner: false },
+        { name: "task", isMut: true, isSigner: false },
+        { name: "commitment", isMut: true, isSigner: false },
+        { name: "receipt", isMut: true, isSigner: false },
+        { name: "agent", isMut: true, isSigner: true },
+        { name: "systemProgram", isMut: false, isSigner: false },
+      ],
+      args: [
+        {
+          name: "params",
+          type: {
+            defined: "RevealReceiptParams",
+          },
+        },
+      ],
+    },
+    {
+      name: "submitCompletion",
+      accounts: [
+        { name: "graph", isMut: false, isSigner: false },
+        { name: "task", isMut: true, isSigner: false },
+        { name: "receipt", isMut: true, isSigner: false },
+        { name: "agent", isMut: true, isSigner: true },
+        { name: "systemProgram", isMut: false, isSigner: false },
+      ],
+      args: [
+        {
+          name: "params",
+          type: {
+            defined: "SubmitCompletionParams",
+          },
+        },
+      ],
+    },
+    {
+      name: "approveCompletion",
+      accounts: [
+        { name: "graph", isMut: false, isSigner: false },
+        { name: "task", isMut: true, isSigner: false },
+        { name: "receipt", isMut: true, isSigner: false },
+        { name: "escrow", isMut: true, isSigner: false },
+        { name: "protocolConfig", isMut: false, isSigner: false },
+        { name: "treasury", isMut: true, isSigner: false },
+        { name: "graphAuthority", isMut: true, isSigner: false },
+        { name: "creator", isMut: false, isSigner: true },
+        { name: "worker", isMut: true, isSigner: false },
+      ],
+      args: [],
+    },
+    {
+      name: "rejectCompletion",
+      accounts: [
+        { name: "task", isMut: true, isSigner: false },
+        { name: "receipt", isMut: true, isSigner: false },
+        { name: "creator", isMut: false, isSigner: true },
+        { name: "worker", isMut: true, isSigner: false },
+      ],
+      args: [],
+    },
+    {
+      name: "acceptContribution",
+      accounts: [
+        { name: "receipt", isMut: true, isSigner: false },
+        { name: "contributor", isMut: false, isSigner: true },
+      ],
+      args: [],
+    },
+    {
+      name: "selectWinners",
+      accounts: [
+        { name: "graph", isMut: false, isSigner: false },
+        { name: "task", isMut: true, isSigner: false },
+        { name: "escrow", isMut: false, isSigner: false },
+        { name: "selector", isMut: false, isSigner: true },
+      ],
+      args: [
+        {
+          name: "params",
+          type: {
+            defined: "SelectWinnersParams",
+          },
+        },
+      ],
+    },
     {
       name: "disputeTask",
       accounts: [
@@ -94,34 +482,223 @@ export const BOUNTYGRAPH_IDL = {
       name: "resolveDispute",
       accounts: [
         { name: "graph", isMut: false, isSigner: false },
-        { name: "authority", isMut: false, isSigner: true },
+        { name: "authority", isMut: true, isSigner: true },
         { name: "task", isMut: true, isSigner: false },
         { name: "dispute", isMut: true, isSigner: false },
         { name: "escrow", isMut: true, isSigner: false },
         { name: "creator", isMut: true, isSigner: false },
         { name: "worker", isMut: true, isSigner: false },
+        { name: "protocolConfig", isMut: false, isSigner: false },
+        { name: "treasury", isMut: true, isSigner: false },
+        { name: "receipt", isMut: false, isSigner: false, isOptional: true },
+      ],
+      args: [
+        {
+          name: "params",
+          type: {
+            defined: "ResolveDisputeParams",
+          },
+        },
+      ],
+    },
+    {
+      name: "claimTask",
+      accounts: [
+        { name: "task", isMut: true, isSigner: false },
+        { name: "agent", isMut: false, isSigner: true },
+      ],
+      args: [],
+    },
+    {
+      name: "unclaimTask",
+      accounts: [
+        { name: "task", isMut: true, isSigner: false },
+        { name: "agent", isMut: false, isSigner: true },
+      ],
+      args: [],
+    },
+    {
+      name: "assignTask",
+      accounts: [
+        { name: "task", isMut: true, isSigner: false },
+        { name: "creator", isMut: false, isSigner: true },
+      ],
+      args: [{ name: "worker", type: { option: "publicKey" } }],
+    },
+    {
+      name: "amendTaskSpec",
+      accounts: [
+        { name: "graph", isMut: false, isSigner: false },
+        { name: "task", isMut: true, isSigner: false },
+        { name: "creator", isMut: false, isSigner: true },
+        { name: "worker", isMut: false, isSigner: true, isOptional: true },
+      ],
+      args: [
+        {
+          name: "params",
+          type: {
+            defined: "AmendTaskSpecParams",
+          },
+        },
+      ],
+    },
+    {
+      name: "cancelTask",
+      accounts: [
+        { name: "task", isMut: true, isSigner: false },
+        { name: "escrow", isMut: true, isSigner: false, isOptional: true },
+        { name: "parent", isMut: true, isSigner: false, isOptional: true },
+        { name: "creator", isMut: true, isSigner: true },
+      ],
+      args: [],
+    },
+    {
+      name: "refundDeposit",
+      accounts: [
+        { name: "task", isMut: true, isSigner: false },
+        { name: "escrow", isMut: true, isSigner: false, isOptional: true },
+        { name: "deposit", isMut: true, isSigner: false },
+        { name: "funder", isMut: true, isSigner: false },
+        { name: "creator", isMut: true, isSigner: false },
+      ],
+      args: [],
+    },
+    {
+      name: "closeTask",
+      accounts: [
+        { name: "graph", isMut: false, isSigner: false },
+        { name: "task", isMut: true, isSigner: false },
+        { name: "creator", isMut: true, isSigner: true },
+      ],
+      args: [],
+    },
+    {
+      name: "closeReceipt",
+      accounts: [
+        { name: "graph", isMut: false, isSigner: false },
+        { name: "task", isMut: false, isSigner: false },
+        { name: "receipt", isMut: true, isSigner: false },
+        { name: "agent", isMut: true, isSigner: true },
+      ],
+      args: [],
+    },
+    {
+      name: "closeCommitment",
+      accounts: [
+        { name: "task", isMut: false, isSigner: false },
+        { name: "commitment", isMut: true, isSigner: false },
+        { name: "agent", isMut: true, isSigner: true },
+      ],
+      args: [],
+    },
+    {
+      name: "closeDispute",
+      accounts: [
+        { name: "graph", isMut: false, isSigner: false },
+        { name: "task", isMut: false, isSigner: false },
+        { name: "dispute", isMut: true, isSigner: false },
+        { name: "initiator", isMut: true, isSigner: true },
+      ],
+      args: [],
+    },
+    {
+      name: "migrateGraph",
+      accounts: [
+        { name: "account", isMut: true, isSigner: false },
+        { name: "payer", isMut: true, isSigner: true },
+        { name: "systemProgram", isMut: false, isSigner: false },
+      ],
+      args: [],
+    },
+    {
+      name: "migrateTask",
+      accounts: [
+        { name: "account", isMut: true, isSigner: false },
+        { name: "escrow", isMut: false, isSigner: false },
+        { name: "payer", isMut: true, isSigner: true },
+        { name: "systemProgram", isMut: false, isSigner: false },
+      ],
+      args: [],
+    },
+    {
+      name: "migrateEscrow",
+      accounts: [
+        { name: "account", isMut: true, isSigner: false },
+        { name: "payer", isMut: true, isSigner: true },
+        { name: "systemProgram", isMut: false, isSigner: false },
+      ],
+      args: [],
+    },
+    {
+      name: "migrateReceipt",
+      accounts: [
+        { name: "account", isMut: true, isSigner: false },
+        { name: "payer", isMut: true, isSigner: true },
         { name: "systemProgram", isMut: false, isSigner: false },
       ],
-      args: [
-        {
-          name: "params",
-          type: {
-            defined: "ResolveDisputeParams",
-          },
-        },
+      args: [],
+    },
+    {
+      name: "migrateDispute",
+      accounts: [
+        { name: "account", isMut: true, isSigner: false },
+        { name: "payer", isMut: true, isSigner: true },
+        { name: "systemProgram", isMut: false, isSigner: false },
       ],
+      args: [],
     },
   ],
   accounts: [
+    {
+      name: "ProtocolConfig",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "admin", type: "publicKey" },
+          { name: "treasury", type: "publicKey" },
+          { name: "feeBps", type: "u16" },
+          { name: "bump", type: "u8" },
+          { name: "version", type: "u8" },
+        ],
+      },
+    },
     {
       name: "Graph",
       type: {
         kind: "struct",
         fields: [
           { name: "authority", type: "publicKey" },
+          { name: "graphId", type: "u64" },
           { name: "maxDependenciesPerTask", type: "u16" },
           { name: "taskCount", type: "u64" },
+          { name: "creationPolicy", type: { defined: "CreationPolicy" } },
+          { name: "creationDepositLamports", type: "u64" },
+          { name: "guardian", type: { option: "publicKey" } },
+          { name: "paused", type: "bool" },
+          { name: "pauseReason", type: "u16" },
+          { name: "pausedAtSlot", type: "u64" },
+          { name: "minRewardLamports", type: "u64" },
+          { name: "maxRewardLamports", type: "u64" },
+          { name: "configVersion", type: "u32" },
+          { name: "closeRetentionSlots", type: "u64" },
+          { name: "graphFeeBps", type: "u16" },
+          { name: "requireDependentApproval", type: "bool" },
+          { name: "dependencyDisputePolicy", type: { defined: "DependencyDisputePolicy" } },
+          { name: "bump", type: "u8" },
+          { name: "version", type: "u8" },
+        ],
+      },
+    },
+    {
+      name: "CreatorAllowlist",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "graph", type: "publicKey" },
+          { name: "creator", type: "publicKey" },
+          { name: "addedAtSlot", type: "u64" },
           { name: "bump", type: "u8" },
+          { name: "version", type: "u8" },
         ],
       },
     },
@@ -144,7 +721,80 @@ export const BOUNTYGRAPH_IDL = {
           { name: "resolvedBy", type: { option: "publicKey" } },
           { name: "disputeResolvedAtSlot", type: "u64" },
           { name: "workerAwardLamports", type: "u64" },
+          { name: "creationDepositLamports", type: "u64" },
+          { name: "configVersion", type: "u32" },
+          { name: "settledAtSlot", type: "u64" },
+          { name: "claimDurationSlots", type: "u64" },
+          { name: "assignee", type: { option: "publicKey" } },
+          { name: "claimExpiresAtSlot", type: "u64" },
+          { name: "competitionDeadlineSlot", type: "u64" },
+          { name: "submissionCount", type: "u32" },
+          { name: "payoutPoolLamports", type: "u64" },
+          { name: "unclaimedPayoutBps", type: "u16" },
+          { name: "teamSize", type: "u8" },
+          { name: "milestones", type: { vec: { defined: "Milestone" } } },
+          { name: "vesting", type: { option: { defined: "VestingSchedule" } } },
+          { name: "vestingStartSlot", type: "u64" },
+          { name: "vestedWithdrawnLamports", type: "u64" },
+          { name: "crowdfunded", type: "bool" },
+          { name: "requireFunding", type: "bool" },
+          { name: "fundedLamports", type: "u64" },
+          { name: "dependencyGroups", type: { vec: { defined: "DependencyGroup" } } },
+          { name: "externalDependencies", type: { vec: { defined: "ExternalDependency" } } },
+          { name: "disputeWorkerPct", type: "u8" },
+          { name: "isEpic", type: "bool" },
+          { name: "parentTaskId", type: { option: "u64" } },
+          { name: "childCount", type: "u16" },
+          { name: "specUri", type: "string" },
+          { name: "specHash", type: { array: ["u8", 32] } },
+          { name: "category", type: "u16" },
+          { name: "tags", type: { array: ["u16", 4] } },
+          { name: "specRevision", type: "u32" },
+          { name: "maxCompletions", type: "u16" },
+          { name: "completionCount", type: "u16" },
+          { name: "recurrenceIntervalSlots", type: "u64" },
+          { name: "recurrenceEndSlot", type: "u64" },
+          { name: "lastCompletedSlot", type: "u64" },
+          { name: "pendingCompletionAgent", type: { option: "publicKey" } },
+          { name: "lastCommitSlot", type: "u64" },
+          { name: "verifiers", type: { vec: "publicKey" } },
+          { name: "verifierThreshold", type: "u8" },
+          { name: "depositedLamports", type: "u64" },
+          { name: "depositorCount", type: "u16" },
+          { name: "refundPoolLamports", type: "u64" },
+          { name: "bump", type: "u8" },
+          { name: "version", type: "u8" },
+        ],
+      },
+    },
+    {
+      name: "TaskTombstone",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "graph", type: "publicKey" },
+          { name: "taskId", type: "u64" },
+          { name: "creator", type: "publicKey" },
+          { name: "completed", type: "bool" },
+          { name: "workerPct", type: "u8" },
+          { name: "parentTaskId", type: { option: "u64" } },
+          { name: "completedBy", type: { option: "publicKey" } },
+          { name: "closedAtSlot", type: "u64" },
+          { name: "bump", type: "u8" },
+          { name: "version", type: "u8" },
+        ],
+      },
+    },
+    {
+      name: "GraphTemplate",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "authority", type: "publicKey" },
+          { name: "templateId", type: "u64" },
+          { name: "tasks", type: { vec: { defined: "TemplateTask" } } },
           { name: "bump", type: "u8" },
+          { name: "version", type: "u8" },
         ],
       },
     },
@@ -155,6 +805,20 @@ export const BOUNTYGRAPH_IDL = {
         fields: [
           { name: "task", type: "publicKey" },
           { name: "bump", type: "u8" },
+          { name: "version", type: "u8" },
+        ],
+      },
+    },
+    {
+      name: "Deposit",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "task", type: "publicKey" },
+          { name: "funder", type: "publicKey" },
+          { name: "lamports", type: "u64" },
+          { name: "bump", type: "u8" },
+          { name: "version", type: "u8" },
         ],
       },
     },
@@ -168,7 +832,27 @@ export const BOUNTYGRAPH_IDL = {
           { name: "workHash", type: { array: ["u8", 32] } },
           { name: "uri", type: "string" },
           { name: "submittedAtSlot", type: "u64" },
+          { name: "payoutBps", type: "u16" },
+          { name: "claimed", type: "bool" },
+          { name: "contributors", type: { vec: { defined: "Contributor" } } },
+          { name: "milestoneIndex", type: { option: "u8" } },
+          { name: "completionIndex", type: { option: "u16" } },
+          { name: "bump", type: "u8" },
+          { name: "version", type: "u8" },
+        ],
+      },
+    },
+    {
+      name: "ReceiptCommitment",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "task", type: "publicKey" },
+          { name: "agent", type: "publicKey" },
+          { name: "commitment", type: { array: ["u8", 32] } },
+          { name: "committedAtSlot", type: "u64" },
           { name: "bump", type: "u8" },
+          { name: "version", type: "u8" },
         ],
       },
     },
@@ -188,7 +872,9 @@ export const BOUNTYGRAPH_IDL = {
           { name: "arbiter", type: { option: "publicKey" } },
           { name: "creatorPct", type: { option: "u8" } },
           { name: "workerPct", type: { option: "u8" } },
+          { name: "specRevision", type: "u32" },
           { name: "bump", type: "u8" },
+          { name: "version", type: "u8" },
         ],
       },
     },
@@ -198,7 +884,62 @@ export const BOUNTYGRAPH_IDL = {
       name: "InitializeGraphParams",
       type: {
         kind: "struct",
-        fields: [{ name: "maxDependenciesPerTask", type: "u16" }],
+        fields: [
+          { name: "graphId", type: "u64" },
+          { name: "maxDependenciesPerTask", type: "u16" },
+          { name: "creationPolicy", type: { defined: "CreationPolicy" } },
+          { name: "creationDepositLamports", type: "u64" },
+          { name: "guardian", type: { option: "publicKey" } },
+        ],
+      },
+    },
+    {
+      name: "UpdateGraphConfigParams",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "maxDependenciesPerTask", type: { option: "u16" } },
+          { name: "minRewardLamports", type: { option: "u64" } },
+          { name: "maxRewardLamports", type: { option: "u64" } },
+          { name: "closeRetentionSlots", type: { option: "u64" } },
+          { name: "graphFeeBps", type: { option: "u16" } },
+          { name: "requireDependentApproval", type: { option: "bool" } },
+          {
+            name: "dependencyDisputePolicy",
+            type: { option: { defined: "DependencyDisputePolicy" } },
+          },
+        ],
+      },
+    },
+    {
+      name: "InitializeProtocolConfigParams",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "treasury", type: "publicKey" },
+          { name: "feeBps", type: "u16" },
+        ],
+      },
+    },
+    {
+      name: "UpdateProtocolConfigParams",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "admin", type: { option: "publicKey" } },
+          { name: "treasury", type: { option: "publicKey" } },
+          { name: "feeBps", type: { option: "u16" } },
+        ],
+      },
+    },
+    {
+      name: "SetCreationPolicyParams",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "creationPolicy", type: { defined: "CreationPolicy" } },
+          { name: "creationDepositLamports", type: "u64" },
+        ],
       },
     },
     {
@@ -209,9 +950,35 @@ export const BOUNTYGRAPH_IDL = {
           { name: "taskId", type: "u64" },
           { name: "rewardLamports", type: "u64" },
           { name: "dependencies", type: { vec: "u64" } },
+          { name: "dependencyGroups", type: { vec: { defined: "DependencyGroup" } } },
+          { name: "externalDependencies", type: { vec: { defined: "ExternalTaskRef" } } },
+          { name: "claimDurationSlots", type: "u64" },
+          { name: "competitionDeadlineSlot", type: "u64" },
+          { name: "milestones", type: { vec: "u64" } },
+          { name: "vesting", type: { option: { defined: "VestingSchedule" } } },
+          { name: "crowdfunded", type: "bool" },
+          { name: "requireFunding", type: "bool" },
+          { name: "epic", type: "bool" },
+          { name: "parentTaskId", type: { option: "u64" } },
+          { name: "specUri", type: "string" },
+          { name: "specHash", type: { array: ["u8", 32] } },
+          { name: "category", type: "u16" },
+          { name: "tags", type: { vec: "u16" } },
+          { name: "maxCompletions", type: "u16" },
+          { name: "recurrenceIntervalSlots", type: "u64" },
+          { name: "recurrenceEndSlot", type: "u64" },
+          { name: "verifiers", type: { vec: "publicKey" } },
+          { name: "verifierThreshold", type: "u8" },
         ],
       },
     },
+    {
+      name: "SelectWinnersParams",
+      type: {
+        kind: "struct",
+        fields: [{ name: "weightsBps", type: { vec: "u16" } }],
+      },
+    },
     {
       name: "SubmitReceiptParams",
       type: {
@@ -219,6 +986,177 @@ export const BOUNTYGRAPH_IDL = {
         fields: [
           { name: "workHash", type: { array: ["u8", 32] } },
           { name: "uri", type: "string" },
+          { name: "contributors", type: { vec: { defined: "ContributorShare" } } },
+        ],
+      },
+    },
+    {
+      name: "SubmitMilestoneParams",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "index", type: "u8" },
+          { name: "workHash", type: { array: ["u8", 32] } },
+          { name: "uri", type: "string" },
+        ],
+      },
+    },
+    {
+      name: "RevealReceiptParams",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "workHash", type: { array: ["u8", 32] } },
+          { name: "uri", type: "string" },
+          { name: "salt", type: { array: ["u8", 32] } },
+        ],
+      },
+    },
+    {
+      name: "SubmitCompletionParams",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "workHash", type: { array: ["u8", 32] } },
+          { name: "uri", type: "string" },
+        ],
+      },
+    },
+    {
+      name: "DependencyDisputePolicy",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "blockWhileRaised", type: "bool" },
+          { name: "minWorkerPct", type: "u8" },
+        ],
+      },
+    },
+    {
+      name: "AmendTaskSpecParams",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "specUri", type: "string" },
+          { name: "specHash", type: { array: ["u8", 32] } },
+        ],
+      },
+    },
+    {
+      name: "TemplateTask",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "rewardLamports", type: "u64" },
+          { name: "dependencies", type: "bytes" },
+          { name: "claimDurationSlots", type: "u64" },
+          { name: "requireFunding", type: "bool" },
+          { name: "specUri", type: "string" },
+          { name: "specHash", type: { array: ["u8", 32] } },
+          { name: "category", type: "u16" },
+          { name: "tags", type: { vec: "u16" } },
+        ],
+      },
+    },
+    {
+      name: "InstantiateTemplateParams",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "taskIdOffset", type: "u64" },
+          { name: "rewardMultiplierBps", type: { option: "u32" } },
+        ],
+      },
+    },
+    {
+      name: "ExternalTaskRef",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "graph", type: "publicKey" },
+          { name: "taskId", type: "u64" },
+        ],
+      },
+    },
+    {
+      name: "ExternalDependency",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "graph", type: "publicKey" },
+          { name: "taskId", type: "u64" },
+          { name: "approved", type: "bool" },
+        ],
+      },
+    },
+    {
+      name: "DependencyGroup",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "threshold", type: "u8" },
+          { name: "taskIds", type: { vec: "u64" } },
+        ],
+      },
+    },
+    {
+      name: "TaskFunding",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "status", type: { defined: "TaskStatus" } },
+          { name: "rewardLamports", type: "u64" },
+          { name: "fundedLamports", type: "u64" },
+          { name: "fullyFunded", type: "bool" },
+        ],
+      },
+    },
+    {
+      name: "VestingSchedule",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "cliffSlots", type: "u64" },
+          { name: "durationSlots", type: "u64" },
+        ],
+      },
+    },
+    {
+      name: "Milestone",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "amountLamports", type: "u64" },
+          { name: "status", type: { defined: "MilestoneStatus" } },
+          { name: "worker", type: { option: "publicKey" } },
+        ],
+      },
+    },
+    {
+      name: "MilestoneStatus",
+      type: {
+        kind: "enum",
+        variants: [{ name: "Pending" }, { name: "Submitted" }, { name: "Paid" }],
+      },
+    },
+    {
+      name: "ContributorShare",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "worker", type: "publicKey" },
+          { name: "shareBps", type: "u16" },
+        ],
+      },
+    },
+    {
+      name: "Contributor",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "worker", type: "publicKey" },
+          { name: "shareBps", type: "u16" },
+          { name: "accepted", type: "bool" },
         ],
       },
     },
@@ -239,11 +1177,23 @@ export const BOUNTYGRAPH_IDL = {
         ],
       },
     },
+    {
+      name: "CreationPolicy",
+      type: {
+        kind: "enum",
+        variants: [{ name: "AuthorityOnly" }, { name: "Allowlist" }, { name: "Open" }],
+      },
+    },
     {
       name: "TaskStatus",
       type: {
         kind: "enum",
-        variants: [{ name: "Open" }, { name: "Completed" }],
+        variants: [
+          { name: "Open" },
+          { name: "Completed" },
+          { name: "Cancelled" },
+          { name: "Draft" },
+        ],
       },
     },
     {
@@ -254,6 +1204,63 @@ export const BOUNTYGRAPH_IDL = {
       },
     },
   ],
+  events: [
+    {
+      name: "FeesCollected",
+      fields: [
+        { name: "task", type: "publicKey", index: false },
+        { name: "grossLamports", type: "u64", index: false },
+        { name: "protocolFeeLamports", type: "u64", index: false },
+        { name: "graphFeeLamports", type: "u64", index: false },
+        { name: "treasury", type: "publicKey", index: false },
+        { name: "graphFeeRecipient", type: "publicKey", index: false },
+      ],
+    },
+    {
+      name: "RewardIncreased",
+      fields: [
+        { name: "task", type: "publicKey", index: false },
+        { name: "funder", type: "publicKey", index: false },
+        { name: "addedLamports", type: "u64", index: false },
+        { name: "newRewardLamports", type: "u64", index: false },
+      ],
+    },
+    {
+      name: "TaskCreated",
+      fields: [
+        { name: "graph", type: "publicKey", index: false },
+        { name: "task", type: "publicKey", index: false },
+        { name: "taskId", type: "u64", index: false },
+        { name: "creator", type: "publicKey", index: false },
+        { name: "category", type: "u16", index: false },
+        { name: "tags", type: { array: ["u16", 4] }, index: false },
+        { name: "specHash", type: { array: ["u8", 32] }, index: false },
+      ],
+    },
+    {
+      name: "TaskSpecAmended",
+      fields: [
+        { name: "task", type: "publicKey", index: false },
+        { name: "revision", type: "u32", index: false },
+        { name: "previousSpecHash", type: { array: ["u8", 32] }, index: false },
+        { name: "specHash", type: { array: ["u8", 32] }, index: false },
+        { name: "specUri", type: "string", index: false },
+        { name: "countersignedBy", type: { option: "publicKey" }, index: false },
+        { name: "slot", type: "u64", index: false },
+      ],
+    },
+    {
+      name: "ReceiptAttested",
+      fields: [
+        { name: "task", type: "publicKey", index: false },
+        { name: "receipt", type: "publicKey", index: false },
+        { name: "agent", type: "publicKey", index: false },
+        { name: "workHash", type: { array: ["u8", 32] }, index: false },
+        { name: "attestations", type: "u8", index: false },
+        { name: "slot", type: "u64", index: false },
+      ],
+    },
+  ],
   metadata: {
     address: "Ghm5zPnHy5yJwQ6P22NYgNVrqPokDqAV3otdut3DSbSS",
   },